@@ -0,0 +1,35 @@
+//! Regenerates the embedded dream noise look-up tables.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/core/src/dream/spec.md`
+//!
+//! Usage: `cargo run --example generate_noise_luts [OUTPUT_DIR]`
+//! (defaults to `src/dream/lut`).
+
+use std::path::PathBuf;
+
+use chromatic_core::dream::{NoiseProfile, NoiseTable, NOISE_LUT_SIZE};
+use chromatic_core::utils::crc32;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/dream/lut"));
+    std::fs::create_dir_all(&out_dir)?;
+    for profile in NoiseProfile::ALL {
+        let table =
+            NoiseTable::generate(profile, NOISE_LUT_SIZE, NOISE_LUT_SIZE, profile.lut_seed());
+        let bytes = table.to_bytes();
+        let path = out_dir.join(profile.file_name());
+        std::fs::write(&path, &bytes)?;
+        println!(
+            "{:<9} {} ({} bytes, crc32 {:08x})",
+            profile.name(),
+            path.display(),
+            bytes.len(),
+            crc32(&bytes[..bytes.len() - 4])
+        );
+    }
+    Ok(())
+}
@@ -14,6 +14,14 @@ use std::f32::consts::PI;
 
 use crate::{tensor::*, Fx, HUE_CATEGORIES};
 
+mod ums_io;
+
+pub use ums_io::{
+    decode_ums_batch, decode_ums_json, decode_ums_vector, encode_ums_batch, encode_ums_json,
+    encode_ums_vector, read_ums_file, write_ums_file, UmsEncoding, UmsFile, UmsHeader, UmsReader,
+    UmsRecord, UmsWriter, UMS_HEADER_LEN, UMS_LAYOUT_VERSION, UMS_MAGIC,
+};
+
 <<<<<<< ours
 <<<<<<< ours
 <<<<<<< ours
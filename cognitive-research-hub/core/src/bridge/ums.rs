@@ -156,6 +156,11 @@ impl Default for UnifiedModalitySpace {
 =======
 >>>>>>> theirs
 impl CompressedUnifiedModality {
+    /// Rebuilds a compressed vector from its half-precision payload and μ/σ statistics.
+    pub fn from_parts(data: [u16; UMS_DIM], mean: Fx, std: Fx) -> Self {
+        Self { data, mean, std }
+    }
+
     /// Returns the stored global mean used for μ/σ normalisation.
     pub fn mean(&self) -> Fx {
         self.mean
@@ -0,0 +1,849 @@
+//! Binary persistence for Unified Modality Space vectors.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/bridge/spec.md`
+//!
+//! A `.ums` archive is laid out little-endian as:
+//!
+//! | Field | Size | Notes |
+//! | --- | --- | --- |
+//! | magic | 4 | `CUMS` |
+//! | layout version | 2 | [`UMS_LAYOUT_VERSION`] |
+//! | encoding | 1 | `0` = f32 vectors, `1` = μ/σ + f16 vectors |
+//! | reserved | 1 | always zero |
+//! | dimension | 4 | must equal `UMS_DIM` |
+//! | count | 8 | number of records |
+//! | payload | count × (record + 4) | fixed-size records, each followed by its CRC-32 |
+//! | checksum | 4 | CRC-32 over header and record payloads |
+//!
+//! The archive checksum skips the record checksums: a CRC run over data
+//! followed by that data's own CRC no longer depends on the data. The
+//! per-record checksums let [`UmsFile`] seek to one record and verify it
+//! without touching the rest of the archive; the streaming [`UmsReader`]
+//! checks both levels.
+//!
+//! Single vectors use the same container with a count of one, so every file
+//! the bridge writes can be opened by the batch readers. The JSON mirror
+//! carries the same header fields plus the archive checksum of the equivalent
+//! binary archive, so either form can be validated against the other.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use super::{CompressedUnifiedModality, UnifiedModalitySpace};
+use crate::{
+    error::{CoreResult, DreamError},
+    utils::{crc32, Crc32, JsonValue},
+    Fx, UMS_DIM,
+};
+
+/// Magic bytes opening every `.ums` archive.
+pub const UMS_MAGIC: [u8; 4] = *b"CUMS";
+/// Layout version written by this build of the bridge.
+pub const UMS_LAYOUT_VERSION: u16 = 1;
+/// Size in bytes of the fixed `.ums` header.
+pub const UMS_HEADER_LEN: usize = 20;
+/// Size in bytes of the trailing checksum and of each record checksum.
+const UMS_CHECKSUM_LEN: usize = 4;
+
+/// Payload encoding stored inside a `.ums` archive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UmsEncoding {
+    /// Raw `f32` vectors ([`UnifiedModalitySpace`]).
+    Full,
+    /// μ/σ-normalised half-precision vectors ([`CompressedUnifiedModality`]).
+    Half,
+}
+
+impl UmsEncoding {
+    fn tag(self) -> u8 {
+        match self {
+            UmsEncoding::Full => 0,
+            UmsEncoding::Half => 1,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            UmsEncoding::Full => "full",
+            UmsEncoding::Half => "half",
+        }
+    }
+
+    fn from_tag(tag: u8) -> CoreResult<Self> {
+        match tag {
+            0 => Ok(UmsEncoding::Full),
+            1 => Ok(UmsEncoding::Half),
+            other => Err(DreamError::Bridge(format!(
+                "unknown UMS payload encoding {}",
+                other
+            ))),
+        }
+    }
+
+    /// Returns the size in bytes of a single record with this encoding.
+    pub fn record_len(self) -> usize {
+        match self {
+            UmsEncoding::Full => UMS_DIM.saturating_mul(4),
+            UmsEncoding::Half => UMS_DIM.saturating_mul(2).saturating_add(8),
+        }
+    }
+
+    /// Returns the on-disk size of a record including its checksum.
+    pub fn stride(self) -> usize {
+        self.record_len().saturating_add(UMS_CHECKSUM_LEN)
+    }
+}
+
+/// Decoded `.ums` header describing the payload that follows it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UmsHeader {
+    pub version: u16,
+    pub encoding: UmsEncoding,
+    pub dim: u32,
+    pub count: u64,
+}
+
+impl UmsHeader {
+    fn new(encoding: UmsEncoding, count: u64) -> Self {
+        Self {
+            version: UMS_LAYOUT_VERSION,
+            encoding,
+            dim: UMS_DIM as u32,
+            count,
+        }
+    }
+
+    fn to_bytes(self) -> [u8; UMS_HEADER_LEN] {
+        let mut bytes = [0u8; UMS_HEADER_LEN];
+        bytes[0..4].copy_from_slice(&UMS_MAGIC);
+        bytes[4..6].copy_from_slice(&self.version.to_le_bytes());
+        bytes[6] = self.encoding.tag();
+        bytes[8..12].copy_from_slice(&self.dim.to_le_bytes());
+        bytes[12..20].copy_from_slice(&self.count.to_le_bytes());
+        bytes
+    }
+
+    fn parse(bytes: &[u8]) -> CoreResult<Self> {
+        if bytes.len() < UMS_HEADER_LEN {
+            return Err(DreamError::Bridge("truncated UMS header".to_string()));
+        }
+        if bytes[0..4] != UMS_MAGIC {
+            return Err(DreamError::Bridge("missing UMS magic bytes".to_string()));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != UMS_LAYOUT_VERSION {
+            return Err(DreamError::Bridge(format!(
+                "unsupported UMS layout version {} (expected {})",
+                version, UMS_LAYOUT_VERSION
+            )));
+        }
+        let encoding = UmsEncoding::from_tag(bytes[6])?;
+        let dim = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+        if dim as usize != UMS_DIM {
+            return Err(DreamError::Bridge(format!(
+                "UMS dimension mismatch: file has {}, build expects {}",
+                dim, UMS_DIM
+            )));
+        }
+        let mut count_bytes = [0u8; 8];
+        count_bytes.copy_from_slice(&bytes[12..20]);
+        Ok(Self {
+            version,
+            encoding,
+            dim,
+            count: u64::from_le_bytes(count_bytes),
+        })
+    }
+
+    /// Returns the number of records as `usize`, rejecting counts the platform cannot address.
+    pub fn record_count(&self) -> CoreResult<usize> {
+        usize::try_from(self.count).map_err(|_| {
+            DreamError::Bridge(format!("UMS record count {} exceeds usize", self.count))
+        })
+    }
+}
+
+/// Vector types that can be stored as fixed-size `.ums` records.
+pub trait UmsRecord: Sized {
+    /// Encoding tag written into the archive header.
+    const ENCODING: UmsEncoding;
+
+    /// Appends the little-endian record bytes to `out`.
+    fn encode_record(&self, out: &mut Vec<u8>);
+
+    /// Decodes a record from exactly `ENCODING.record_len()` bytes.
+    fn decode_record(bytes: &[u8]) -> Self;
+
+    /// Converts the record into its JSON mirror.
+    fn to_json(&self) -> JsonValue;
+
+    /// Rebuilds a record from its JSON mirror.
+    fn from_json(value: &JsonValue) -> CoreResult<Self>;
+}
+
+fn json_array<'a>(value: &'a JsonValue, key: &str) -> CoreResult<&'a [JsonValue]> {
+    let items = value
+        .get(key)
+        .and_then(JsonValue::as_array)
+        .ok_or_else(|| DreamError::Bridge(format!("UMS JSON record missing `{}` array", key)))?;
+    if items.len() != UMS_DIM {
+        return Err(DreamError::Bridge(format!(
+            "UMS JSON `{}` has {} entries, expected {}",
+            key,
+            items.len(),
+            UMS_DIM
+        )));
+    }
+    Ok(items)
+}
+
+fn json_f32(value: &JsonValue, key: &str) -> CoreResult<Fx> {
+    value
+        .get(key)
+        .and_then(JsonValue::as_f64)
+        .map(|v| v as Fx)
+        .ok_or_else(|| DreamError::Bridge(format!("UMS JSON missing numeric `{}`", key)))
+}
+
+fn read_f32(bytes: &[u8], offset: usize) -> Fx {
+    Fx::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+/// Appends `record` and its CRC-32 to `out`.
+fn push_record<T: UmsRecord>(record: &T, out: &mut Vec<u8>) {
+    let start = out.len();
+    record.encode_record(out);
+    let checksum = crc32(&out[start..]);
+    out.extend_from_slice(&checksum.to_le_bytes());
+}
+
+/// Verifies one stored record and decodes it.
+fn check_record<T: UmsRecord>(stored: &[u8], index: u64) -> CoreResult<T> {
+    let (payload, trailer) = stored.split_at(T::ENCODING.record_len());
+    let expected = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    let computed = crc32(payload);
+    if expected != computed {
+        return Err(DreamError::Validation(format!(
+            "UMS record {} checksum mismatch: stored {:08x}, computed {:08x}",
+            index, expected, computed
+        )));
+    }
+    Ok(T::decode_record(payload))
+}
+
+impl UmsRecord for UnifiedModalitySpace {
+    const ENCODING: UmsEncoding = UmsEncoding::Full;
+
+    fn encode_record(&self, out: &mut Vec<u8>) {
+        for value in self.as_slice() {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    fn decode_record(bytes: &[u8]) -> Self {
+        let mut data = [0.0; UMS_DIM];
+        for (idx, value) in data.iter_mut().enumerate() {
+            *value = read_f32(bytes, idx.saturating_mul(4));
+        }
+        UnifiedModalitySpace::from_array(data)
+    }
+
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object().with("data", self.as_slice().to_vec())
+    }
+
+    fn from_json(value: &JsonValue) -> CoreResult<Self> {
+        let items = json_array(value, "data")?;
+        let mut data = [0.0; UMS_DIM];
+        for (slot, item) in data.iter_mut().zip(items) {
+            *slot = item
+                .as_f64()
+                .ok_or_else(|| DreamError::Bridge("non-numeric UMS JSON value".to_string()))?
+                as Fx;
+        }
+        Ok(UnifiedModalitySpace::from_array(data))
+    }
+}
+
+impl UmsRecord for CompressedUnifiedModality {
+    const ENCODING: UmsEncoding = UmsEncoding::Half;
+
+    fn encode_record(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.mean().to_le_bytes());
+        out.extend_from_slice(&self.std().to_le_bytes());
+        for bits in self.data() {
+            out.extend_from_slice(&bits.to_le_bytes());
+        }
+    }
+
+    fn decode_record(bytes: &[u8]) -> Self {
+        let mean = read_f32(bytes, 0);
+        let std = read_f32(bytes, 4);
+        let mut data = [0u16; UMS_DIM];
+        for (idx, bits) in data.iter_mut().enumerate() {
+            let offset = idx.saturating_mul(2).saturating_add(8);
+            *bits = u16::from_le_bytes([bytes[offset], bytes[offset + 1]]);
+        }
+        CompressedUnifiedModality::from_parts(data, mean, std)
+    }
+
+    fn to_json(&self) -> JsonValue {
+        let bits: Vec<u32> = self.data().iter().map(|&b| b as u32).collect();
+        JsonValue::object()
+            .with("mean", self.mean())
+            .with("std", self.std())
+            .with("data", bits)
+    }
+
+    fn from_json(value: &JsonValue) -> CoreResult<Self> {
+        let mean = json_f32(value, "mean")?;
+        let std = json_f32(value, "std")?;
+        let items = json_array(value, "data")?;
+        let mut data = [0u16; UMS_DIM];
+        for (slot, item) in data.iter_mut().zip(items) {
+            *slot = item
+                .as_u64()
+                .and_then(|v| u16::try_from(v).ok())
+                .ok_or_else(|| DreamError::Bridge("invalid f16 payload in UMS JSON".to_string()))?;
+        }
+        Ok(CompressedUnifiedModality::from_parts(data, mean, std))
+    }
+}
+
+/// Streaming `.ums` writer that emits records as they arrive.
+///
+/// The record count is declared up front so the header can be written before
+/// any payload; [`UmsWriter::finish`] rejects archives whose count does not
+/// match and appends the checksum.
+pub struct UmsWriter<W: Write, T: UmsRecord> {
+    inner: W,
+    header: UmsHeader,
+    written: u64,
+    hasher: Crc32,
+    buffer: Vec<u8>,
+    _record: PhantomData<T>,
+}
+
+impl<W: Write, T: UmsRecord> UmsWriter<W, T> {
+    /// Writes the header for `count` records and returns the writer.
+    pub fn new(mut inner: W, count: u64) -> CoreResult<Self> {
+        let header = UmsHeader::new(T::ENCODING, count);
+        let bytes = header.to_bytes();
+        let mut hasher = Crc32::new();
+        hasher.update(&bytes);
+        inner.write_all(&bytes)?;
+        Ok(Self {
+            inner,
+            header,
+            written: 0,
+            hasher,
+            buffer: Vec::with_capacity(T::ENCODING.stride()),
+            _record: PhantomData,
+        })
+    }
+
+    /// Returns the header written at the start of the archive.
+    pub fn header(&self) -> UmsHeader {
+        self.header
+    }
+
+    /// Appends a single record to the archive.
+    pub fn write_record(&mut self, record: &T) -> CoreResult<()> {
+        if self.written >= self.header.count {
+            return Err(DreamError::Bridge(format!(
+                "UMS writer declared {} records; refusing to write more",
+                self.header.count
+            )));
+        }
+        self.buffer.clear();
+        push_record(record, &mut self.buffer);
+        self.hasher.update(&self.buffer[..T::ENCODING.record_len()]);
+        self.inner.write_all(&self.buffer)?;
+        self.written = self.written.saturating_add(1);
+        Ok(())
+    }
+
+    /// Writes the trailing checksum, flushes, and returns the underlying writer.
+    pub fn finish(mut self) -> CoreResult<W> {
+        if self.written != self.header.count {
+            return Err(DreamError::Bridge(format!(
+                "UMS writer declared {} records but received {}",
+                self.header.count, self.written
+            )));
+        }
+        self.inner.write_all(&self.hasher.finish().to_le_bytes())?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+/// Streaming `.ums` reader yielding records in file order.
+///
+/// Each record's checksum is verified as it is read, and the archive checksum
+/// once the final record has been consumed; mismatches surface as
+/// [`DreamError::Validation`].
+pub struct UmsReader<R: Read, T: UmsRecord> {
+    inner: R,
+    header: UmsHeader,
+    remaining: u64,
+    hasher: Crc32,
+    buffer: Vec<u8>,
+    finished: bool,
+    _record: PhantomData<T>,
+}
+
+impl<R: Read, T: UmsRecord> UmsReader<R, T> {
+    /// Reads and validates the header, preparing to stream records.
+    pub fn new(mut inner: R) -> CoreResult<Self> {
+        let mut bytes = [0u8; UMS_HEADER_LEN];
+        inner.read_exact(&mut bytes)?;
+        let header = UmsHeader::parse(&bytes)?;
+        if header.encoding != T::ENCODING {
+            return Err(DreamError::Bridge(format!(
+                "UMS payload encoding {:?} does not match requested {:?}",
+                header.encoding,
+                T::ENCODING
+            )));
+        }
+        let mut hasher = Crc32::new();
+        hasher.update(&bytes);
+        Ok(Self {
+            inner,
+            header,
+            remaining: header.count,
+            hasher,
+            buffer: vec![0u8; T::ENCODING.stride()],
+            finished: false,
+            _record: PhantomData,
+        })
+    }
+
+    /// Returns the archive header.
+    pub fn header(&self) -> UmsHeader {
+        self.header
+    }
+
+    /// Reads the next record, returning `Ok(None)` once the checksum has been verified.
+    pub fn read_record(&mut self) -> CoreResult<Option<T>> {
+        if self.finished {
+            return Ok(None);
+        }
+        if self.remaining == 0 {
+            self.finished = true;
+            let mut trailer = [0u8; UMS_CHECKSUM_LEN];
+            self.inner.read_exact(&mut trailer)?;
+            let stored = u32::from_le_bytes(trailer);
+            let computed = self.hasher.finish();
+            if stored != computed {
+                return Err(DreamError::Validation(format!(
+                    "UMS checksum mismatch: stored {:08x}, computed {:08x}",
+                    stored, computed
+                )));
+            }
+            return Ok(None);
+        }
+        self.inner.read_exact(&mut self.buffer)?;
+        self.hasher.update(&self.buffer[..T::ENCODING.record_len()]);
+        let index = self.header.count - self.remaining;
+        self.remaining -= 1;
+        check_record(&self.buffer, index).map(Some)
+    }
+}
+
+impl<R: Read, T: UmsRecord> Iterator for UmsReader<R, T> {
+    type Item = CoreResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_record() {
+            Ok(Some(record)) => Some(Ok(record)),
+            Ok(None) => None,
+            Err(err) => {
+                self.finished = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// `.ums` archive opened for random access by record index.
+///
+/// Opening reads only the header and checks the archive length. [`UmsFile::get`]
+/// seeks to `header + index × stride` and reads and verifies that one record,
+/// so the rest of the archive is never loaded. The archive-level checksum is
+/// only checked by [`UmsReader`], which reads everything anyway.
+#[derive(Debug)]
+pub struct UmsFile<T: UmsRecord, R: Read + Seek = File> {
+    source: R,
+    header: UmsHeader,
+    count: usize,
+    buffer: Vec<u8>,
+    _record: PhantomData<T>,
+}
+
+impl<T: UmsRecord> UmsFile<T> {
+    /// Opens the archive at `path` and validates its header and length.
+    pub fn open<P: AsRef<Path>>(path: P) -> CoreResult<Self> {
+        Self::from_reader(File::open(path)?)
+    }
+}
+
+impl<T: UmsRecord, R: Read + Seek> UmsFile<T, R> {
+    /// Validates the header and length of the archive in `source`.
+    pub fn from_reader(mut source: R) -> CoreResult<Self> {
+        let mut bytes = [0u8; UMS_HEADER_LEN];
+        source.seek(SeekFrom::Start(0))?;
+        source.read_exact(&mut bytes)?;
+        let header = UmsHeader::parse(&bytes)?;
+        if header.encoding != T::ENCODING {
+            return Err(DreamError::Bridge(format!(
+                "UMS payload encoding {:?} does not match requested {:?}",
+                header.encoding,
+                T::ENCODING
+            )));
+        }
+        let count = header.record_count()?;
+        let expected = count
+            .checked_mul(T::ENCODING.stride())
+            .and_then(|payload| payload.checked_add(UMS_HEADER_LEN + UMS_CHECKSUM_LEN))
+            .ok_or_else(|| DreamError::Bridge("UMS archive size overflows".to_string()))?;
+        let actual = source.seek(SeekFrom::End(0))?;
+        if actual != expected as u64 {
+            return Err(DreamError::Bridge(format!(
+                "UMS archive length {} does not match expected {}",
+                actual, expected
+            )));
+        }
+        Ok(Self {
+            source,
+            header,
+            count,
+            buffer: vec![0u8; T::ENCODING.stride()],
+            _record: PhantomData,
+        })
+    }
+
+    /// Returns the archive header.
+    pub fn header(&self) -> UmsHeader {
+        self.header
+    }
+
+    /// Returns the number of records in the archive.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns true when the archive holds no records.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Reads and verifies the record at `index`, or `None` when out of range.
+    pub fn get(&mut self, index: usize) -> CoreResult<Option<T>> {
+        if index >= self.count {
+            return Ok(None);
+        }
+        let offset = UMS_HEADER_LEN + index * T::ENCODING.stride();
+        self.source.seek(SeekFrom::Start(offset as u64))?;
+        self.source.read_exact(&mut self.buffer)?;
+        check_record(&self.buffer, index as u64).map(Some)
+    }
+}
+
+/// Encodes a single vector as a self-describing `.ums` byte buffer.
+pub fn encode_ums_vector<T: UmsRecord>(record: &T) -> Vec<u8> {
+    encode_ums_batch(std::slice::from_ref(record))
+}
+
+/// Decodes a buffer produced by [`encode_ums_vector`].
+pub fn decode_ums_vector<T: UmsRecord>(bytes: &[u8]) -> CoreResult<T> {
+    let mut records = decode_ums_batch::<T>(bytes)?;
+    if records.len() != 1 {
+        return Err(DreamError::Bridge(format!(
+            "expected a single UMS vector, found {}",
+            records.len()
+        )));
+    }
+    Ok(records.remove(0))
+}
+
+/// Encodes a batch of vectors into an in-memory `.ums` archive.
+pub fn encode_ums_batch<T: UmsRecord>(records: &[T]) -> Vec<u8> {
+    let capacity = records
+        .len()
+        .saturating_mul(T::ENCODING.stride())
+        .saturating_add(UMS_HEADER_LEN + UMS_CHECKSUM_LEN);
+    let mut bytes = Vec::with_capacity(capacity);
+    bytes.extend_from_slice(&UmsHeader::new(T::ENCODING, records.len() as u64).to_bytes());
+    let mut hasher = Crc32::new();
+    hasher.update(&bytes);
+    for record in records {
+        let start = bytes.len();
+        push_record(record, &mut bytes);
+        hasher.update(&bytes[start..bytes.len() - UMS_CHECKSUM_LEN]);
+    }
+    bytes.extend_from_slice(&hasher.finish().to_le_bytes());
+    bytes
+}
+
+/// Archive checksum the binary form of `records` would carry.
+fn batch_checksum<T: UmsRecord>(records: &[T]) -> u32 {
+    let binary = encode_ums_batch(records);
+    let trailer = &binary[binary.len() - UMS_CHECKSUM_LEN..];
+    u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]])
+}
+
+/// Decodes and verifies every record of an in-memory `.ums` archive.
+pub fn decode_ums_batch<T: UmsRecord>(bytes: &[u8]) -> CoreResult<Vec<T>> {
+    let mut rest = bytes;
+    let records = UmsReader::<_, T>::new(&mut rest)?.collect::<CoreResult<Vec<T>>>()?;
+    if !rest.is_empty() {
+        return Err(DreamError::Bridge(format!(
+            "UMS archive has {} trailing bytes",
+            rest.len()
+        )));
+    }
+    Ok(records)
+}
+
+/// Writes `records` to a `.ums` file at `path` using the streaming writer.
+pub fn write_ums_file<T: UmsRecord, P: AsRef<Path>>(path: P, records: &[T]) -> CoreResult<()> {
+    let file = File::create(path)?;
+    let mut writer = UmsWriter::<_, T>::new(BufWriter::new(file), records.len() as u64)?;
+    for record in records {
+        writer.write_record(record)?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Reads every record of the `.ums` file at `path` using the streaming reader.
+pub fn read_ums_file<T: UmsRecord, P: AsRef<Path>>(path: P) -> CoreResult<Vec<T>> {
+    let file = File::open(path)?;
+    let reader = UmsReader::<_, T>::new(BufReader::new(file))?;
+    reader.collect()
+}
+
+/// Encodes a batch of vectors as a deterministic JSON document.
+pub fn encode_ums_json<T: UmsRecord>(records: &[T]) -> String {
+    let checksum = batch_checksum(records);
+    let items: Vec<JsonValue> = records.iter().map(UmsRecord::to_json).collect();
+    JsonValue::object()
+        .with("magic", String::from_utf8_lossy(&UMS_MAGIC).into_owned())
+        .with("layout_version", UMS_LAYOUT_VERSION as u32)
+        .with("encoding", T::ENCODING.name())
+        .with("dim", UMS_DIM)
+        .with("count", records.len())
+        .with("records", JsonValue::Array(items))
+        .with("checksum", checksum)
+        .to_json_string()
+}
+
+/// Decodes a JSON document produced by [`encode_ums_json`], verifying its checksum.
+pub fn decode_ums_json<T: UmsRecord>(text: &str) -> CoreResult<Vec<T>> {
+    let doc = JsonValue::parse(text)?;
+    let field = |key: &str| {
+        doc.get(key)
+            .ok_or_else(|| DreamError::Bridge(format!("UMS JSON missing `{}`", key)))
+    };
+    if field("magic")?.as_str() != Some("CUMS") {
+        return Err(DreamError::Bridge("missing UMS magic in JSON".to_string()));
+    }
+    if field("layout_version")?.as_u64() != Some(UMS_LAYOUT_VERSION as u64) {
+        return Err(DreamError::Bridge(
+            "unsupported UMS JSON layout version".to_string(),
+        ));
+    }
+    if field("encoding")?.as_str() != Some(T::ENCODING.name()) {
+        return Err(DreamError::Bridge(format!(
+            "UMS JSON encoding does not match requested {:?}",
+            T::ENCODING
+        )));
+    }
+    if field("dim")?.as_u64() != Some(UMS_DIM as u64) {
+        return Err(DreamError::Bridge(
+            "UMS JSON dimension mismatch".to_string(),
+        ));
+    }
+    let items = field("records")?
+        .as_array()
+        .ok_or_else(|| DreamError::Bridge("UMS JSON `records` must be an array".to_string()))?;
+    if field("count")?.as_u64() != Some(items.len() as u64) {
+        return Err(DreamError::Bridge(
+            "UMS JSON count does not match records".to_string(),
+        ));
+    }
+    let records = items
+        .iter()
+        .map(T::from_json)
+        .collect::<CoreResult<Vec<T>>>()?;
+    let stored = field("checksum")?
+        .as_u64()
+        .ok_or_else(|| DreamError::Bridge("UMS JSON checksum must be an integer".to_string()))?;
+    let computed = batch_checksum(&records);
+    if stored != computed as u64 {
+        return Err(DreamError::Validation(format!(
+            "UMS JSON checksum mismatch: stored {:08x}, computed {:08x}",
+            stored, computed
+        )));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bridge::compress_ums;
+
+    fn sample_ums(seed: Fx) -> UnifiedModalitySpace {
+        let mut data = [0.0; UMS_DIM];
+        for (idx, value) in data.iter_mut().enumerate() {
+            *value = seed + (idx as Fx * 0.013).sin();
+        }
+        UnifiedModalitySpace::from_array(data)
+    }
+
+    #[test]
+    fn single_vector_round_trip_is_bit_exact() {
+        let ums = sample_ums(0.25);
+        let bytes = encode_ums_vector(&ums);
+        assert_eq!(
+            bytes.len(),
+            UMS_HEADER_LEN + UmsEncoding::Full.stride() + UMS_CHECKSUM_LEN
+        );
+        let restored: UnifiedModalitySpace = decode_ums_vector(&bytes).unwrap();
+        assert_eq!(restored, ums);
+    }
+
+    #[test]
+    fn compressed_vector_round_trip_preserves_payload() {
+        let compressed = compress_ums(&sample_ums(-0.5));
+        let bytes = encode_ums_vector(&compressed);
+        let restored: CompressedUnifiedModality = decode_ums_vector(&bytes).unwrap();
+        assert_eq!(restored, compressed);
+    }
+
+    #[test]
+    fn streaming_writer_and_reader_agree() {
+        let batch: Vec<_> = (0..4).map(|i| sample_ums(i as Fx * 0.1)).collect();
+        let mut writer = UmsWriter::<_, UnifiedModalitySpace>::new(Vec::new(), 4).unwrap();
+        for ums in &batch {
+            writer.write_record(ums).unwrap();
+        }
+        let bytes = writer.finish().unwrap();
+        assert_eq!(bytes, encode_ums_batch(&batch));
+
+        let reader = UmsReader::<_, UnifiedModalitySpace>::new(bytes.as_slice()).unwrap();
+        assert_eq!(reader.header().count, 4);
+        let restored: Vec<_> = reader.collect::<CoreResult<_>>().unwrap();
+        assert_eq!(restored, batch);
+    }
+
+    #[test]
+    fn writer_rejects_count_mismatch() {
+        let mut writer = UmsWriter::<_, UnifiedModalitySpace>::new(Vec::new(), 2).unwrap();
+        writer.write_record(&sample_ums(0.0)).unwrap();
+        assert!(writer.finish().is_err());
+    }
+
+    /// Counts the bytes read through it.
+    struct Counting<R> {
+        inner: R,
+        read: usize,
+    }
+
+    impl<R: Read> Read for Counting<R> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let n = self.inner.read(buf)?;
+            self.read += n;
+            Ok(n)
+        }
+    }
+
+    impl<R: Seek> Seek for Counting<R> {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    #[test]
+    fn indexed_reads_touch_only_the_requested_record() {
+        let batch: Vec<_> = (0..5).map(|i| compress_ums(&sample_ums(i as Fx))).collect();
+        let mut bytes = encode_ums_batch(&batch);
+        // Damage record 0; reading record 3 must neither load nor check it.
+        bytes[UMS_HEADER_LEN + 9] ^= 0x40;
+        let source = Counting {
+            inner: std::io::Cursor::new(bytes),
+            read: 0,
+        };
+        let mut archive = UmsFile::<CompressedUnifiedModality, _>::from_reader(source).unwrap();
+        assert_eq!(archive.len(), 5);
+        assert_eq!(archive.source.read, UMS_HEADER_LEN);
+        assert_eq!(archive.get(3).unwrap(), Some(batch[3].clone()));
+        assert_eq!(
+            archive.source.read,
+            UMS_HEADER_LEN + UmsEncoding::Half.stride()
+        );
+        assert!(matches!(archive.get(0), Err(DreamError::Validation(_))));
+        assert!(archive.get(5).unwrap().is_none());
+    }
+
+    #[test]
+    fn corrupted_payload_fails_checksum() {
+        let mut bytes = encode_ums_batch(&[sample_ums(0.3), sample_ums(0.6)]);
+        bytes[UMS_HEADER_LEN + 17] ^= 0x40;
+        let decoded = decode_ums_batch::<UnifiedModalitySpace>(&bytes);
+        assert!(matches!(decoded, Err(DreamError::Validation(_))));
+        let streamed: CoreResult<Vec<UnifiedModalitySpace>> =
+            UmsReader::new(bytes.as_slice()).unwrap().collect();
+        assert!(matches!(streamed, Err(DreamError::Validation(_))));
+        let mut archive =
+            UmsFile::<UnifiedModalitySpace, _>::from_reader(std::io::Cursor::new(bytes)).unwrap();
+        assert!(matches!(archive.get(0), Err(DreamError::Validation(_))));
+        assert!(archive.get(1).unwrap().is_some());
+    }
+
+    #[test]
+    fn json_mirror_round_trips_both_encodings() {
+        let full = vec![sample_ums(0.2), sample_ums(-0.4)];
+        let text = encode_ums_json(&full);
+        assert_eq!(
+            decode_ums_json::<UnifiedModalitySpace>(&text).unwrap(),
+            full
+        );
+        assert_eq!(text, encode_ums_json(&full));
+
+        let half: Vec<_> = full.iter().map(compress_ums).collect();
+        let text = encode_ums_json(&half);
+        assert_eq!(
+            decode_ums_json::<CompressedUnifiedModality>(&text).unwrap(),
+            half
+        );
+    }
+
+    #[test]
+    fn json_checksum_detects_tampering() {
+        let text = encode_ums_json(&[sample_ums(0.7)]);
+        let tampered = text.replacen("[0.69", "[0.79", 1);
+        assert_ne!(tampered, text);
+        assert!(matches!(
+            decode_ums_json::<UnifiedModalitySpace>(&tampered),
+            Err(DreamError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn encoding_mismatch_is_rejected() {
+        let bytes = encode_ums_vector(&sample_ums(0.1));
+        assert!(decode_ums_vector::<CompressedUnifiedModality>(&bytes).is_err());
+    }
+}
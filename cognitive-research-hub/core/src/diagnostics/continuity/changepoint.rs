@@ -0,0 +1,328 @@
+//! CUSUM and PELT changepoint detection over metric series.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/continuity/spec.md`
+//!
+//! Both detectors treat a steady slope as the normal state, so a ramp is one
+//! regime while a level jump or a change of slope starts a new one.
+//!
+//! - CUSUM runs on consecutive differences against the median of a warmup
+//!   window, in units of the noise scale. It is online: each alarm reports the
+//!   onset and the sample at which it fired.
+//! - PELT finds the exact optimal segmentation into straight lines under a
+//!   Gaussian cost, `RSS / σ²` per segment plus `penalty` per changepoint,
+//!   using prefix sums so each segment cost is O(1).
+//!
+//! A changepoint is the first sample off the previous regime's line. At a
+//! change of slope the vertex lies on both lines and stays with the earlier
+//! regime, so both detectors report the sample after it.
+//!
+//! The noise scale is the median absolute second difference divided by
+//! `0.6745·√6`, which ignores linear trends and a few isolated jumps.
+
+use super::trend::ordered_series;
+use crate::{diagnostics::metrics::CycleRecord, Fx};
+
+/// Noise floor that keeps noise-free series from dividing by zero.
+pub const MIN_NOISE_SIGMA: Fx = 1e-3;
+/// Fewest samples in a PELT segment.
+pub const PELT_MIN_SEGMENT: usize = 3;
+
+/// Gaussian consistency constant for the median absolute deviation.
+const MAD_SCALE: f64 = 1.482_6;
+/// PELT totals closer than this, in units of `σ²`, are treated as ties.
+const COST_TIE: f64 = 1e-6;
+
+/// Regime change found by [`cusum`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Changepoint {
+    /// First sample of the new regime.
+    pub index: usize,
+    /// Sample at which the alarm fired; `detected_at − index` is the delay.
+    pub detected_at: usize,
+    /// `1` when the slope or level rose, `-1` when it fell.
+    pub direction: i8,
+}
+
+/// CUSUM parameters in units of the difference noise scale.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CusumConfig {
+    /// Allowance `k` subtracted from every standardized step.
+    pub drift: Fx,
+    /// Decision interval `h`.
+    pub threshold: Fx,
+    /// Differences used to estimate each regime's reference slope.
+    pub warmup: usize,
+}
+
+impl Default for CusumConfig {
+    fn default() -> Self {
+        Self {
+            drift: 0.5,
+            threshold: 5.0,
+            warmup: 5,
+        }
+    }
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        0.5 * (values[mid - 1] + values[mid])
+    } else {
+        values[mid]
+    }
+}
+
+/// Robust noise standard deviation of `series`, at least [`MIN_NOISE_SIGMA`].
+pub fn estimate_noise(series: &[Fx]) -> Fx {
+    if series.len() < 3 {
+        return MIN_NOISE_SIGMA;
+    }
+    let mut second: Vec<f64> = series
+        .windows(3)
+        .map(|w| (w[2] as f64 - 2.0 * w[1] as f64 + w[0] as f64).abs())
+        .collect();
+    let sigma = MAD_SCALE * median(&mut second) / 6f64.sqrt();
+    (sigma as Fx).max(MIN_NOISE_SIGMA)
+}
+
+/// Two-sided CUSUM over the first differences of `series`.
+pub fn cusum(series: &[Fx], config: &CusumConfig) -> Vec<Changepoint> {
+    let mut found = Vec::new();
+    if series.iter().any(|v| !v.is_finite()) {
+        return found;
+    }
+    let diffs: Vec<f64> = series
+        .windows(2)
+        .map(|w| w[1] as f64 - w[0] as f64)
+        .collect();
+    // Differences of white noise have twice its variance.
+    let sigma = std::f64::consts::SQRT_2 * estimate_noise(series) as f64;
+    let warmup = config.warmup.max(1);
+    let (k, h) = (config.drift as f64, config.threshold as f64);
+
+    let mut start = 0;
+    while start + warmup < diffs.len() {
+        let reference = median(&mut diffs[start..start + warmup].to_vec());
+        let (mut high, mut low) = (0.0f64, 0.0f64);
+        let (mut high_onset, mut low_onset) = (start, start);
+        let mut alarm = None;
+        for (t, &d) in diffs.iter().enumerate().skip(start) {
+            let z = (d - reference) / sigma;
+            high = (high + z - k).max(0.0);
+            low = (low - z - k).max(0.0);
+            if high == 0.0 {
+                high_onset = t + 1;
+            }
+            if low == 0.0 {
+                low_onset = t + 1;
+            }
+            if high > h {
+                alarm = Some((high_onset, t, 1));
+            } else if low > h {
+                alarm = Some((low_onset, t, -1));
+            }
+            if alarm.is_some() {
+                break;
+            }
+        }
+        let Some((onset, t, direction)) = alarm else {
+            break;
+        };
+        // Difference `i` spans samples `i` and `i + 1`.
+        found.push(Changepoint {
+            index: onset + 1,
+            detected_at: t + 1,
+            direction,
+        });
+        start = t + 1;
+    }
+    found
+}
+
+/// Prefix sums for O(1) straight-line segment costs.
+struct SegmentCost {
+    sums: Vec<[f64; 5]>,
+    inv_var: f64,
+}
+
+impl SegmentCost {
+    fn new(series: &[Fx]) -> Self {
+        let mut sums = vec![[0.0; 5]; series.len() + 1];
+        for (i, &y) in series.iter().enumerate() {
+            let (x, y) = (i as f64, y as f64);
+            let prev = sums[i];
+            sums[i + 1] = [
+                prev[0] + x,
+                prev[1] + x * x,
+                prev[2] + y,
+                prev[3] + x * y,
+                prev[4] + y * y,
+            ];
+        }
+        let sigma = estimate_noise(series) as f64;
+        Self {
+            sums,
+            inv_var: 1.0 / (sigma * sigma),
+        }
+    }
+
+    /// Residual sum of squares of the line through `[a, b)`, over `σ²`.
+    fn cost(&self, a: usize, b: usize) -> f64 {
+        let (lo, hi) = (self.sums[a], self.sums[b]);
+        let s: [f64; 5] = std::array::from_fn(|j| hi[j] - lo[j]);
+        let n = (b - a) as f64;
+        let sxx = s[1] - s[0] * s[0] / n;
+        let sxy = s[3] - s[0] * s[2] / n;
+        let syy = s[4] - s[2] * s[2] / n;
+        let rss = if sxx > 0.0 {
+            syy - sxy * sxy / sxx
+        } else {
+            syy
+        };
+        rss.max(0.0) * self.inv_var
+    }
+}
+
+/// BIC-style penalty for a line segment (slope, intercept, and location).
+pub fn default_penalty(len: usize) -> Fx {
+    3.0 * (len.max(2) as Fx).ln()
+}
+
+/// Optimal piecewise-linear segmentation; returns the first index of every
+/// segment after the first, in ascending order.
+pub fn pelt(series: &[Fx], penalty: Fx) -> Vec<usize> {
+    let n = series.len();
+    if n < 2 * PELT_MIN_SEGMENT || series.iter().any(|v| !v.is_finite()) {
+        return Vec::new();
+    }
+    let cost = SegmentCost::new(series);
+    let beta = penalty.max(0.0) as f64;
+    let mut best = vec![f64::INFINITY; n + 1];
+    let mut last = vec![0usize; n + 1];
+    best[0] = -beta;
+    let mut candidates = vec![0usize];
+    for t in PELT_MIN_SEGMENT..=n {
+        // Candidates ascend, so accepting ties keeps the latest split and
+        // leaves a shared vertex in the earlier segment.
+        for &s in candidates.iter().filter(|&&s| t - s >= PELT_MIN_SEGMENT) {
+            let total = best[s] + cost.cost(s, t) + beta;
+            if total < best[t] + COST_TIE {
+                best[t] = total;
+                last[t] = s;
+            }
+        }
+        candidates.retain(|&s| t - s < PELT_MIN_SEGMENT || best[s] + cost.cost(s, t) <= best[t]);
+        candidates.push(t);
+    }
+    let mut splits = Vec::new();
+    let mut t = last[n];
+    while t > 0 {
+        splits.push(t);
+        t = last[t];
+    }
+    splits.reverse();
+    splits
+}
+
+/// Segments `series` with [`pelt`] under [`default_penalty`].
+pub fn segment_series(series: &[Fx]) -> Vec<usize> {
+    pelt(series, default_penalty(series.len()))
+}
+
+/// Cycle ids at which the coherence of `records` enters a new regime.
+pub fn detect_changepoints(records: &[CycleRecord]) -> Vec<u64> {
+    let mut ids: Vec<u64> = records.iter().map(|record| record.cycle_id).collect();
+    ids.sort_unstable();
+    segment_series(&ordered_series(records, |record| record.coherence))
+        .into_iter()
+        .map(|index| ids[index])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic zero-mean noise in `[-amplitude, amplitude]`.
+    fn noise(len: usize, amplitude: Fx) -> Vec<Fx> {
+        let mut state = 0x2545_f491u32;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                amplitude * (2.0 * (state as Fx / u32::MAX as Fx) - 1.0)
+            })
+            .collect()
+    }
+
+    fn kinked(len: usize, at: usize, before: Fx, after: Fx) -> Vec<Fx> {
+        (0..len)
+            .map(|t| {
+                let t = t as Fx;
+                let k = at as Fx;
+                0.5 + before * t.min(k) + after * (t - k).max(0.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ramps_are_one_regime() {
+        let ramp: Vec<Fx> = (0..40).map(|t| 0.2 + 0.01 * t as Fx).collect();
+        assert!(segment_series(&ramp).is_empty());
+        assert!(cusum(&ramp, &CusumConfig::default()).is_empty());
+        let noisy: Vec<Fx> = ramp
+            .iter()
+            .zip(noise(40, 0.002))
+            .map(|(r, e)| r + e)
+            .collect();
+        assert!(segment_series(&noisy).is_empty());
+        assert!(cusum(&noisy, &CusumConfig::default()).is_empty());
+    }
+
+    #[test]
+    fn level_jumps_and_slope_changes_are_located() {
+        let mut step = vec![0.4; 20];
+        step.extend(vec![0.7; 20]);
+        let step: Vec<Fx> = step
+            .iter()
+            .zip(noise(40, 0.005))
+            .map(|(s, e)| s + e)
+            .collect();
+        assert_eq!(segment_series(&step), vec![20]);
+        let alarms = cusum(&step, &CusumConfig::default());
+        assert_eq!(alarms.len(), 1);
+        // The onset estimate can lead the jump by a sample of positive noise.
+        assert_eq!((alarms[0].detected_at, alarms[0].direction), (20, 1));
+        assert!((19..=20).contains(&alarms[0].index));
+
+        // Sample 25 is the vertex; 26 is the first sample off the rising line.
+        let kink = kinked(40, 25, 0.02, -0.02);
+        assert_eq!(segment_series(&kink), vec![26]);
+        let alarm = cusum(&kink, &CusumConfig::default())[0];
+        assert_eq!((alarm.index, alarm.direction), (26, -1));
+        assert!(alarm.detected_at - alarm.index <= 1);
+    }
+
+    #[test]
+    fn record_changepoints_follow_cycle_order() {
+        let series: Vec<Fx> = (0..30).map(|i| if i < 18 { 0.5 } else { 0.3 }).collect();
+        let mut records: Vec<CycleRecord> = series
+            .iter()
+            .enumerate()
+            .map(|(i, &coherence)| CycleRecord {
+                cycle_id: 100 + i as u64,
+                coherence,
+                ..CycleRecord::default()
+            })
+            .collect();
+        records.reverse();
+        assert_eq!(detect_changepoints(&records), vec![118]);
+    }
+}
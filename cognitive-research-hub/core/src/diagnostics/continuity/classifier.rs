@@ -1,3 +1,415 @@
-//! Placeholder for the rule-based trend classifier.
+//! Rule-based stability classifier with LUT thresholds and hysteresis.
 //!
-//! Implements `classify_stability` as defined in `continuity/spec.md`.
\ No newline at end of file
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/continuity/spec.md`
+//!
+//! Rules are checked top to bottom; the first match wins:
+//!
+//! | Class | Condition |
+//! | --- | --- |
+//! | Divergent (3) | non-finite input, or `|short| > ε₄` and `|short − long| > ε₅` |
+//! | Oscillatory (2) | `oscillation ≥ ε₃` and `stdev > ε₂` |
+//! | Improvement (1) | `long > ε₁` and `oscillation < ε₃` |
+//! | Degradation (−1) | `long < −ε₁` and `stdev > ε₂` |
+//! | Stable (0) | otherwise |
+//!
+//! ε₃ sits at the 99th percentile of the
+//! [`detect_oscillations`](super::oscillation::detect_oscillations) score for
+//! white noise over [`LONG_WINDOW`](super::trend::LONG_WINDOW) samples, so a
+//! noisy plateau stays Stable.
+//!
+//! Values and thresholds are rounded to multiples of [`THRESHOLD_QUANTUM`] and
+//! compared as integers. Thresholds ship in `lut/thresholds.tbl`.
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use super::trend::TrendModel;
+use crate::{
+    error::{CoreResult, DreamError},
+    utils::{parse_toml, JsonValue},
+    Fx,
+};
+
+/// Resolution of threshold comparisons.
+pub const THRESHOLD_QUANTUM: Fx = 1e-4;
+
+const THRESHOLDS_TABLE: &str = include_str!("lut/thresholds.tbl");
+
+/// Discrete stability state of a metric history.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StabilityClass {
+    #[default]
+    Stable,
+    Improvement,
+    Degradation,
+    Oscillatory,
+    Divergent,
+}
+
+impl StabilityClass {
+    /// Every class in rule-table order.
+    pub const ALL: [StabilityClass; 5] = [
+        StabilityClass::Stable,
+        StabilityClass::Improvement,
+        StabilityClass::Degradation,
+        StabilityClass::Oscillatory,
+        StabilityClass::Divergent,
+    ];
+
+    /// Spec class id.
+    pub fn id(self) -> i8 {
+        match self {
+            StabilityClass::Stable => 0,
+            StabilityClass::Improvement => 1,
+            StabilityClass::Degradation => -1,
+            StabilityClass::Oscillatory => 2,
+            StabilityClass::Divergent => 3,
+        }
+    }
+
+    pub fn from_id(id: i8) -> Option<Self> {
+        Self::ALL.iter().copied().find(|class| class.id() == id)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            StabilityClass::Stable => "stable",
+            StabilityClass::Improvement => "improvement",
+            StabilityClass::Degradation => "degradation",
+            StabilityClass::Oscillatory => "oscillatory",
+            StabilityClass::Divergent => "divergent",
+        }
+    }
+}
+
+/// Classifier thresholds ε₁…ε₅ and the hysteresis length.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StabilityThresholds {
+    /// ε₁: long-slope magnitude for Improvement and Degradation.
+    pub slope: Fx,
+    /// ε₂: spread required for Degradation and Oscillatory.
+    pub stdev: Fx,
+    /// ε₃: oscillation score for Oscillatory.
+    pub oscillation: Fx,
+    /// ε₄: short-slope magnitude for Divergent.
+    pub divergence_slope: Fx,
+    /// ε₅: short/long slope gap for Divergent.
+    pub divergence_acceleration: Fx,
+    /// Consistent windows required before a [`StabilityTracker`] switches class.
+    pub hysteresis_windows: usize,
+}
+
+impl Default for StabilityThresholds {
+    /// Spec default tolerance set, with ε₃ fitted to the oscillation score.
+    fn default() -> Self {
+        Self {
+            slope: 0.01,
+            stdev: 0.005,
+            oscillation: 0.85,
+            divergence_slope: 0.05,
+            divergence_acceleration: 0.03,
+            hysteresis_windows: 3,
+        }
+    }
+}
+
+fn threshold_field(doc: &JsonValue, key: &str, default: Fx) -> CoreResult<Fx> {
+    match doc.get(key) {
+        None => Ok(default),
+        Some(value) => value
+            .as_f64()
+            .map(|v| v as Fx)
+            .ok_or_else(|| DreamError::Config(format!("thresholds.{} must be a number", key))),
+    }
+}
+
+impl StabilityThresholds {
+    /// Parses a `thresholds.tbl` document; missing keys keep their defaults.
+    pub fn from_tbl_str(text: &str) -> CoreResult<Self> {
+        let doc = parse_toml(text)?;
+        let defaults = Self::default();
+        let hysteresis_windows = match doc.get("hysteresis_windows") {
+            None => defaults.hysteresis_windows,
+            Some(value) => value
+                .as_u64()
+                .and_then(|v| usize::try_from(v).ok())
+                .ok_or_else(|| {
+                    DreamError::Config(
+                        "thresholds.hysteresis_windows must be a non-negative integer".to_string(),
+                    )
+                })?,
+        };
+        let thresholds = Self {
+            slope: threshold_field(&doc, "slope", defaults.slope)?,
+            stdev: threshold_field(&doc, "stdev", defaults.stdev)?,
+            oscillation: threshold_field(&doc, "oscillation", defaults.oscillation)?,
+            divergence_slope: threshold_field(&doc, "divergence_slope", defaults.divergence_slope)?,
+            divergence_acceleration: threshold_field(
+                &doc,
+                "divergence_acceleration",
+                defaults.divergence_acceleration,
+            )?,
+            hysteresis_windows,
+        };
+        thresholds.validate()?;
+        Ok(thresholds)
+    }
+
+    /// Loads and validates a threshold table from disk.
+    pub fn load(path: impl AsRef<Path>) -> CoreResult<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Self::from_tbl_str(&text)
+    }
+
+    /// Returns the table embedded from `lut/thresholds.tbl`, parsed on first use.
+    pub fn embedded() -> Self {
+        static EMBEDDED: OnceLock<StabilityThresholds> = OnceLock::new();
+        *EMBEDDED.get_or_init(|| {
+            Self::from_tbl_str(THRESHOLDS_TABLE).expect("embedded thresholds.tbl is valid")
+        })
+    }
+
+    /// Requires finite, non-negative thresholds and at least one hysteresis window.
+    pub fn validate(&self) -> CoreResult<()> {
+        let values = [
+            ("slope", self.slope),
+            ("stdev", self.stdev),
+            ("oscillation", self.oscillation),
+            ("divergence_slope", self.divergence_slope),
+            ("divergence_acceleration", self.divergence_acceleration),
+        ];
+        for (name, value) in values {
+            if !(value.is_finite() && value >= 0.0) {
+                return Err(DreamError::Config(format!(
+                    "thresholds.{} must be a non-negative number, got {}",
+                    name, value
+                )));
+            }
+        }
+        if self.divergence_slope < self.slope {
+            return Err(DreamError::Config(format!(
+                "thresholds.divergence_slope ({}) must not be below thresholds.slope ({})",
+                self.divergence_slope, self.slope
+            )));
+        }
+        if self.hysteresis_windows == 0 {
+            return Err(DreamError::Config(
+                "thresholds.hysteresis_windows must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn quantize(value: Fx) -> i64 {
+    (value as f64 / THRESHOLD_QUANTUM as f64).round() as i64
+}
+
+/// Applies the rule table to `trend` under `thresholds`.
+pub fn classify_with(trend: &TrendModel, thresholds: &StabilityThresholds) -> StabilityClass {
+    let values = [
+        trend.short_slope,
+        trend.long_slope,
+        trend.stdev,
+        trend.oscillation_score,
+    ];
+    if values.iter().any(|v| !v.is_finite()) {
+        return StabilityClass::Divergent;
+    }
+    let short = quantize(trend.short_slope);
+    let long = quantize(trend.long_slope);
+    let stdev = quantize(trend.stdev);
+    let oscillation = quantize(trend.oscillation_score);
+    let eps_slope = quantize(thresholds.slope);
+    let eps_stdev = quantize(thresholds.stdev);
+    let eps_oscillation = quantize(thresholds.oscillation);
+
+    if short.abs() > quantize(thresholds.divergence_slope)
+        && (short - long).abs() > quantize(thresholds.divergence_acceleration)
+    {
+        StabilityClass::Divergent
+    } else if oscillation >= eps_oscillation && stdev > eps_stdev {
+        StabilityClass::Oscillatory
+    } else if long > eps_slope && oscillation < eps_oscillation {
+        StabilityClass::Improvement
+    } else if long < -eps_slope && stdev > eps_stdev {
+        StabilityClass::Degradation
+    } else {
+        StabilityClass::Stable
+    }
+}
+
+/// Classifies `trend` with the embedded thresholds and returns the spec class id.
+pub fn classify_stability(trend: &TrendModel) -> i8 {
+    classify_with(trend, &StabilityThresholds::embedded()).id()
+}
+
+/// Debounces classifications so the class only changes after
+/// `hysteresis_windows` consecutive windows agree on a new class.
+#[derive(Clone, Debug)]
+pub struct StabilityTracker {
+    thresholds: StabilityThresholds,
+    current: Option<StabilityClass>,
+    pending: Option<(StabilityClass, usize)>,
+}
+
+impl StabilityTracker {
+    pub fn new(thresholds: StabilityThresholds) -> Self {
+        Self {
+            thresholds,
+            current: None,
+            pending: None,
+        }
+    }
+
+    /// Reported class; `None` before the first window.
+    pub fn current(&self) -> Option<StabilityClass> {
+        self.current
+    }
+
+    /// Classifies one window and returns the debounced class.
+    ///
+    /// The first window is adopted immediately.
+    pub fn update(&mut self, trend: &TrendModel) -> StabilityClass {
+        let observed = classify_with(trend, &self.thresholds);
+        let current = match self.current {
+            None => observed,
+            Some(current) if current == observed => {
+                self.pending = None;
+                current
+            }
+            Some(current) => {
+                let count = match self.pending {
+                    Some((class, count)) if class == observed => count + 1,
+                    _ => 1,
+                };
+                if count >= self.thresholds.hysteresis_windows {
+                    self.pending = None;
+                    observed
+                } else {
+                    self.pending = Some((observed, count));
+                    current
+                }
+            }
+        };
+        self.current = Some(current);
+        current
+    }
+}
+
+impl Default for StabilityTracker {
+    fn default() -> Self {
+        Self::new(StabilityThresholds::embedded())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::continuity::trend::{analyze_series, LONG_WINDOW};
+
+    fn model(short: Fx, long: Fx, stdev: Fx, oscillation: Fx) -> TrendModel {
+        TrendModel {
+            short_slope: short,
+            long_slope: long,
+            stdev,
+            oscillation_score: oscillation,
+            ..TrendModel::default()
+        }
+    }
+
+    #[test]
+    fn rule_table_maps_every_class() {
+        let t = StabilityThresholds::default();
+        let cases = [
+            (model(0.0, 0.0, 0.001, 0.0), StabilityClass::Stable),
+            (model(0.02, 0.02, 0.03, 0.0), StabilityClass::Improvement),
+            (model(-0.02, -0.02, 0.03, 0.01), StabilityClass::Degradation),
+            (model(0.0, 0.0, 0.03, 0.95), StabilityClass::Oscillatory),
+            (model(0.09, 0.01, 0.03, 0.0), StabilityClass::Divergent),
+            (model(Fx::NAN, 0.0, 0.0, 0.0), StabilityClass::Divergent),
+        ];
+        for (trend, expected) in cases {
+            assert_eq!(classify_with(&trend, &t), expected, "{:?}", trend);
+            assert_eq!(StabilityClass::from_id(expected.id()), Some(expected));
+        }
+        // Exactly on ε₁ (after rounding) is not growth.
+        assert_eq!(
+            classify_with(&model(0.0, 0.010_000_4, 0.03, 0.0), &t),
+            StabilityClass::Stable
+        );
+    }
+
+    #[test]
+    fn single_dip_in_a_ramp_is_still_improvement() {
+        let ramp: Vec<Fx> = (0..20).map(|i| 0.5 + 0.015 * i as Fx).collect();
+        let mut dipped = ramp.clone();
+        dipped[10] -= 0.02;
+        let t = StabilityThresholds::default();
+        for series in [&ramp, &dipped] {
+            let trend = analyze_series(series);
+            assert_eq!(classify_with(&trend, &t), StabilityClass::Improvement);
+        }
+    }
+
+    #[test]
+    fn noisy_plateau_is_stable() {
+        let mut state = 0x2545_f491u32;
+        let plateau: Vec<Fx> = (0..LONG_WINDOW)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                0.7 + 0.01 * (2.0 * (state as Fx / u32::MAX as Fx) - 1.0)
+            })
+            .collect();
+        let trend = analyze_series(&plateau);
+        // Spread alone clears ε₂, so only the oscillation score decides.
+        assert!(trend.stdev > StabilityThresholds::default().stdev);
+        assert_eq!(
+            classify_with(&trend, &StabilityThresholds::default()),
+            StabilityClass::Stable
+        );
+        let wave: Vec<Fx> = (0..LONG_WINDOW)
+            .map(|i| 0.7 + 0.03 * (i as Fx * 1.3).sin())
+            .collect();
+        assert_eq!(
+            classify_with(&analyze_series(&wave), &StabilityThresholds::default()),
+            StabilityClass::Oscillatory
+        );
+    }
+
+    #[test]
+    fn shipped_table_matches_spec_defaults() {
+        assert_eq!(
+            StabilityThresholds::embedded(),
+            StabilityThresholds::default()
+        );
+        let custom =
+            StabilityThresholds::from_tbl_str("slope = 0.02\nhysteresis_windows = 1").unwrap();
+        assert_eq!(custom.slope, 0.02);
+        assert_eq!(custom.stdev, 0.005);
+        assert!(StabilityThresholds::from_tbl_str("slope = -1").is_err());
+        assert!(StabilityThresholds::from_tbl_str("hysteresis_windows = 0").is_err());
+        assert!(StabilityThresholds::from_tbl_str("stdev = \"wide\"").is_err());
+    }
+
+    #[test]
+    fn hysteresis_waits_for_consistent_windows() {
+        let mut tracker = StabilityTracker::default();
+        let stable = model(0.0, 0.0, 0.001, 0.0);
+        let rising = model(0.02, 0.02, 0.03, 0.0);
+        assert_eq!(tracker.update(&stable), StabilityClass::Stable);
+        assert_eq!(tracker.update(&rising), StabilityClass::Stable);
+        // An interruption resets the count.
+        assert_eq!(tracker.update(&stable), StabilityClass::Stable);
+        assert_eq!(tracker.update(&rising), StabilityClass::Stable);
+        assert_eq!(tracker.update(&rising), StabilityClass::Stable);
+        assert_eq!(tracker.update(&rising), StabilityClass::Improvement);
+        assert_eq!(tracker.current(), Some(StabilityClass::Improvement));
+    }
+}
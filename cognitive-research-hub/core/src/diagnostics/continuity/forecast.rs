@@ -0,0 +1,203 @@
+//! Short-horizon forecasting with prediction intervals.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/continuity/spec.md`
+//!
+//! Forecasts use Holt's linear method (non-seasonal Holt-Winters). It starts
+//! from `level = y₀` and `trend = y₁ − y₀`. The smoothing weights are picked
+//! from the fixed [`HOLT_GRID`] by the smallest one-step squared error, with
+//! the first grid entry winning ties.
+//!
+//! The `h`-step interval is `±z·σ·√(1 + Σ_{j<h} α²(1 + jβ)²)`, where `σ` is
+//! the RMS one-step error. [`project_trend`] forecasts only the regime after
+//! the last [`segment_series`] changepoint, so an older opposite trend
+//! cannot mask a new one.
+
+use super::changepoint::segment_series;
+use super::trend::{analyze_series, ordered_series, TrendModel, SHORT_WINDOW};
+use crate::{diagnostics::metrics::CycleRecord, Fx};
+
+/// Default number of samples forecast ahead.
+pub const FORECAST_HORIZON: usize = SHORT_WINDOW;
+/// Fewest samples that yield a forecast.
+pub const MIN_FORECAST_SAMPLES: usize = 4;
+/// Normal quantile for the default 95% prediction interval.
+pub const INTERVAL_Z: Fx = 1.96;
+/// Smoothing weights searched by [`forecast_series`], as `(α, β)` pairs.
+pub const HOLT_GRID: [(Fx, Fx); 9] = [
+    (0.2, 0.1),
+    (0.2, 0.3),
+    (0.2, 0.5),
+    (0.5, 0.1),
+    (0.5, 0.3),
+    (0.5, 0.5),
+    (0.8, 0.1),
+    (0.8, 0.3),
+    (0.8, 0.5),
+];
+
+/// Forecast path with symmetric prediction intervals.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Forecast {
+    /// Point forecasts for steps `1..=horizon`.
+    pub mean: Vec<Fx>,
+    pub lower: Vec<Fx>,
+    pub upper: Vec<Fx>,
+    /// Level smoothing weight `α`.
+    pub alpha: Fx,
+    /// Trend smoothing weight `β`.
+    pub beta: Fx,
+    /// RMS one-step error over the fitted series.
+    pub sigma: Fx,
+}
+
+struct HoltFit {
+    level: f64,
+    trend: f64,
+    sse: f64,
+    errors: usize,
+}
+
+fn fit_holt(series: &[Fx], alpha: f64, beta: f64) -> HoltFit {
+    let mut level = series[0] as f64;
+    let mut trend = series[1] as f64 - level;
+    let mut sse = 0.0;
+    for &y in &series[1..] {
+        let y = y as f64;
+        let error = y - (level + trend);
+        sse += error * error;
+        let previous = level;
+        level += trend + alpha * error;
+        trend += beta * (level - previous - trend);
+    }
+    HoltFit {
+        level,
+        trend,
+        sse,
+        errors: series.len() - 1,
+    }
+}
+
+/// Forecasts `horizon` steps with fixed weights and a `z`-scaled interval.
+pub fn forecast_holt(
+    series: &[Fx],
+    horizon: usize,
+    alpha: Fx,
+    beta: Fx,
+    z: Fx,
+) -> Option<Forecast> {
+    if series.len() < MIN_FORECAST_SAMPLES || series.iter().any(|v| !v.is_finite()) {
+        return None;
+    }
+    let (a, b) = (alpha.clamp(0.0, 1.0) as f64, beta.clamp(0.0, 1.0) as f64);
+    let fit = fit_holt(series, a, b);
+    let sigma = (fit.sse / fit.errors as f64).sqrt();
+    let mut forecast = Forecast {
+        alpha: a as Fx,
+        beta: b as Fx,
+        sigma: sigma as Fx,
+        ..Forecast::default()
+    };
+    let mut spread = 0.0f64;
+    for h in 1..=horizon {
+        let mean = fit.level + h as f64 * fit.trend;
+        let width = z as f64 * sigma * (1.0 + spread).sqrt();
+        forecast.mean.push(mean as Fx);
+        forecast.lower.push((mean - width) as Fx);
+        forecast.upper.push((mean + width) as Fx);
+        let gain = a * (1.0 + h as f64 * b);
+        spread += gain * gain;
+    }
+    Some(forecast)
+}
+
+/// Forecasts `horizon` steps with the best [`HOLT_GRID`] weights and a 95% interval.
+pub fn forecast_series(series: &[Fx], horizon: usize) -> Option<Forecast> {
+    if series.len() < MIN_FORECAST_SAMPLES || series.iter().any(|v| !v.is_finite()) {
+        return None;
+    }
+    let mut best = HOLT_GRID[0];
+    let mut best_sse = f64::INFINITY;
+    for &(alpha, beta) in &HOLT_GRID {
+        let sse = fit_holt(series, alpha as f64, beta as f64).sse;
+        if sse < best_sse {
+            best = (alpha, beta);
+            best_sse = sse;
+        }
+    }
+    forecast_holt(series, horizon, best.0, best.1, INTERVAL_Z)
+}
+
+/// Forecasts the coherence of `records` after sorting them by `cycle_id`.
+pub fn forecast_trends(records: &[CycleRecord], horizon: usize) -> Option<Forecast> {
+    forecast_series(&ordered_series(records, |record| record.coherence), horizon)
+}
+
+/// Trend model of the current regime extended by its [`FORECAST_HORIZON`] forecast.
+///
+/// Falls back to the whole series when the regime is too short to forecast.
+pub fn project_trend(series: &[Fx]) -> TrendModel {
+    let regime = match segment_series(series).last() {
+        Some(&start) if series.len() - start >= MIN_FORECAST_SAMPLES => &series[start..],
+        _ => series,
+    };
+    match forecast_series(regime, FORECAST_HORIZON) {
+        Some(forecast) => {
+            let mut extended = regime.to_vec();
+            extended.extend(forecast.mean);
+            analyze_series(&extended)
+        }
+        None => analyze_series(series),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::continuity::StabilityClass;
+
+    #[test]
+    fn linear_series_forecast_exactly_with_zero_width() {
+        let ramp: Vec<Fx> = (0..12).map(|t| 0.3 + 0.02 * t as Fx).collect();
+        let forecast = forecast_series(&ramp, 3).unwrap();
+        for (h, mean) in forecast.mean.iter().enumerate() {
+            assert!((mean - (0.3 + 0.02 * (12 + h) as Fx)).abs() < 1e-5);
+        }
+        assert!(forecast.sigma < 1e-5);
+        assert!(forecast_series(&ramp[..3], 3).is_none());
+    }
+
+    #[test]
+    fn intervals_widen_with_horizon_and_cover_the_mean() {
+        let series: Vec<Fx> = (0..24)
+            .map(|t| 0.5 + 0.01 * t as Fx + if t % 3 == 0 { 0.02 } else { -0.01 })
+            .collect();
+        let forecast = forecast_series(&series, 5).unwrap();
+        assert!(forecast.sigma > 0.0);
+        let widths: Vec<Fx> = (0..5)
+            .map(|h| forecast.upper[h] - forecast.lower[h])
+            .collect();
+        assert!(widths.windows(2).all(|w| w[1] > w[0]));
+        for h in 0..5 {
+            assert!(forecast.lower[h] < forecast.mean[h] && forecast.mean[h] < forecast.upper[h]);
+        }
+        assert_eq!(forecast_series(&series, 5), Some(forecast));
+    }
+
+    #[test]
+    fn projection_sees_a_new_decline_before_the_long_window() {
+        let mut series: Vec<Fx> = (0..20).map(|t| 0.3 + 0.02 * t as Fx).collect();
+        series.extend([0.66, 0.64, 0.62, 0.60]);
+        assert_ne!(
+            analyze_series(&series).class_id,
+            StabilityClass::Degradation.id()
+        );
+        assert_eq!(
+            project_trend(&series).class_id,
+            StabilityClass::Degradation.id()
+        );
+    }
+}
@@ -1,16 +1,44 @@
 //! Deterministic temporal reasoning and trend analysis.
 //!
 //! This module organizes the sub-modules for trend modeling,
-//! oscillation detection, and stability classification, as
-//! defined in `continuity/spec.md`.
+//! oscillation detection, changepoint detection, forecasting, and
+//! stability classification, as defined in `continuity/spec.md`.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/continuity/spec.md`
 
+pub mod changepoint;
 pub mod classifier;
+pub mod forecast;
 pub mod oscillation;
 pub mod planner;
 pub mod trend;
 
-// (Placeholder re-exports)
-// pub use self::classifier::{classify_stability, TrendModel};
-// pub use self::oscillation::detect_oscillations;
-// pub use self::planner::{plan_temporal_action, TemporalAction};
-// pub use self::trend::analyze_trends;
\ No newline at end of file
+pub use self::changepoint::{
+    cusum, default_penalty, detect_changepoints, estimate_noise, pelt, segment_series, Changepoint,
+    CusumConfig, MIN_NOISE_SIGMA, PELT_MIN_SEGMENT,
+};
+pub use self::classifier::{
+    classify_stability, classify_with, StabilityClass, StabilityThresholds, StabilityTracker,
+    THRESHOLD_QUANTUM,
+};
+pub use self::forecast::{
+    forecast_holt, forecast_series, forecast_trends, project_trend, Forecast, FORECAST_HORIZON,
+    HOLT_GRID, INTERVAL_Z, MIN_FORECAST_SAMPLES,
+};
+pub use self::oscillation::{
+    analyze_oscillations, detect_oscillations, OscillationReport, MIN_OSCILLATION_SAMPLES,
+    OSCILLATION_WINDOW,
+};
+pub use self::planner::{
+    execute_action, plan_ahead, plan_temporal_action, plan_with, ActionExecutor, ActionKind,
+    ActionOutcome, ActionRecord, AuditTrail, TemporalAction, TemporalPlanner, DEGRADATION_DAMPING,
+    OSCILLATION_DAMPING,
+};
+pub use self::trend::{
+    analyze_series, analyze_trends, analyze_trends_by, fit_exponential, fit_linear, ordered_series,
+    verify_trend_determinism, ExponentialFit, LinearFit, TrendModel, LONG_WINDOW, SHORT_WINDOW,
+};
@@ -1,3 +1,496 @@
-//! Placeholder for TemporalAction generation.
+//! TemporalAction planning, execution, and audit trail.
 //!
-//! Implements `plan_temporal_action` as defined in `continuity/spec.md`.
\ No newline at end of file
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/continuity/spec.md`
+//!
+//! | Class | Action |
+//! | --- | --- |
+//! | Stable | none |
+//! | Improvement | Hold |
+//! | Oscillatory | Adjust × [`OSCILLATION_DAMPING`] |
+//! | Degradation, `|long| ≤ ε₄` | Adjust × [`DEGRADATION_DAMPING`] |
+//! | Degradation, `|long| > ε₄` | Reset |
+//! | Divergent | Relearn (roll back to a checkpoint) |
+//!
+//! Confidence is `1 − ε / value` for the metric that triggered the rule, so it
+//! grows as the metric moves past its threshold.
+
+use std::fs;
+use std::path::Path;
+
+use super::classifier::{classify_with, StabilityClass, StabilityThresholds};
+use super::forecast::project_trend;
+use super::trend::TrendModel;
+use crate::{
+    error::{CoreResult, DreamError},
+    utils::JsonValue,
+    Fx,
+};
+
+/// Adjustment factor applied while a metric oscillates.
+pub const OSCILLATION_DAMPING: Fx = 0.5;
+/// Adjustment factor applied while a metric degrades slowly.
+pub const DEGRADATION_DAMPING: Fx = 0.8;
+
+/// Corrective decision encoded in [`TemporalAction::action_code`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ActionKind {
+    Hold,
+    Reset,
+    Adjust,
+    Relearn,
+}
+
+impl ActionKind {
+    /// Every action in code order.
+    pub const ALL: [ActionKind; 4] = [
+        ActionKind::Hold,
+        ActionKind::Reset,
+        ActionKind::Adjust,
+        ActionKind::Relearn,
+    ];
+
+    /// Spec action code.
+    pub fn code(self) -> u8 {
+        match self {
+            ActionKind::Hold => 0,
+            ActionKind::Reset => 1,
+            ActionKind::Adjust => 2,
+            ActionKind::Relearn => 3,
+        }
+    }
+
+    pub fn from_code(code: u8) -> Option<Self> {
+        Self::ALL.iter().copied().find(|kind| kind.code() == code)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ActionKind::Hold => "hold",
+            ActionKind::Reset => "reset",
+            ActionKind::Adjust => "adjust",
+            ActionKind::Relearn => "relearn",
+        }
+    }
+}
+
+/// Planned corrective action.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TemporalAction {
+    /// `0 = Hold, 1 = Reset, 2 = Adjust, 3 = Relearn`.
+    pub action_code: u8,
+    /// Deterministic confidence in `[0, 1]`.
+    pub confidence: Fx,
+    /// Multiplier for noise or learning rate; 1 unless the action is Adjust.
+    pub adjustment: Fx,
+}
+
+impl TemporalAction {
+    pub fn new(kind: ActionKind, confidence: Fx, adjustment: Fx) -> Self {
+        Self {
+            action_code: kind.code(),
+            confidence: confidence.clamp(0.0, 1.0),
+            adjustment,
+        }
+    }
+
+    pub fn kind(&self) -> Option<ActionKind> {
+        ActionKind::from_code(self.action_code)
+    }
+
+    pub fn to_json(&self) -> JsonValue {
+        JsonValue::object()
+            .with("action_code", self.action_code as u32)
+            .with("action", self.kind().map_or("unknown", ActionKind::name))
+            .with("confidence", self.confidence)
+            .with("adjustment", self.adjustment)
+    }
+}
+
+/// `1 − threshold / value`, or 1 when the threshold is zero.
+fn confidence(value: Fx, threshold: Fx) -> Fx {
+    if !value.is_finite() || threshold <= Fx::EPSILON {
+        return 1.0;
+    }
+    (1.0 - threshold / value.abs().max(Fx::EPSILON)).clamp(0.0, 1.0)
+}
+
+/// Maps a trend model to an action under `thresholds`.
+pub fn plan_with(trend: &TrendModel, thresholds: &StabilityThresholds) -> Option<TemporalAction> {
+    let action = match classify_with(trend, thresholds) {
+        StabilityClass::Stable => return None,
+        StabilityClass::Improvement => TemporalAction::new(
+            ActionKind::Hold,
+            confidence(trend.long_slope, thresholds.slope),
+            1.0,
+        ),
+        StabilityClass::Oscillatory => TemporalAction::new(
+            ActionKind::Adjust,
+            confidence(trend.oscillation_score, thresholds.oscillation),
+            OSCILLATION_DAMPING,
+        ),
+        StabilityClass::Degradation if trend.long_slope.abs() > thresholds.divergence_slope => {
+            TemporalAction::new(
+                ActionKind::Reset,
+                confidence(trend.long_slope, thresholds.divergence_slope),
+                1.0,
+            )
+        }
+        StabilityClass::Degradation => TemporalAction::new(
+            ActionKind::Adjust,
+            confidence(trend.long_slope, thresholds.slope),
+            DEGRADATION_DAMPING,
+        ),
+        StabilityClass::Divergent => TemporalAction::new(
+            ActionKind::Relearn,
+            confidence(trend.short_slope, thresholds.divergence_slope),
+            1.0,
+        ),
+    };
+    Some(action)
+}
+
+/// Maps a trend model to an action using the embedded thresholds.
+pub fn plan_temporal_action(trend: &TrendModel) -> Option<TemporalAction> {
+    plan_with(trend, &StabilityThresholds::embedded())
+}
+
+/// Plans from [`project_trend`] of `series`, so degradation is acted on before
+/// the long window shows it.
+pub fn plan_ahead(series: &[Fx], thresholds: &StabilityThresholds) -> Option<TemporalAction> {
+    plan_with(&project_trend(series), thresholds)
+}
+
+/// Target that applies planned actions, e.g. a dream cycle or a trainer.
+pub trait ActionExecutor {
+    /// Keeps the current configuration.
+    fn hold(&mut self) -> CoreResult<()> {
+        Ok(())
+    }
+
+    /// Discards accumulated state such as the dream pool.
+    fn reset(&mut self) -> CoreResult<()>;
+
+    /// Scales the noise amplitude or learning rate by `factor`.
+    fn adjust(&mut self, factor: Fx) -> CoreResult<()>;
+
+    /// Rolls back to the last checkpoint.
+    fn relearn(&mut self) -> CoreResult<()>;
+}
+
+impl<T: ActionExecutor + ?Sized> ActionExecutor for &mut T {
+    fn hold(&mut self) -> CoreResult<()> {
+        (**self).hold()
+    }
+
+    fn reset(&mut self) -> CoreResult<()> {
+        (**self).reset()
+    }
+
+    fn adjust(&mut self, factor: Fx) -> CoreResult<()> {
+        (**self).adjust(factor)
+    }
+
+    fn relearn(&mut self) -> CoreResult<()> {
+        (**self).relearn()
+    }
+}
+
+/// Dispatches `action` to the matching executor method.
+pub fn execute_action(
+    executor: &mut impl ActionExecutor,
+    action: &TemporalAction,
+) -> CoreResult<ActionKind> {
+    let kind = action.kind().ok_or_else(|| {
+        DreamError::Validation(format!("unknown action code {}", action.action_code))
+    })?;
+    match kind {
+        ActionKind::Hold => executor.hold()?,
+        ActionKind::Reset => executor.reset()?,
+        ActionKind::Adjust => {
+            if !(action.adjustment.is_finite() && action.adjustment > 0.0) {
+                return Err(DreamError::Validation(format!(
+                    "adjustment factor must be positive, got {}",
+                    action.adjustment
+                )));
+            }
+            executor.adjust(action.adjustment)?
+        }
+        ActionKind::Relearn => executor.relearn()?,
+    }
+    Ok(kind)
+}
+
+/// What happened to a planned decision.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ActionOutcome {
+    /// The trend needed no action.
+    NoAction,
+    Applied,
+    Failed(String),
+}
+
+/// One audited planning decision.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActionRecord {
+    /// Position in the trail, starting at 0.
+    pub sequence: u64,
+    pub cycle_id: u64,
+    pub class_id: i8,
+    pub short_slope: Fx,
+    pub long_slope: Fx,
+    pub action: Option<TemporalAction>,
+    pub outcome: ActionOutcome,
+}
+
+impl ActionRecord {
+    pub fn to_json(&self) -> JsonValue {
+        let (outcome, error) = match &self.outcome {
+            ActionOutcome::NoAction => ("none", JsonValue::Null),
+            ActionOutcome::Applied => ("applied", JsonValue::Null),
+            ActionOutcome::Failed(msg) => ("failed", JsonValue::from(msg.as_str())),
+        };
+        JsonValue::object()
+            .with("sequence", self.sequence)
+            .with("cycle_id", self.cycle_id)
+            .with("class_id", self.class_id as i32)
+            .with("short_slope", self.short_slope)
+            .with("long_slope", self.long_slope)
+            .with(
+                "action",
+                self.action.map_or(JsonValue::Null, |a| a.to_json()),
+            )
+            .with("outcome", outcome)
+            .with("error", error)
+    }
+}
+
+/// Append-only log of planning decisions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AuditTrail {
+    records: Vec<ActionRecord>,
+}
+
+impl AuditTrail {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn records(&self) -> &[ActionRecord] {
+        &self.records
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    fn push(&mut self, mut record: ActionRecord) {
+        record.sequence = self.records.len() as u64;
+        self.records.push(record);
+    }
+
+    /// One JSON object per line, in decision order.
+    pub fn to_jsonl(&self) -> String {
+        let mut out = String::new();
+        for record in &self.records {
+            out.push_str(&record.to_json().to_json_string());
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Writes the trail as JSON lines, replacing any existing file.
+    pub fn save_jsonl(&self, path: impl AsRef<Path>) -> CoreResult<()> {
+        fs::write(path, self.to_jsonl())?;
+        Ok(())
+    }
+}
+
+/// Plans, applies, and audits actions for successive trend windows.
+#[derive(Clone, Debug)]
+pub struct TemporalPlanner {
+    thresholds: StabilityThresholds,
+    trail: AuditTrail,
+}
+
+impl Default for TemporalPlanner {
+    fn default() -> Self {
+        Self::new(StabilityThresholds::embedded())
+    }
+}
+
+impl TemporalPlanner {
+    pub fn new(thresholds: StabilityThresholds) -> Self {
+        Self {
+            thresholds,
+            trail: AuditTrail::new(),
+        }
+    }
+
+    pub fn thresholds(&self) -> &StabilityThresholds {
+        &self.thresholds
+    }
+
+    pub fn trail(&self) -> &AuditTrail {
+        &self.trail
+    }
+
+    pub fn into_trail(self) -> AuditTrail {
+        self.trail
+    }
+
+    /// Plans an action for `trend`, applies it through `executor`, and records
+    /// the decision. Executor errors are recorded before being returned.
+    pub fn step(
+        &mut self,
+        cycle_id: u64,
+        trend: &TrendModel,
+        executor: &mut impl ActionExecutor,
+    ) -> CoreResult<Option<TemporalAction>> {
+        let action = plan_with(trend, &self.thresholds);
+        let result = match &action {
+            Some(action) => execute_action(executor, action).map(|_| ()),
+            None => Ok(()),
+        };
+        let outcome = match (&action, &result) {
+            (None, _) => ActionOutcome::NoAction,
+            (Some(_), Ok(())) => ActionOutcome::Applied,
+            (Some(_), Err(err)) => ActionOutcome::Failed(err.to_string()),
+        };
+        self.trail.push(ActionRecord {
+            sequence: 0,
+            cycle_id,
+            class_id: classify_with(trend, &self.thresholds).id(),
+            short_slope: trend.short_slope,
+            long_slope: trend.long_slope,
+            action,
+            outcome,
+        });
+        result.map(|()| action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        calls: Vec<String>,
+        fail_relearn: bool,
+    }
+
+    impl ActionExecutor for Recorder {
+        fn reset(&mut self) -> CoreResult<()> {
+            self.calls.push("reset".to_string());
+            Ok(())
+        }
+
+        fn adjust(&mut self, factor: Fx) -> CoreResult<()> {
+            self.calls.push(format!("adjust {}", factor));
+            Ok(())
+        }
+
+        fn relearn(&mut self) -> CoreResult<()> {
+            if self.fail_relearn {
+                return Err(DreamError::Dream("no checkpoint".to_string()));
+            }
+            self.calls.push("relearn".to_string());
+            Ok(())
+        }
+    }
+
+    fn model(short: Fx, long: Fx, stdev: Fx, oscillation: Fx) -> TrendModel {
+        TrendModel {
+            short_slope: short,
+            long_slope: long,
+            stdev,
+            oscillation_score: oscillation,
+            ..TrendModel::default()
+        }
+    }
+
+    #[test]
+    fn classes_map_to_actions() {
+        let plan = |t: TrendModel| plan_temporal_action(&t).map(|a| (a.kind().unwrap(), a));
+        assert_eq!(plan(model(0.0, 0.0, 0.001, 0.0)), None);
+        let (kind, hold) = plan(model(0.02, 0.02, 0.03, 0.0)).unwrap();
+        assert_eq!(kind, ActionKind::Hold);
+        assert!((hold.confidence - 0.5).abs() < 1e-6);
+        let (kind, damp) = plan(model(0.0, 0.0, 0.03, 0.95)).unwrap();
+        assert_eq!(
+            (kind, damp.adjustment),
+            (ActionKind::Adjust, OSCILLATION_DAMPING)
+        );
+        let (kind, slow) = plan(model(-0.02, -0.02, 0.03, 0.0)).unwrap();
+        assert_eq!(
+            (kind, slow.adjustment),
+            (ActionKind::Adjust, DEGRADATION_DAMPING)
+        );
+        assert_eq!(
+            plan(model(-0.06, -0.06, 0.1, 0.0)).unwrap().0,
+            ActionKind::Reset
+        );
+        let (kind, relearn) = plan(model(Fx::NAN, 0.0, 0.0, 0.0)).unwrap();
+        assert_eq!((kind, relearn.confidence), (ActionKind::Relearn, 1.0));
+
+        let mut series: Vec<Fx> = (0..20).map(|t| 0.3 + 0.02 * t as Fx).collect();
+        series.extend([0.66, 0.64, 0.62, 0.60]);
+        let ahead = plan_ahead(&series, &StabilityThresholds::embedded()).unwrap();
+        assert_eq!(ahead.kind(), Some(ActionKind::Adjust));
+        assert_eq!(ahead.adjustment, DEGRADATION_DAMPING);
+    }
+
+    #[test]
+    fn planner_applies_and_audits_every_decision() {
+        let mut planner = TemporalPlanner::default();
+        let mut executor = Recorder {
+            fail_relearn: true,
+            ..Recorder::default()
+        };
+        let trends = [
+            model(0.0, 0.0, 0.001, 0.0),
+            model(0.0, 0.0, 0.03, 0.95),
+            model(-0.06, -0.06, 0.1, 0.0),
+        ];
+        for (cycle, trend) in trends.iter().enumerate() {
+            planner.step(cycle as u64, trend, &mut executor).unwrap();
+        }
+        assert!(planner
+            .step(3, &model(0.2, 0.0, 0.1, 0.0), &mut executor)
+            .is_err());
+        assert_eq!(executor.calls, vec!["adjust 0.5", "reset"]);
+
+        let trail = planner.trail();
+        assert_eq!(trail.len(), 4);
+        assert_eq!(trail.records()[0].outcome, ActionOutcome::NoAction);
+        assert_eq!(
+            trail.records()[2].class_id,
+            StabilityClass::Degradation.id()
+        );
+        assert!(matches!(
+            trail.records()[3].outcome,
+            ActionOutcome::Failed(_)
+        ));
+        let lines: Vec<JsonValue> = trail
+            .to_jsonl()
+            .lines()
+            .map(|line| JsonValue::parse(line).unwrap())
+            .collect();
+        assert_eq!(
+            lines[3].get("sequence").and_then(JsonValue::as_u64),
+            Some(3)
+        );
+        assert_eq!(
+            lines[3].get("outcome").and_then(JsonValue::as_str),
+            Some("failed")
+        );
+    }
+}
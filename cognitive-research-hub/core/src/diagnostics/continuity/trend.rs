@@ -1,3 +1,234 @@
-//! Placeholder for regression and slope models.
+//! Regression and slope models over metric histories.
 //!
-//! Implements `analyze_trends` as defined in `continuity/spec.md`.
\ No newline at end of file
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/continuity/spec.md`
+//!
+//! Slopes come from least squares over the last [`SHORT_WINDOW`] and
+//! [`LONG_WINDOW`] samples with `x = 0, 1, …, n − 1`. For a fixed window the
+//! normal matrix `[[n, Σx], [Σx, Σx²]]` depends only on `n`, so its inverse is
+//! computed in closed form rather than by an iterative solver. The exponential
+//! model `y = A·e^{rt}` is the same fit on `ln y`. Records are sorted by
+//! `cycle_id` before analysis, so input order never changes the result.
+
+use super::classifier::classify_stability;
+use super::oscillation::detect_oscillations;
+use crate::{diagnostics::metrics::CycleRecord, Fx};
+
+/// Samples in the short (instantaneous) slope window.
+pub const SHORT_WINDOW: usize = 5;
+/// Samples in the long (smoothed) window used for every other statistic.
+pub const LONG_WINDOW: usize = 20;
+
+/// Trend summary of a metric history.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TrendModel {
+    /// Slope per sample over the last [`SHORT_WINDOW`] samples.
+    pub short_slope: Fx,
+    /// Slope per sample over the last [`LONG_WINDOW`] samples.
+    pub long_slope: Fx,
+    /// Population standard deviation over the long window.
+    pub stdev: Fx,
+    /// FFT oscillation score of the long window from [`detect_oscillations`], in `[0, 1]`.
+    pub oscillation_score: Fx,
+    /// Exponential rate `r` over the long window; 0 unless all samples are positive.
+    pub growth_rate: Fx,
+    /// Stability class id assigned by [`classify_stability`].
+    pub class_id: i8,
+}
+
+/// Straight line `y = intercept + slope·x`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LinearFit {
+    pub intercept: Fx,
+    pub slope: Fx,
+}
+
+/// Exponential curve `y = amplitude·e^{rate·x}`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExponentialFit {
+    pub amplitude: Fx,
+    pub rate: Fx,
+}
+
+/// Inverse of the normal matrix for `x = 0..n`, or `None` below two samples.
+fn normal_inverse(n: usize) -> Option<[[f64; 2]; 2]> {
+    if n < 2 {
+        return None;
+    }
+    let n = n as f64;
+    let sx = n * (n - 1.0) / 2.0;
+    let sxx = (n - 1.0) * n * (2.0 * n - 1.0) / 6.0;
+    let det = n * sxx - sx * sx;
+    Some([[sxx / det, -sx / det], [-sx / det, n / det]])
+}
+
+fn fit(values: impl Iterator<Item = f64>, n: usize) -> Option<(f64, f64)> {
+    let inv = normal_inverse(n)?;
+    let mut sy = 0.0;
+    let mut sxy = 0.0;
+    for (x, y) in values.enumerate() {
+        sy += y;
+        sxy += x as f64 * y;
+    }
+    Some((
+        inv[0][0] * sy + inv[0][1] * sxy,
+        inv[1][0] * sy + inv[1][1] * sxy,
+    ))
+}
+
+/// Least-squares line through `series`; flat at the first sample below two samples.
+pub fn fit_linear(series: &[Fx]) -> LinearFit {
+    match fit(series.iter().map(|&y| y as f64), series.len()) {
+        Some((intercept, slope)) => LinearFit {
+            intercept: intercept as Fx,
+            slope: slope as Fx,
+        },
+        None => LinearFit {
+            intercept: series.first().copied().unwrap_or(0.0),
+            slope: 0.0,
+        },
+    }
+}
+
+/// Least-squares exponential through `series`; `None` for non-positive samples.
+pub fn fit_exponential(series: &[Fx]) -> Option<ExponentialFit> {
+    if series.iter().any(|&y| !(y > 0.0 && y.is_finite())) {
+        return None;
+    }
+    let (log_amplitude, rate) = fit(series.iter().map(|&y| (y as f64).ln()), series.len())?;
+    Some(ExponentialFit {
+        amplitude: log_amplitude.exp() as Fx,
+        rate: rate as Fx,
+    })
+}
+
+fn tail(series: &[Fx], window: usize) -> &[Fx] {
+    &series[series.len().saturating_sub(window)..]
+}
+
+/// Builds a trend model from a chronologically ordered series.
+pub fn analyze_series(series: &[Fx]) -> TrendModel {
+    let long = tail(series, LONG_WINDOW);
+    if long.len() < 2 {
+        return TrendModel::default();
+    }
+    let n = long.len() as Fx;
+    let mean = long.iter().sum::<Fx>() / n;
+    let stdev = (long.iter().map(|v| (v - mean) * (v - mean)).sum::<Fx>() / n).sqrt();
+    let mut model = TrendModel {
+        short_slope: fit_linear(tail(series, SHORT_WINDOW)).slope,
+        long_slope: fit_linear(long).slope,
+        stdev,
+        oscillation_score: detect_oscillations(long),
+        growth_rate: fit_exponential(long).map_or(0.0, |fit| fit.rate),
+        class_id: 0,
+    };
+    model.class_id = classify_stability(&model);
+    model
+}
+
+/// Analyzes the coherence of `records` after sorting them by `cycle_id`.
+pub fn analyze_trends(records: &[CycleRecord]) -> TrendModel {
+    analyze_trends_by(records, |record| record.coherence)
+}
+
+/// Analyzes the metric selected by `metric` after sorting by `cycle_id`.
+pub fn analyze_trends_by(
+    records: &[CycleRecord],
+    metric: impl Fn(&CycleRecord) -> Fx,
+) -> TrendModel {
+    analyze_series(&ordered_series(records, metric))
+}
+
+/// Extracts `metric` from `records` in `cycle_id` order.
+pub fn ordered_series(records: &[CycleRecord], metric: impl Fn(&CycleRecord) -> Fx) -> Vec<Fx> {
+    let mut ordered: Vec<&CycleRecord> = records.iter().collect();
+    ordered.sort_by_key(|record| record.cycle_id);
+    ordered.into_iter().map(metric).collect()
+}
+
+/// Confirms two trend models are bit-for-bit identical.
+pub fn verify_trend_determinism(a: &TrendModel, b: &TrendModel) -> bool {
+    let bits = |m: &TrendModel| {
+        [
+            m.short_slope.to_bits(),
+            m.long_slope.to_bits(),
+            m.stdev.to_bits(),
+            m.oscillation_score.to_bits(),
+            m.growth_rate.to_bits(),
+        ]
+    };
+    bits(a) == bits(b) && a.class_id == b.class_id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::continuity::StabilityClass;
+
+    #[test]
+    fn fits_recover_linear_and_exponential_series() {
+        let line: Vec<Fx> = (0..8).map(|x| 0.5 + 0.25 * x as Fx).collect();
+        let fit = fit_linear(&line);
+        assert!((fit.slope - 0.25).abs() < 1e-6);
+        assert!((fit.intercept - 0.5).abs() < 1e-6);
+        let curve: Vec<Fx> = (0..8).map(|x| 2.0 * (-0.1 * x as Fx).exp()).collect();
+        let exp = fit_exponential(&curve).unwrap();
+        assert!((exp.rate + 0.1).abs() < 1e-5);
+        assert!((exp.amplitude - 2.0).abs() < 1e-4);
+        assert!(fit_exponential(&[1.0, 0.0]).is_none());
+    }
+
+    #[test]
+    fn short_window_reacts_before_long_window() {
+        // Twenty cycles of growth, then a recent drop.
+        let mut series: Vec<Fx> = (0..20).map(|x| 0.3 + 0.02 * x as Fx).collect();
+        series.extend([0.66, 0.64, 0.62, 0.60]);
+        let model = analyze_series(&series);
+        assert!(model.short_slope < 0.0);
+        assert!(model.long_slope > model.short_slope);
+        assert_ne!(model.class_id, StabilityClass::Degradation.id());
+        let falling: Vec<Fx> = series[..20].iter().rev().copied().collect();
+        assert_eq!(
+            analyze_series(&falling).class_id,
+            StabilityClass::Degradation.id()
+        );
+    }
+
+    #[test]
+    fn oscillation_score_is_the_fft_score_of_the_long_window() {
+        let mut series: Vec<Fx> = (0..24).map(|i| 0.5 + 0.015 * i as Fx).collect();
+        series[14] -= 0.02;
+        let model = analyze_series(&series);
+        let long = &series[series.len() - LONG_WINDOW..];
+        assert_eq!(model.oscillation_score, detect_oscillations(long));
+        // One dip is a single residual spike, not a periodic swing.
+        assert!(model.oscillation_score < 0.2, "{:?}", model);
+        let wave: Vec<Fx> = (0..20).map(|i| 0.5 + 0.1 * (i % 2) as Fx).collect();
+        assert!(analyze_series(&wave).oscillation_score > 0.9);
+    }
+
+    #[test]
+    fn records_are_sorted_before_analysis() {
+        let records: Vec<CycleRecord> = [3, 0, 2, 1, 4]
+            .iter()
+            .map(|&id| CycleRecord {
+                cycle_id: id,
+                coherence: 0.5 + 0.02 * id as Fx,
+                ..CycleRecord::default()
+            })
+            .collect();
+        let model = analyze_trends(&records);
+        assert_eq!(model.class_id, StabilityClass::Improvement.id());
+        assert!((model.long_slope - 0.02).abs() < 1e-6);
+        let mut sorted = records.clone();
+        sorted.sort_by_key(|r| r.cycle_id);
+        assert!(verify_trend_determinism(&model, &analyze_trends(&sorted)));
+        let mut nudged = model.clone();
+        nudged.stdev = Fx::from_bits(model.stdev.to_bits() ^ 1);
+        assert!(!verify_trend_determinism(&model, &nudged));
+    }
+}
@@ -0,0 +1,155 @@
+//! Trend and oscillation metrics over cycle histories.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/metrics/spec.md`
+//!
+//! Metrics are taken over the coherence series. The slope is the least-squares
+//! fit against `cycle_id`, the deviation is the population standard deviation,
+//! and the oscillation index is `1 − |ΣΔ| / Σ|Δ|` over consecutive
+//! differences: 0 for a monotone series, approaching 1 for a zig-zag.
+
+use crate::Fx;
+
+/// Trend class for series without significant movement.
+pub const TREND_STABLE: i8 = 0;
+/// Trend class for rising coherence.
+pub const TREND_GROWTH: i8 = 1;
+/// Trend class for falling coherence.
+pub const TREND_DECAY: i8 = -1;
+/// Trend class for series that keep reversing direction.
+pub const TREND_OSCILLATORY: i8 = 2;
+
+/// Oscillation index above which a moving series is classed oscillatory.
+pub const OSCILLATION_LIMIT: Fx = 0.5;
+/// Smallest per-cycle slope counted as growth or decay.
+pub const TREND_SLOPE_EPSILON: Fx = 1e-3;
+/// Standard deviation below which a series is treated as flat.
+pub const STABLE_STDEV: Fx = 1e-3;
+
+/// Per-cycle summary as logged by the Chronicle.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CycleRecord {
+    pub cycle_id: u64,
+    pub coherence: Fx,
+    pub energy_total: Fx,
+    pub delta_e: Fx,
+}
+
+/// Slope, spread, and oscillation of a cycle history.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ContinuityMetrics {
+    pub slope: Fx,
+    pub stdev: Fx,
+    pub oscillation_index: Fx,
+    /// One of the `TREND_*` classes.
+    pub trend_class: i8,
+}
+
+/// Derives continuity metrics from the coherence of `records`.
+///
+/// Fewer than two records yield default (stable) metrics.
+pub fn continuity_from_history(records: &[CycleRecord]) -> ContinuityMetrics {
+    if records.len() < 2 {
+        return ContinuityMetrics::default();
+    }
+    let n = records.len() as Fx;
+    let mean_t = records.iter().map(|r| r.cycle_id as Fx).sum::<Fx>() / n;
+    let mean_c = records.iter().map(|r| r.coherence).sum::<Fx>() / n;
+    let mut cov = 0.0;
+    let mut var_t = 0.0;
+    let mut var_c = 0.0;
+    for record in records {
+        let dt = record.cycle_id as Fx - mean_t;
+        let dc = record.coherence - mean_c;
+        cov += dt * dc;
+        var_t += dt * dt;
+        var_c += dc * dc;
+    }
+    let slope = if var_t <= Fx::EPSILON {
+        0.0
+    } else {
+        cov / var_t
+    };
+    let stdev = (var_c / n).sqrt();
+
+    let coherence: Vec<Fx> = records.iter().map(|r| r.coherence).collect();
+    let oscillation_index = oscillation_index(&coherence);
+    ContinuityMetrics {
+        slope,
+        stdev,
+        oscillation_index,
+        trend_class: trend_class(slope, stdev, oscillation_index),
+    }
+}
+
+/// `1 − |ΣΔ| / Σ|Δ|` over consecutive differences of `series`.
+pub fn oscillation_index(series: &[Fx]) -> Fx {
+    let mut net = 0.0;
+    let mut path = 0.0;
+    for pair in series.windows(2) {
+        let step = pair[1] - pair[0];
+        net += step;
+        path += step.abs();
+    }
+    if path <= Fx::EPSILON {
+        0.0
+    } else {
+        (1.0 - net.abs() / path).max(0.0)
+    }
+}
+
+/// Maps slope, spread, and oscillation onto a `TREND_*` class.
+pub fn trend_class(slope: Fx, stdev: Fx, oscillation_index: Fx) -> i8 {
+    if stdev < STABLE_STDEV {
+        TREND_STABLE
+    } else if oscillation_index > OSCILLATION_LIMIT {
+        TREND_OSCILLATORY
+    } else if slope > TREND_SLOPE_EPSILON {
+        TREND_GROWTH
+    } else if slope < -TREND_SLOPE_EPSILON {
+        TREND_DECAY
+    } else {
+        TREND_STABLE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(values: &[Fx]) -> Vec<CycleRecord> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(idx, &coherence)| CycleRecord {
+                cycle_id: idx as u64,
+                coherence,
+                ..CycleRecord::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn classes_cover_decay_flat_and_short_histories() {
+        let decay = continuity_from_history(&history(&[0.9, 0.8, 0.75, 0.6]));
+        assert_eq!(decay.trend_class, TREND_DECAY);
+        assert_eq!(decay.oscillation_index, 0.0);
+        let flat = continuity_from_history(&history(&[0.5, 0.5, 0.5]));
+        assert_eq!(flat.trend_class, TREND_STABLE);
+        assert_eq!(
+            continuity_from_history(&history(&[0.7])),
+            ContinuityMetrics::default()
+        );
+    }
+
+    #[test]
+    fn small_noise_keeps_the_growth_class() {
+        let clean = continuity_from_history(&history(&[0.60, 0.62, 0.64, 0.66, 0.68]));
+        let noisy = continuity_from_history(&history(&[0.606, 0.614, 0.646, 0.653, 0.687]));
+        assert_eq!(clean.trend_class, TREND_GROWTH);
+        assert_eq!(noisy.trend_class, clean.trend_class);
+    }
+}
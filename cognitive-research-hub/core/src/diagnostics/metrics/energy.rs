@@ -0,0 +1,268 @@
+//! Energy conservation audit across the chromatic ↔ spectral bridge.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/bridge/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/metrics/spec.md`
+//!
+//! The bridge maps per-cell saturation onto hue bins, so chromatic energy is
+//! measured on saturation: `Σs/N` (L1) or `Σs²/N` (L2). Spectral energy is
+//! [`spectral_energy`] (L1) or `Σb²` (L2). Deltas are `10·log10(after/before)`
+//! and must stay within [`ENERGY_TOLERANCE_DB`]. L1 is conserved by the
+//! linear hue-bin split; L2 also exposes energy spread across bins.
+
+use crate::{
+    bridge::{decode_to_chromatic, encode_to_spectral},
+    error::{CoreResult, DreamError},
+    tensor::{rgb_to_hsl, spectral_energy, ChromaticTensor, SpectralTensor},
+    Fx,
+};
+
+/// Maximum allowed |ΔEnergy| across the bridge in decibels.
+pub const ENERGY_TOLERANCE_DB: Fx = 0.5;
+
+/// Norm used to measure energy on both sides of the bridge.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EnergyNorm {
+    /// Sum of magnitudes.
+    #[default]
+    L1,
+    /// Sum of squared magnitudes.
+    L2,
+}
+
+/// Mean saturation energy of a chromatic tensor.
+pub fn chromatic_energy(tensor: &ChromaticTensor, norm: EnergyNorm) -> Fx {
+    let mut total = 0.0;
+    for px in tensor.rgb.chunks(3) {
+        let (_, s, _) = rgb_to_hsl(px[0], px[1], px[2]);
+        total += match norm {
+            EnergyNorm::L1 => s,
+            EnergyNorm::L2 => s * s,
+        };
+    }
+    total / tensor.shape.cell_count().max(1) as Fx
+}
+
+/// Energy of the spectral bins under `norm`.
+pub fn spectral_energy_with(spectrum: &SpectralTensor, norm: EnergyNorm) -> Fx {
+    match norm {
+        EnergyNorm::L1 => spectral_energy(spectrum),
+        EnergyNorm::L2 => spectrum.bins.iter().fold(0.0, |acc, &v| acc + v * v),
+    }
+}
+
+/// `10·log10(after / before)`; `0` when both are silent, `±∞` when only one is.
+pub fn energy_ratio_db(after: Fx, before: Fx) -> Fx {
+    match (after <= Fx::EPSILON, before <= Fx::EPSILON) {
+        (true, true) => 0.0,
+        (false, true) => Fx::INFINITY,
+        (true, false) => Fx::NEG_INFINITY,
+        (false, false) => 10.0 * (after / before).log10(),
+    }
+}
+
+/// Energies of one tensor before and after the bridge.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnergySample {
+    /// Position of the tensor in the audited batch.
+    pub index: usize,
+    pub chromatic: Fx,
+    /// Energy of `encode_to_spectral(tensor)`.
+    pub spectral: Fx,
+    /// Energy of `decode_to_chromatic(encode_to_spectral(tensor))`.
+    pub decoded: Fx,
+    /// Encode delta, spectral over chromatic.
+    pub delta_db: Fx,
+    /// Round-trip delta, decoded over chromatic.
+    pub round_trip_db: Fx,
+}
+
+impl EnergySample {
+    /// Returns `true` when both deltas stay within `tolerance_db`.
+    pub fn within(&self, tolerance_db: Fx) -> bool {
+        self.delta_db.abs() <= tolerance_db && self.round_trip_db.abs() <= tolerance_db
+    }
+}
+
+/// Per-sample and per-batch results of an [`EnergyAudit`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnergyAuditReport {
+    pub norm: EnergyNorm,
+    pub tolerance_db: Fx,
+    pub samples: Vec<EnergySample>,
+    /// Encode delta of the summed batch energies.
+    pub batch_delta_db: Fx,
+    /// Round-trip delta of the summed batch energies.
+    pub batch_round_trip_db: Fx,
+    /// Largest absolute per-sample delta of either kind.
+    pub max_abs_delta_db: Fx,
+    /// Indices of samples outside the tolerance.
+    pub violations: Vec<usize>,
+}
+
+impl EnergyAuditReport {
+    /// Returns `true` when no sample or batch delta exceeds the tolerance.
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+            && self.batch_delta_db.abs() <= self.tolerance_db
+            && self.batch_round_trip_db.abs() <= self.tolerance_db
+    }
+}
+
+/// Measures energy on both sides of `encode_to_spectral`/`decode_to_chromatic`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EnergyAudit {
+    pub norm: EnergyNorm,
+    pub tolerance_db: Fx,
+    /// Turn violations into [`DreamError::Validation`].
+    pub strict: bool,
+}
+
+impl Default for EnergyAudit {
+    fn default() -> Self {
+        Self {
+            norm: EnergyNorm::L1,
+            tolerance_db: ENERGY_TOLERANCE_DB,
+            strict: false,
+        }
+    }
+}
+
+impl EnergyAudit {
+    pub fn new(norm: EnergyNorm) -> Self {
+        Self {
+            norm,
+            ..Self::default()
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn tolerance_db(mut self, tolerance_db: Fx) -> Self {
+        self.tolerance_db = tolerance_db;
+        self
+    }
+
+    /// Audits a single tensor as sample `index`.
+    pub fn measure(&self, index: usize, tensor: &ChromaticTensor) -> EnergySample {
+        let spectrum = encode_to_spectral(tensor);
+        let decoded = decode_to_chromatic(&spectrum);
+        let chromatic = chromatic_energy(tensor, self.norm);
+        let spectral = spectral_energy_with(&spectrum, self.norm);
+        let decoded = chromatic_energy(&decoded, self.norm);
+        EnergySample {
+            index,
+            chromatic,
+            spectral,
+            decoded,
+            delta_db: energy_ratio_db(spectral, chromatic),
+            round_trip_db: energy_ratio_db(decoded, chromatic),
+        }
+    }
+
+    /// Audits a batch; in strict mode a failing report becomes an error.
+    pub fn run(&self, tensors: &[ChromaticTensor]) -> CoreResult<EnergyAuditReport> {
+        if !(self.tolerance_db.is_finite() && self.tolerance_db >= 0.0) {
+            return Err(DreamError::Config(format!(
+                "energy tolerance must be a non-negative number of dB, got {}",
+                self.tolerance_db
+            )));
+        }
+        let samples: Vec<EnergySample> = tensors
+            .iter()
+            .enumerate()
+            .map(|(index, tensor)| self.measure(index, tensor))
+            .collect();
+        let sum = |f: fn(&EnergySample) -> Fx| samples.iter().map(f).sum::<Fx>();
+        let chromatic = sum(|s| s.chromatic);
+        let report = EnergyAuditReport {
+            norm: self.norm,
+            tolerance_db: self.tolerance_db,
+            batch_delta_db: energy_ratio_db(sum(|s| s.spectral), chromatic),
+            batch_round_trip_db: energy_ratio_db(sum(|s| s.decoded), chromatic),
+            max_abs_delta_db: samples
+                .iter()
+                .map(|s| s.delta_db.abs().max(s.round_trip_db.abs()))
+                .fold(0.0, Fx::max),
+            violations: samples
+                .iter()
+                .filter(|s| !s.within(self.tolerance_db))
+                .map(|s| s.index)
+                .collect(),
+            samples,
+        };
+        if self.strict && !report.passed() {
+            return Err(DreamError::Validation(format!(
+                "energy audit exceeded ±{} dB: batch {:.3} dB, max {:.3} dB, {} violating sample(s) {:?}",
+                report.tolerance_db,
+                report.batch_delta_db,
+                report.max_abs_delta_db,
+                report.violations.len(),
+                report.violations
+            )));
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::{hsl_to_rgb, Shape2D};
+    use std::f32::consts::PI;
+
+    fn uniform(h: Fx, s: Fx, l: Fx) -> ChromaticTensor {
+        let shape = Shape2D::new(2, 2);
+        let (r, g, b) = hsl_to_rgb(h, s, l);
+        ChromaticTensor::new(shape, [r, g, b].repeat(shape.cell_count()), None)
+    }
+
+    #[test]
+    fn uniform_tensors_conserve_energy_in_both_norms() {
+        // Hues on bin centres, so L2 is not split across neighbouring bins.
+        let batch = [uniform(0.0, 0.6, 0.5), uniform(2.0 * PI / 3.0, 0.3, 0.4)];
+        for norm in [EnergyNorm::L1, EnergyNorm::L2] {
+            let report = EnergyAudit::new(norm).strict(true).run(&batch).unwrap();
+            assert!(report.passed());
+            assert!(report.max_abs_delta_db < 0.05, "{:?}", report);
+        }
+    }
+
+    #[test]
+    fn strict_mode_rejects_energy_lost_to_averaging() {
+        // Two saturations on one hue collapse into a single bin: L1 survives,
+        // but L2 drops from (0.81 + 0.01) / 2 to 0.5² (about −2.2 dB).
+        let shape = Shape2D::new(1, 2);
+        let (a, b) = (hsl_to_rgb(PI, 0.9, 0.5), hsl_to_rgb(PI, 0.1, 0.5));
+        let mixed = ChromaticTensor::new(shape, vec![a.0, a.1, a.2, b.0, b.1, b.2], None);
+        let batch = [uniform(0.0, 0.6, 0.5), mixed];
+        assert!(EnergyAudit::new(EnergyNorm::L1)
+            .strict(true)
+            .run(&batch)
+            .is_ok());
+        let lenient = EnergyAudit::new(EnergyNorm::L2).run(&batch).unwrap();
+        assert_eq!(lenient.violations, vec![1]);
+        assert!((lenient.samples[1].delta_db + 2.15).abs() < 0.05);
+        let strict = EnergyAudit::new(EnergyNorm::L2).strict(true).run(&batch);
+        assert!(matches!(strict, Err(DreamError::Validation(_))));
+        assert!(matches!(
+            EnergyAudit::default().tolerance_db(-1.0).run(&batch),
+            Err(DreamError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn silent_inputs_report_zero_decibels() {
+        let shape = Shape2D::new(1, 1);
+        let grey = ChromaticTensor::new(shape, vec![0.5; 3], None);
+        let sample = EnergyAudit::default().measure(0, &grey);
+        assert_eq!(sample.delta_db, 0.0);
+        assert_eq!(energy_ratio_db(0.0, 1.0), Fx::NEG_INFINITY);
+    }
+}
@@ -1,3 +1,69 @@
-//! Placeholder for color-space metrics (ΔH, ΔS, ΔL).
+//! Color-space metrics (ΔH, ΔS, ΔL).
 //!
-//! Implements `compute_delta_hsl` as defined in `metrics/spec.md`.
\ No newline at end of file
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/metrics/spec.md`
+//!
+//! Hue differences are wrapped with `atan2(sin Δ, cos Δ)` so the red seam
+//! never produces a spurious jump; all components are reported as absolute
+//! values averaged over cells in row-major order.
+
+use crate::{
+    tensor::{delta_hsl, rgb_to_hsl, ChromaticTensor},
+    Fx,
+};
+
+/// Mean per-cell HSL difference between two chromatic tensors.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChromaticDelta {
+    /// Mean absolute hue difference in radians.
+    pub delta_h: Fx,
+    /// Mean absolute saturation difference.
+    pub delta_s: Fx,
+    /// Mean absolute luminance difference.
+    pub delta_l: Fx,
+    /// Mean per-cell Euclidean magnitude `√(ΔH² + ΔS² + ΔL²)`.
+    pub magnitude: Fx,
+}
+
+/// Computes seam-safe ΔHSL averages between `a` and `b`.
+pub fn compute_delta_hsl(a: &ChromaticTensor, b: &ChromaticTensor) -> ChromaticDelta {
+    assert_eq!(a.shape, b.shape, "delta HSL needs equal shapes");
+    let mut sums = [0.0; 4];
+    for (pa, pb) in a.rgb.chunks(3).zip(b.rgb.chunks(3)) {
+        let (dh, ds, dl) = delta_hsl(
+            rgb_to_hsl(pa[0], pa[1], pa[2]),
+            rgb_to_hsl(pb[0], pb[1], pb[2]),
+        );
+        sums[0] += dh.abs();
+        sums[1] += ds.abs();
+        sums[2] += dl.abs();
+        sums[3] += (dh * dh + ds * ds + dl * dl).sqrt();
+    }
+    let cells = a.shape.cell_count().max(1) as Fx;
+    ChromaticDelta {
+        delta_h: sums[0] / cells,
+        delta_s: sums[1] / cells,
+        delta_l: sums[2] / cells,
+        magnitude: sums[3] / cells,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::Shape2D;
+
+    #[test]
+    fn hue_seam_is_wrapped() {
+        let shape = Shape2D::new(1, 1);
+        // Hues just either side of red: ~0.1 rad apart, not ~2π.
+        let a = ChromaticTensor::new(shape, vec![1.0, 0.0, 0.05], None);
+        let b = ChromaticTensor::new(shape, vec![1.0, 0.05, 0.0], None);
+        let delta = compute_delta_hsl(&a, &b);
+        assert!(delta.delta_h < 0.2);
+        assert_eq!(compute_delta_hsl(&a, &a), ChromaticDelta::default());
+    }
+}
@@ -9,13 +9,76 @@
 //! - `cognitive-research-hub/core/src/diagnostics/spec.md`
 //! - `cognitive-research-hub/core/src/diagnostics/metrics/spec.md`
 
-// Declare the sub-modules required by the spec's File Layout
 pub mod continuity;
+pub mod energy;
 pub mod hsl;
 pub mod spectral;
 
-// Re-export key data structures and functions for easier access
-// (Placeholders for when implementation is added)
-// pub use self::continuity::{ContinuityMetrics, continuity_from_history};
-// pub use self::hsl::{ChromaticDelta, compute_delta_hsl};
-// pub use self::spectral::{SpectralStats, spectral_energy_balance, phase_coherence_index};
\ No newline at end of file
+pub use self::continuity::{
+    continuity_from_history, oscillation_index, trend_class, ContinuityMetrics, CycleRecord,
+    OSCILLATION_LIMIT, STABLE_STDEV, TREND_DECAY, TREND_GROWTH, TREND_OSCILLATORY,
+    TREND_SLOPE_EPSILON, TREND_STABLE,
+};
+pub use self::energy::{
+    chromatic_energy, energy_ratio_db, spectral_energy_with, EnergyAudit, EnergyAuditReport,
+    EnergyNorm, EnergySample, ENERGY_TOLERANCE_DB,
+};
+pub use self::hsl::{compute_delta_hsl, ChromaticDelta};
+pub use self::spectral::{
+    energy_drift_db, phase_coherence_index, spectral_energy_balance,
+    spectral_energy_balance_against, SpectralStats,
+};
+
+/// All metrics captured for one cycle.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    pub chromatic: ChromaticDelta,
+    pub spectral: SpectralStats,
+    pub continuity: ContinuityMetrics,
+}
+
+impl MetricsSnapshot {
+    fn bits(&self) -> [u32; 12] {
+        let c = &self.chromatic;
+        let s = &self.spectral;
+        let t = &self.continuity;
+        [
+            c.delta_h.to_bits(),
+            c.delta_s.to_bits(),
+            c.delta_l.to_bits(),
+            c.magnitude.to_bits(),
+            s.energy_total.to_bits(),
+            s.energy_drift.to_bits(),
+            s.centroid.to_bits(),
+            s.coherence.to_bits(),
+            t.slope.to_bits(),
+            t.stdev.to_bits(),
+            t.oscillation_index.to_bits(),
+            t.trend_class as u32,
+        ]
+    }
+}
+
+/// Confirms two snapshots are bit-for-bit identical.
+///
+/// Unlike `==`, this treats matching NaNs as equal and `0.0` and `-0.0` as
+/// different.
+pub fn validate_determinism(metrics_a: &MetricsSnapshot, metrics_b: &MetricsSnapshot) -> bool {
+    metrics_a.bits() == metrics_b.bits()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Fx;
+
+    #[test]
+    fn determinism_compares_bits() {
+        let mut a = MetricsSnapshot::default();
+        a.spectral.coherence = Fx::NAN;
+        let mut b = a.clone();
+        assert!(validate_determinism(&a, &b));
+        b.continuity.slope = -0.0;
+        assert!(!validate_determinism(&a, &b));
+    }
+}
@@ -1,4 +1,115 @@
-//! Placeholder for spectral energy, drift, and coherence metrics.
+//! Spectral energy, drift, and coherence metrics.
 //!
-//! Implements `spectral_energy_balance` and `phase_coherence_index`
-//! as defined in `metrics/spec.md`.
\ No newline at end of file
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/metrics/spec.md`
+//!
+//! Energy drift follows `E_drift = 10·log10(Σf² / Σf₀²)` in decibels against a
+//! baseline spectrum. `SpectralTensor` stores magnitudes only, so the phase
+//! coherence index treats neighbouring bins as consecutive frames and reports
+//! the cosine similarity of the spectrum with its one-bin shift.
+
+use crate::{
+    tensor::{spectral_centroid, spectral_energy, SpectralTensor},
+    Fx,
+};
+
+/// Energy balance summary of one spectrum.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpectralStats {
+    /// L1 energy of the bins.
+    pub energy_total: Fx,
+    /// Drift from the baseline in dB; zero when no baseline is given.
+    pub energy_drift: Fx,
+    /// Amplitude-weighted mean frequency.
+    pub centroid: Fx,
+    /// Phase alignment index in `[-1, 1]`.
+    pub coherence: Fx,
+}
+
+/// Calculates energy, centroid, and coherence of `spectrum` with zero drift.
+pub fn spectral_energy_balance(spectrum: &SpectralTensor) -> SpectralStats {
+    SpectralStats {
+        energy_total: spectral_energy(spectrum),
+        energy_drift: 0.0,
+        centroid: spectral_centroid(spectrum),
+        coherence: phase_coherence_index(spectrum),
+    }
+}
+
+/// Like [`spectral_energy_balance`], with drift measured against `baseline`.
+pub fn spectral_energy_balance_against(
+    spectrum: &SpectralTensor,
+    baseline: &SpectralTensor,
+) -> SpectralStats {
+    SpectralStats {
+        energy_drift: energy_drift_db(spectrum, baseline),
+        ..spectral_energy_balance(spectrum)
+    }
+}
+
+fn power(spectrum: &SpectralTensor) -> Fx {
+    spectrum.bins.iter().fold(0.0, |acc, &v| acc + v * v)
+}
+
+/// Power ratio of `spectrum` over `baseline` in decibels.
+///
+/// Two silent spectra report `0`; energy appearing from a silent baseline
+/// reports `+∞`.
+pub fn energy_drift_db(spectrum: &SpectralTensor, baseline: &SpectralTensor) -> Fx {
+    let (current, reference) = (power(spectrum), power(baseline));
+    if reference <= Fx::EPSILON {
+        return if current <= Fx::EPSILON {
+            0.0
+        } else {
+            Fx::INFINITY
+        };
+    }
+    10.0 * (current.max(Fx::MIN_POSITIVE) / reference).log10()
+}
+
+/// Cosine similarity between adjacent bins; 1 for a single bin.
+pub fn phase_coherence_index(spectrum: &SpectralTensor) -> Fx {
+    let bins = &spectrum.bins;
+    if bins.len() < 2 {
+        return 1.0;
+    }
+    let mut dot = 0.0;
+    let mut head = 0.0;
+    let mut tail = 0.0;
+    for pair in bins.windows(2) {
+        dot += pair[0] * pair[1];
+        head += pair[0] * pair[0];
+        tail += pair[1] * pair[1];
+    }
+    let (silent_head, silent_tail) = (head <= Fx::EPSILON, tail <= Fx::EPSILON);
+    if silent_head || silent_tail {
+        return if silent_head && silent_tail { 1.0 } else { 0.0 };
+    }
+    (dot / (head.sqrt() * tail.sqrt())).clamp(-1.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drift_is_reported_in_decibels() {
+        let base = SpectralTensor::new(vec![1.0, 1.0], None, 10.0, 5.0, false);
+        let louder = SpectralTensor::new(vec![2.0, 2.0], None, 10.0, 5.0, false);
+        // Doubling amplitude quadruples power: +6.02 dB.
+        assert!((energy_drift_db(&louder, &base) - 6.0206).abs() < 1e-3);
+        assert_eq!(energy_drift_db(&base, &base), 0.0);
+        let stats = spectral_energy_balance_against(&louder, &base);
+        assert_eq!(stats.energy_total, 4.0);
+        assert_eq!(stats.energy_drift, energy_drift_db(&louder, &base));
+    }
+
+    #[test]
+    fn alternating_bins_have_negative_coherence() {
+        let spec = SpectralTensor::new(vec![1.0, -1.0, 1.0, -1.0], None, 10.0, 5.0, false);
+        assert!((phase_coherence_index(&spec) + 1.0).abs() < 1e-6);
+    }
+}
@@ -3,7 +3,25 @@
 //! This module organizes all diagnostic sub-systems, including
 //! metrics, visualizers, and temporal continuity analysis,
 //! as defined in `diagnostics/spec.md`.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
 
 pub mod continuity;
 pub mod metrics;
-pub mod visual;
\ No newline at end of file
+pub mod visual;
+
+use self::metrics::{continuity_from_history, CycleRecord, TREND_OSCILLATORY};
+
+/// Returns `true` when the history's coherence does not oscillate and every
+/// record is finite.
+pub fn validate_continuity(history: &[CycleRecord]) -> bool {
+    let finite = history.iter().all(|record| {
+        record.coherence.is_finite()
+            && record.energy_total.is_finite()
+            && record.delta_e.is_finite()
+    });
+    finite && continuity_from_history(history).trend_class != TREND_OSCILLATORY
+}
@@ -0,0 +1,275 @@
+//! Plot frames, axis ticks, legends, and colour bars.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/visual/spec.md`
+//!
+//! Ticks fall on multiples of 1, 2, or 5 times a power of ten, computed as
+//! integer multiples of the step so that float error never accumulates.
+//! Everything draws through [`Surface`], so raster and SVG output share one
+//! layout.
+
+use super::colormap::{Colormap, Normalization};
+use super::renderer::{Rgb8, Surface, BLACK};
+use super::svg::TextAnchor;
+use crate::Fx;
+
+/// Approximate number of ticks per axis.
+pub const TICK_TARGET: usize = 5;
+/// Tick mark length in pixels.
+pub const TICK_LENGTH: Fx = 3.0;
+/// Colour of frame lines, ticks, and labels.
+pub const AXIS_COLOR: Rgb8 = BLACK;
+
+/// Step of 1, 2, 5, or 10 times a power of ten covering `span` in about `target` steps.
+pub fn tick_step(span: Fx, target: usize) -> Fx {
+    let span = span.abs() as f64;
+    if span.is_nan() || span <= 0.0 || span.is_infinite() {
+        return 1.0;
+    }
+    let raw = span / target.max(1) as f64;
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|&step| step >= raw)
+        .unwrap_or(10.0 * magnitude);
+    step as Fx
+}
+
+/// Tick values inside `[min, max]`.
+pub fn nice_ticks(min: Fx, max: Fx, target: usize) -> Vec<Fx> {
+    let (lo, hi) = (min.min(max) as f64, min.max(max) as f64);
+    if !(lo.is_finite() && hi.is_finite()) {
+        return Vec::new();
+    }
+    let step = tick_step((hi - lo) as Fx, target) as f64;
+    let first = (lo / step - 1e-6).ceil() as i64;
+    let last = (hi / step + 1e-6).floor() as i64;
+    (first..=last).map(|k| (k as f64 * step) as Fx).collect()
+}
+
+/// Formats a tick with as many decimals as `step` needs.
+pub fn format_tick(value: Fx, step: Fx) -> String {
+    let decimals = if step > 0.0 && step < 1.0 {
+        (-(step as f64).log10().floor()).clamp(0.0, 6.0) as usize
+    } else {
+        0
+    };
+    let text = format!("{:.*}", decimals, value);
+    match text.strip_prefix('-') {
+        Some(rest) if rest.chars().all(|c| c == '0' || c == '.') => rest.to_string(),
+        _ => text,
+    }
+}
+
+/// Rectangular plot area mapping data coordinates to pixels, `y` upwards.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotFrame {
+    pub left: Fx,
+    pub top: Fx,
+    pub width: Fx,
+    pub height: Fx,
+    pub x_range: (Fx, Fx),
+    pub y_range: (Fx, Fx),
+    pub x_ticks: Vec<Fx>,
+    pub y_ticks: Vec<Fx>,
+}
+
+fn widen(range: (Fx, Fx)) -> (Fx, Fx) {
+    let (lo, hi) = range;
+    if !(lo.is_finite() && hi.is_finite()) {
+        return (0.0, 1.0);
+    }
+    if hi - lo <= Fx::EPSILON * lo.abs().max(1.0) {
+        return (lo - 0.5, hi + 0.5);
+    }
+    (lo, hi)
+}
+
+impl PlotFrame {
+    /// Frame with [`nice_ticks`] on both axes; empty ranges are widened by ±0.5.
+    pub fn new(
+        left: Fx,
+        top: Fx,
+        width: Fx,
+        height: Fx,
+        x_range: (Fx, Fx),
+        y_range: (Fx, Fx),
+    ) -> Self {
+        let (x_range, y_range) = (widen(x_range), widen(y_range));
+        Self {
+            left,
+            top,
+            width,
+            height,
+            x_ticks: nice_ticks(x_range.0, x_range.1, TICK_TARGET),
+            y_ticks: nice_ticks(y_range.0, y_range.1, TICK_TARGET),
+            x_range,
+            y_range,
+        }
+    }
+
+    /// Keeps only whole-number x ticks, for epoch axes.
+    pub fn integer_x_ticks(mut self) -> Self {
+        self.x_ticks.retain(|t| t.fract() == 0.0);
+        self
+    }
+
+    /// Keeps only whole-number y ticks, for bin axes.
+    pub fn integer_y_ticks(mut self) -> Self {
+        self.y_ticks.retain(|t| t.fract() == 0.0);
+        self
+    }
+
+    pub fn right(&self) -> Fx {
+        self.left + self.width
+    }
+
+    pub fn bottom(&self) -> Fx {
+        self.top + self.height
+    }
+
+    pub fn map_x(&self, value: Fx) -> Fx {
+        let (lo, hi) = self.x_range;
+        self.left + (value - lo) / (hi - lo) * self.width
+    }
+
+    pub fn map_y(&self, value: Fx) -> Fx {
+        let (lo, hi) = self.y_range;
+        self.bottom() - (value - lo) / (hi - lo) * self.height
+    }
+
+    /// Clamps `value` into the y range before mapping, for off-scale points.
+    pub fn map_y_clamped(&self, value: Fx) -> Fx {
+        let (lo, hi) = self.y_range;
+        if value.is_nan() {
+            return self.map_y(lo);
+        }
+        self.map_y(value.clamp(lo, hi))
+    }
+
+    /// Draws the frame, ticks, tick labels, and axis titles.
+    pub fn draw_axes(&self, surface: &mut impl Surface, x_label: &str, y_label: &str) {
+        let (l, t, r, b) = (self.left, self.top, self.right(), self.bottom());
+        surface.stroke_path(&[(l, t), (l, b), (r, b), (r, t), (l, t)], AXIS_COLOR);
+
+        let x_step = step_of(&self.x_ticks);
+        for &tick in &self.x_ticks {
+            let x = self.map_x(tick);
+            surface.stroke_line(x, b, x, b + TICK_LENGTH, AXIS_COLOR);
+            let text = format_tick(tick, x_step);
+            surface.draw_label(x, b + 5.0, &text, AXIS_COLOR, TextAnchor::Middle);
+        }
+        let y_step = step_of(&self.y_ticks);
+        for &tick in &self.y_ticks {
+            let y = self.map_y(tick);
+            surface.stroke_line(l - TICK_LENGTH, y, l, y, AXIS_COLOR);
+            let text = format_tick(tick, y_step);
+            surface.draw_label(l - 5.0, y - 3.0, &text, AXIS_COLOR, TextAnchor::End);
+        }
+        let centre = l + self.width / 2.0;
+        surface.draw_label(centre, b + 15.0, x_label, AXIS_COLOR, TextAnchor::Middle);
+        surface.draw_label(l, t - 10.0, y_label, AXIS_COLOR, TextAnchor::Start);
+    }
+}
+
+fn step_of(ticks: &[Fx]) -> Fx {
+    match ticks {
+        [a, b, ..] => b - a,
+        _ => 1.0,
+    }
+}
+
+/// Draws legend swatches and labels, one row per entry, from `(x, y)` down.
+pub fn draw_legend(surface: &mut impl Surface, x: Fx, y: Fx, entries: &[(&str, Rgb8)]) {
+    for (i, (label, color)) in entries.iter().enumerate() {
+        let row = y + i as Fx * 10.0;
+        surface.fill_area(x, row, 7.0, 7.0, *color, 1.0);
+        surface.draw_label(x + 10.0, row, label, AXIS_COLOR, TextAnchor::Start);
+    }
+}
+
+/// Draws a vertical colour bar in `[x, y, width, height]` with `norm.max` at the top.
+pub fn draw_colorbar(
+    surface: &mut impl Surface,
+    rect: [Fx; 4],
+    colormap: &Colormap,
+    norm: &Normalization,
+    label: &str,
+) {
+    let [x, y, width, height] = rect;
+    let steps = (height.max(1.0) as usize).min(colormap.table().len());
+    let band = height / steps as Fx;
+    for i in 0..steps {
+        let unit = 1.0 - (i as Fx + 0.5) / steps as Fx;
+        let top = y + i as Fx * band;
+        let next = y + (i + 1) as Fx * band;
+        surface.fill_area(x, top, width, next - top, colormap.sample(unit), 1.0);
+    }
+    let (r, b) = (x + width, y + height);
+    surface.stroke_path(&[(x, y), (x, b), (r, b), (r, y), (x, y)], AXIS_COLOR);
+    let step = tick_step(norm.max - norm.min, TICK_TARGET);
+    for (value, ty) in [(norm.max, y), (norm.min, b)] {
+        surface.stroke_line(r, ty, r + TICK_LENGTH, ty, AXIS_COLOR);
+        let text = format_tick(value, step);
+        surface.draw_label(r + 5.0, ty - 3.0, &text, AXIS_COLOR, TextAnchor::Start);
+    }
+    surface.draw_label(x, y - 10.0, label, AXIS_COLOR, TextAnchor::Start);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::visual::{ImageBuffer, SvgDocument, WHITE};
+
+    #[test]
+    fn ticks_use_one_two_five_steps() {
+        assert_eq!(nice_ticks(0.0, 1.0, 5), vec![0.0, 0.2, 0.4, 0.6, 0.8, 1.0]);
+        assert_eq!(nice_ticks(-0.7, 0.7, 5), vec![-0.5, 0.0, 0.5]);
+        assert_eq!(nice_ticks(0.0, 37.0, 5), vec![0.0, 10.0, 20.0, 30.0]);
+        assert_eq!(format_tick(0.2, 0.2), "0.2");
+        assert_eq!(format_tick(-0.0001, 0.5), "0.0");
+        assert_eq!(format_tick(20.0, 10.0), "20");
+    }
+
+    #[test]
+    fn frame_maps_ranges_with_y_upwards() {
+        let frame = PlotFrame::new(10.0, 5.0, 100.0, 50.0, (0.0, 10.0), (2.0, 2.0));
+        assert_eq!(frame.map_x(5.0), 60.0);
+        assert_eq!(frame.y_range, (1.5, 2.5));
+        assert_eq!(frame.map_y(1.5), 55.0);
+        assert_eq!(frame.map_y_clamped(9.0), 5.0);
+        let integer =
+            PlotFrame::new(0.0, 0.0, 10.0, 10.0, (-0.5, 2.5), (0.0, 1.0)).integer_x_ticks();
+        assert_eq!(integer.x_ticks, vec![0.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn axes_legend_and_colorbar_render_identically_twice() {
+        let draw = || {
+            let mut image = ImageBuffer::new(160, 120, WHITE);
+            let mut svg = SvgDocument::new(160, 120);
+            let frame = PlotFrame::new(30.0, 15.0, 90.0, 80.0, (0.0, 4.0), (-1.0, 1.0));
+            frame.draw_axes(&mut image, "epoch", "dB");
+            frame.draw_axes(&mut svg, "epoch", "dB");
+            let entries = [("energy", [200, 0, 0])];
+            draw_legend(&mut image, 35.0, 20.0, &entries);
+            draw_legend(&mut svg, 35.0, 20.0, &entries);
+            let norm = Normalization::default();
+            let map = Colormap::viridis();
+            draw_colorbar(&mut image, [130.0, 15.0, 8.0, 80.0], map, &norm, "C");
+            draw_colorbar(&mut svg, [130.0, 15.0, 8.0, 80.0], map, &norm, "C");
+            (image, svg.to_svg_string())
+        };
+        let (image, svg) = draw();
+        assert_eq!(draw(), (image.clone(), svg.clone()));
+        assert_eq!(
+            image.pixel(134, 16),
+            Some(Colormap::viridis().sample(1.0 - 1.5 / 80.0))
+        );
+        assert!(svg.contains(">epoch</text>") && svg.contains(">energy</text>"));
+    }
+}
@@ -0,0 +1,494 @@
+//! Per-cell heatmaps of chromatic tensors.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/visual/spec.md`
+//!
+//! A [`CellField`] holds one value per tensor cell: a ΔH/ΔS/ΔL/ΔE
+//! component, the `coh` channel, or the RGB gradient magnitude. Each cell is
+//! drawn as a `scale × scale` block (nearest-neighbour upscaling), optionally
+//! blended over the tensor's own colours, with a colour bar on the right.
+//! Cells whose value exceeds the outline threshold are boxed so round-trip
+//! failures can be located at a glance.
+
+use super::axes::draw_colorbar;
+use super::colormap::{coherence_normalization, Colormap, Normalization};
+use super::renderer::{quantize_rgb, ImageBuffer, Rgb8, Surface, WHITE};
+use super::svg::{Style, SvgDocument, TextAnchor};
+use crate::{
+    bridge::{decode_to_chromatic, encode_to_spectral},
+    error::{CoreResult, DreamError},
+    tensor::{delta_hsl, rgb_to_hsl, ChromaticTensor, Shape2D},
+    Fx,
+};
+
+/// Default pixels per tensor cell; a 12×12 tensor becomes 192×192.
+pub const DEFAULT_CELL_SCALE: u32 = 16;
+/// Default outline threshold, equal to the bridge round-trip tolerance.
+pub const ROUND_TRIP_OUTLINE: Fx = 1e-3;
+/// Colour of outlined cells.
+pub const OUTLINE_COLOR: Rgb8 = [214, 39, 40];
+
+const MARGIN: u32 = 8;
+const TITLE_SPACE: u32 = 16;
+const BAR_SPACE: u32 = 64;
+const BAR_WIDTH: Fx = 10.0;
+
+/// One scalar per tensor cell, in row-major order, with its colour scale.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CellField {
+    pub label: String,
+    pub shape: Shape2D,
+    pub values: Vec<Fx>,
+    pub colormap: &'static Colormap,
+    pub norm: Normalization,
+}
+
+impl CellField {
+    /// Magnitude field on the sequential map, normalized over `[0, max]`.
+    pub fn magnitude(label: &str, shape: Shape2D, values: Vec<Fx>) -> Self {
+        let norm = Normalization::fit(values.iter().copied().chain([0.0]));
+        Self {
+            label: label.to_string(),
+            shape,
+            values,
+            colormap: Colormap::viridis(),
+            norm,
+        }
+    }
+
+    /// Signed field on the diverging map, normalized symmetrically about zero.
+    pub fn signed(label: &str, shape: Shape2D, values: Vec<Fx>) -> Self {
+        let norm = Normalization::symmetric(values.iter().copied());
+        Self {
+            label: label.to_string(),
+            shape,
+            values,
+            colormap: Colormap::diverging(),
+            norm,
+        }
+    }
+
+    /// The `coh` channel on the coherence gradient; `None` when it is absent.
+    pub fn coherence(tensor: &ChromaticTensor) -> Option<Self> {
+        let values = tensor.coh.clone()?;
+        Some(Self {
+            label: "coh".to_string(),
+            shape: tensor.shape,
+            values,
+            colormap: Colormap::coherence(),
+            norm: coherence_normalization(),
+        })
+    }
+
+    /// RGB gradient magnitude `√Σ_c (∂x c)² + (∂y c)²` with central differences.
+    ///
+    /// Edge cells use their clamped neighbour, so one-sided steps count half.
+    pub fn gradient_magnitude(tensor: &ChromaticTensor) -> Self {
+        let Shape2D { h, w } = tensor.shape;
+        let mut values = Vec::with_capacity(h * w);
+        for row in 0..h {
+            for col in 0..w {
+                let (left, right) = (col.saturating_sub(1), (col + 1).min(w - 1));
+                let (up, down) = (row.saturating_sub(1), (row + 1).min(h - 1));
+                let (a, b) = (tensor.rgb_at(row, left), tensor.rgb_at(row, right));
+                let (c, d) = (tensor.rgb_at(up, col), tensor.rgb_at(down, col));
+                let sum: Fx = (0..3)
+                    .map(|i| {
+                        let (dx, dy) = ((b[i] - a[i]) / 2.0, (d[i] - c[i]) / 2.0);
+                        dx * dx + dy * dy
+                    })
+                    .sum();
+                values.push(sum.sqrt());
+            }
+        }
+        Self::magnitude("|grad|", tensor.shape, values)
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<Fx> {
+        if row >= self.shape.h || col >= self.shape.w {
+            return None;
+        }
+        Some(self.values[row * self.shape.w + col])
+    }
+
+    /// `(row, col)` of cells whose magnitude exceeds `threshold`.
+    pub fn exceeding(&self, threshold: Fx) -> Vec<(usize, usize)> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.is_nan() || v.abs() > threshold)
+            .map(|(i, _)| (i / self.shape.w, i % self.shape.w))
+            .collect()
+    }
+}
+
+/// Signed ΔH, ΔS, ΔL and the ΔE magnitude of every cell.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeltaFields {
+    pub delta_h: CellField,
+    pub delta_s: CellField,
+    pub delta_l: CellField,
+    pub delta_e: CellField,
+}
+
+impl DeltaFields {
+    /// Seam-safe per-cell differences `b − a`.
+    ///
+    /// `b` may be a 1×1 tensor, which is compared against every cell of `a`.
+    pub fn between(a: &ChromaticTensor, b: &ChromaticTensor) -> CoreResult<Self> {
+        let broadcast = b.shape == Shape2D::new(1, 1);
+        if a.shape != b.shape && !broadcast {
+            return Err(DreamError::Validation(format!(
+                "delta map needs equal shapes, got {}x{} and {}x{}",
+                a.shape.h, a.shape.w, b.shape.h, b.shape.w
+            )));
+        }
+        let pairs = (0..a.shape.h).flat_map(|row| {
+            (0..a.shape.w).map(move |col| {
+                let other = if broadcast { (0, 0) } else { (row, col) };
+                (a.rgb_at(row, col), b.rgb_at(other.0, other.1))
+            })
+        });
+        Ok(Self::from_pairs(a.shape, pairs))
+    }
+
+    /// Every cell against its own bridge round trip.
+    ///
+    /// The whole-tensor decode is a single mean colour, so comparing cells
+    /// with it would only show texture. Round-tripping each cell alone marks
+    /// exactly the colours the bridge cannot reproduce.
+    pub fn round_trip(tensor: &ChromaticTensor) -> Self {
+        let unit = Shape2D::new(1, 1);
+        let pairs = (0..tensor.shape.h).flat_map(|row| {
+            (0..tensor.shape.w).map(move |col| {
+                let rgb = tensor.rgb_at(row, col);
+                let cell = ChromaticTensor::new(unit, rgb.to_vec(), None);
+                let decoded = decode_to_chromatic(&encode_to_spectral(&cell));
+                (rgb, decoded.rgb_at(0, 0))
+            })
+        });
+        Self::from_pairs(tensor.shape, pairs)
+    }
+
+    fn from_pairs(shape: Shape2D, pairs: impl Iterator<Item = ([Fx; 3], [Fx; 3])>) -> Self {
+        let mut components = [vec![], vec![], vec![], vec![]];
+        for (pa, pb) in pairs {
+            let (dh, ds, dl) = delta_hsl(
+                rgb_to_hsl(pa[0], pa[1], pa[2]),
+                rgb_to_hsl(pb[0], pb[1], pb[2]),
+            );
+            let de = (dh * dh + ds * ds + dl * dl).sqrt();
+            for (values, v) in components.iter_mut().zip([dh, ds, dl, de]) {
+                values.push(v);
+            }
+        }
+        let [h, s, l, e] = components;
+        Self {
+            delta_h: CellField::signed("dH", shape, h),
+            delta_s: CellField::signed("dS", shape, s),
+            delta_l: CellField::signed("dL", shape, l),
+            delta_e: CellField::magnitude("dE", shape, e),
+        }
+    }
+
+    pub fn fields(&self) -> [&CellField; 4] {
+        [&self.delta_h, &self.delta_s, &self.delta_l, &self.delta_e]
+    }
+}
+
+/// Layout and overlay settings of a cell map.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CellMapOptions {
+    /// Pixels per cell edge.
+    pub scale: u32,
+    /// Heat opacity over the tensor colours; `None` draws the heat alone.
+    pub overlay: Option<Fx>,
+    /// Box cells whose magnitude exceeds this value.
+    pub outline_above: Option<Fx>,
+    pub colorbar: bool,
+}
+
+impl Default for CellMapOptions {
+    fn default() -> Self {
+        Self {
+            scale: DEFAULT_CELL_SCALE,
+            overlay: None,
+            outline_above: None,
+            colorbar: true,
+        }
+    }
+}
+
+impl CellMapOptions {
+    /// Blends the heat at `opacity` over the tensor colours.
+    pub fn overlay(mut self, opacity: Fx) -> Self {
+        self.overlay = Some(opacity.clamp(0.0, 1.0));
+        self
+    }
+
+    pub fn outline_above(mut self, threshold: Fx) -> Self {
+        self.outline_above = Some(threshold);
+        self
+    }
+
+    pub fn scale(mut self, scale: u32) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    pub fn without_colorbar(mut self) -> Self {
+        self.colorbar = false;
+        self
+    }
+
+    /// Canvas size for a tensor of `shape`.
+    pub fn canvas_size(&self, shape: Shape2D) -> (u32, u32) {
+        let bar = if self.colorbar { BAR_SPACE } else { MARGIN };
+        let width = MARGIN + shape.w as u32 * self.scale + bar;
+        let height = TITLE_SPACE + shape.h as u32 * self.scale + MARGIN;
+        (width, height)
+    }
+}
+
+/// Draws `field`, optionally over `base`, onto `surface`.
+///
+/// With an overlay, `base` must have the field's shape.
+pub fn draw_cell_map(
+    surface: &mut impl Surface,
+    field: &CellField,
+    base: Option<&ChromaticTensor>,
+    options: &CellMapOptions,
+) -> CoreResult<()> {
+    if let (Some(_), Some(base)) = (options.overlay, base) {
+        if base.shape != field.shape {
+            return Err(DreamError::Validation(format!(
+                "overlay base is {}x{}, field `{}` is {}x{}",
+                base.shape.h, base.shape.w, field.label, field.shape.h, field.shape.w
+            )));
+        }
+    }
+    let scale = options.scale.max(1) as Fx;
+    let (left, top) = (MARGIN as Fx, TITLE_SPACE as Fx);
+    let cell = |row: usize, col: usize| (left + col as Fx * scale, top + row as Fx * scale);
+    for row in 0..field.shape.h {
+        for col in 0..field.shape.w {
+            let (x, y) = cell(row, col);
+            let heat = field
+                .colormap
+                .map(field.get(row, col).unwrap_or(0.0), &field.norm);
+            match (options.overlay, base) {
+                (Some(opacity), Some(base)) => {
+                    let under = quantize_rgb(base.rgb_at(row, col));
+                    surface.fill_area(x, y, scale, scale, under, 1.0);
+                    surface.fill_area(x, y, scale, scale, heat, opacity);
+                }
+                _ => surface.fill_area(x, y, scale, scale, heat, 1.0),
+            }
+        }
+    }
+    if let Some(threshold) = options.outline_above {
+        for (row, col) in field.exceeding(threshold) {
+            let (x, y) = cell(row, col);
+            let (r, b) = (x + scale - 1.0, y + scale - 1.0);
+            surface.stroke_path(&[(x, y), (r, y), (r, b), (x, b), (x, y)], OUTLINE_COLOR);
+        }
+    }
+    let black = [0, 0, 0];
+    surface.draw_label(left, 4.0, &field.label, black, TextAnchor::Start);
+    if options.colorbar {
+        let grid_height = field.shape.h as Fx * scale;
+        let x = left + field.shape.w as Fx * scale + 10.0;
+        let bar = [x, top, BAR_WIDTH, grid_height];
+        draw_colorbar(surface, bar, field.colormap, &field.norm, "");
+    }
+    Ok(())
+}
+
+/// Renders the tensor colours with `scale × scale` pixels per cell.
+pub fn render_tensor(tensor: &ChromaticTensor, scale: u32) -> ImageBuffer {
+    let scale = scale.max(1);
+    let (w, h) = (tensor.shape.w as u32, tensor.shape.h as u32);
+    let mut image = ImageBuffer::new(w * scale, h * scale, WHITE);
+    for row in 0..tensor.shape.h {
+        for col in 0..tensor.shape.w {
+            let color = quantize_rgb(tensor.rgb_at(row, col));
+            let (x, y) = ((col as u32 * scale) as i32, (row as u32 * scale) as i32);
+            image.fill_rect(x, y, scale, scale, color);
+        }
+    }
+    image
+}
+
+/// Renders `field` as an upscaled raster heatmap.
+pub fn render_cell_map(
+    field: &CellField,
+    base: Option<&ChromaticTensor>,
+    options: &CellMapOptions,
+) -> CoreResult<ImageBuffer> {
+    let (width, height) = options.canvas_size(field.shape);
+    let mut image = ImageBuffer::new(width, height, WHITE);
+    draw_cell_map(&mut image, field, base, options)?;
+    Ok(image)
+}
+
+/// SVG variant of [`render_cell_map`].
+pub fn render_cell_map_svg(
+    field: &CellField,
+    base: Option<&ChromaticTensor>,
+    options: &CellMapOptions,
+) -> CoreResult<SvgDocument> {
+    let (width, height) = options.canvas_size(field.shape);
+    let mut svg = SvgDocument::new(width, height);
+    svg.rect(0.0, 0.0, width as Fx, height as Fx, Style::fill(WHITE));
+    draw_cell_map(&mut svg, field, base, options)?;
+    Ok(svg)
+}
+
+/// Round-trip ΔH, ΔS, ΔL and ΔE maps of `tensor`, blended over it with
+/// failing cells outlined.
+pub fn render_round_trip_maps(
+    tensor: &ChromaticTensor,
+    options: &CellMapOptions,
+) -> CoreResult<Vec<ImageBuffer>> {
+    let options = CellMapOptions {
+        outline_above: options.outline_above.or(Some(ROUND_TRIP_OUTLINE)),
+        ..*options
+    };
+    let deltas = DeltaFields::round_trip(tensor);
+    deltas
+        .fields()
+        .into_iter()
+        .map(|field| render_cell_map(field, Some(tensor), &options))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bridge::validate_round_trip;
+    use crate::diagnostics::visual::blend;
+
+    fn tensor(cells: &[[Fx; 3]], w: usize) -> ChromaticTensor {
+        let shape = Shape2D::new(cells.len() / w, w);
+        let rgb = cells.iter().flatten().copied().collect();
+        ChromaticTensor::new(shape, rgb, None)
+    }
+
+    #[test]
+    fn deltas_locate_the_differing_cell() {
+        let grey = [0.5, 0.5, 0.5];
+        let a = tensor(&[grey, grey, grey, grey], 2);
+        let mut b = a.clone();
+        b.set_rgb(1, 0, [0.7, 0.7, 0.7]);
+        let deltas = DeltaFields::between(&a, &b).unwrap();
+        assert_eq!(deltas.delta_e.exceeding(1e-3), vec![(1, 0)]);
+        assert!((deltas.delta_l.get(1, 0).unwrap() - 0.2).abs() < 1e-5);
+        let norm = deltas.delta_l.norm;
+        assert_eq!(norm.min, -norm.max);
+        assert_eq!(deltas.delta_h.norm, Normalization::new(-1.0, 1.0));
+
+        let single = tensor(&[grey], 1);
+        let broadcast = DeltaFields::between(&b, &single).unwrap();
+        assert_eq!(broadcast.delta_e.exceeding(1e-3), vec![(1, 0)]);
+        let wide = tensor(&[grey, grey, grey], 3);
+        assert!(matches!(
+            DeltaFields::between(&a, &wide),
+            Err(DreamError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn coherence_and_gradient_fields() {
+        let mut t = tensor(&[[0.0; 3], [1.0; 3], [1.0; 3]], 3);
+        assert!(CellField::coherence(&t).is_none());
+        t.coh = Some(vec![0.1, 0.5, 0.9]);
+        let coh = CellField::coherence(&t).unwrap();
+        assert_eq!(coh.norm, coherence_normalization());
+        assert_eq!(coh.get(0, 2), Some(0.9));
+
+        let grad = CellField::gradient_magnitude(&t);
+        let expected = (3.0 * 0.25 as Fx).sqrt();
+        assert!((grad.get(0, 0).unwrap() - expected).abs() < 1e-6);
+        assert!((grad.get(0, 1).unwrap() - expected).abs() < 1e-6);
+        assert_eq!(grad.get(0, 2), Some(0.0));
+    }
+
+    #[test]
+    fn cells_are_upscaled_blended_and_outlined() {
+        let t = tensor(&[[1.0, 0.0, 0.0], [0.0, 0.0, 1.0]], 2);
+        let field = CellField::magnitude("m", t.shape, vec![0.0, 1.0]);
+        let options = CellMapOptions::default().scale(4).without_colorbar();
+        let image = render_cell_map(&field, None, &options).unwrap();
+        assert_eq!(image.width(), MARGIN * 2 + 8);
+        let (x, y) = (MARGIN as i32, TITLE_SPACE as i32);
+        let low = Colormap::viridis().sample(0.0);
+        assert!((0..4).all(|d| image.pixel(x + d, y + d) == Some(low)));
+        assert_eq!(image.pixel(x + 4, y), Some(Colormap::viridis().sample(1.0)));
+
+        let blended = render_cell_map(&field, Some(&t), &options.overlay(0.5)).unwrap();
+        let expected = blend([255, 0, 0], low, 0.5);
+        assert_eq!(blended.pixel(x + 1, y + 1), Some(expected));
+
+        let outlined = render_cell_map(&field, None, &options.outline_above(0.5)).unwrap();
+        assert_eq!(outlined.pixel(x + 4, y), Some(OUTLINE_COLOR));
+        assert_eq!(outlined.pixel(x + 5, y + 1), image.pixel(x + 5, y + 1));
+
+        let wrong = tensor(&[[0.0; 3]], 1);
+        assert!(render_cell_map(&field, Some(&wrong), &options.overlay(0.5)).is_err());
+    }
+
+    #[test]
+    fn tensor_render_is_nearest_neighbour() {
+        let t = tensor(&[[1.0, 0.0, 0.0], [0.0, 0.0, 1.0]], 2);
+        let image = render_tensor(&t, 3);
+        assert_eq!((image.width(), image.height()), (6, 3));
+        assert_eq!(image.pixel(2, 2), Some([255, 0, 0]));
+        assert_eq!(image.pixel(3, 0), Some([0, 0, 255]));
+    }
+
+    #[test]
+    fn round_trip_maps_outline_cells_of_a_failing_tensor() {
+        // Black has no saturation, so the bridge decodes it at mid lightness.
+        let t = tensor(&[[0.1, 0.1, 0.9], [0.0, 0.0, 0.0]], 2);
+        assert!(!validate_round_trip(&t));
+        let deltas = DeltaFields::round_trip(&t);
+        assert_eq!(deltas.delta_e.exceeding(ROUND_TRIP_OUTLINE), vec![(0, 1)]);
+
+        let options = CellMapOptions::default().scale(4).without_colorbar();
+        let maps = render_round_trip_maps(&t, &options).unwrap();
+        let (x, y) = (MARGIN as i32, TITLE_SPACE as i32);
+        assert_eq!(maps[3].pixel(x + 4, y), Some(OUTLINE_COLOR));
+        assert_ne!(maps[3].pixel(x, y), Some(OUTLINE_COLOR));
+    }
+
+    #[test]
+    fn textured_tensor_that_round_trips_has_no_outlines() {
+        let (pale, deep) = ([0.6, 0.4, 0.4], [0.8, 0.2, 0.2]);
+        let t = tensor(&[pale, deep, pale, deep], 2);
+        assert!(validate_round_trip(&t));
+        let deltas = DeltaFields::round_trip(&t);
+        for field in deltas.fields() {
+            assert!(
+                field.exceeding(ROUND_TRIP_OUTLINE).is_empty(),
+                "{}",
+                field.label
+            );
+        }
+    }
+
+    #[test]
+    fn round_trip_maps_are_deterministic() {
+        let t = tensor(&[[0.8, 0.2, 0.1], [0.1, 0.6, 0.9]], 2);
+        let options = CellMapOptions::default().overlay(0.6);
+        let maps = render_round_trip_maps(&t, &options).unwrap();
+        assert_eq!(maps.len(), 4);
+        assert_eq!(maps, render_round_trip_maps(&t, &options).unwrap());
+        let svg = render_cell_map_svg(&DeltaFields::round_trip(&t).delta_e, Some(&t), &options)
+            .unwrap()
+            .to_svg_string();
+        assert!(svg.contains(">dE</text>") && svg.contains("opacity=\"0.6\""));
+    }
+}
@@ -0,0 +1,314 @@
+//! Hue–saturation spiral of chromatic trajectories.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/visual/spec.md`
+//!
+//! Each step is placed at `r = S·C` and angle `H`, where `H` and `S` come
+//! from the circular mean in [`mean_hsl`] and `C` is the [`coherence_metric`].
+//! The plot uses `x = r·cos H, y = r·sin H`, so crossing the hue seam at
+//! `2π → 0` moves the point continuously. Markers are filled with the step's
+//! quantized [`mean_rgb`], and the trajectory line uses its run's colour.
+//! Both back ends draw through [`SpiralPlot::draw`].
+
+use super::axes::draw_legend;
+use super::font::GLYPH_HEIGHT;
+use super::renderer::{quantize_rgb, ImageBuffer, Rgb8, Surface, BLACK, WHITE};
+use super::svg::{Style, SvgDocument, TextAnchor};
+use crate::{
+    bridge::mean_hsl,
+    dream::{coherence_metric, DreamEntry},
+    tensor::{mean_rgb, ChromaticTensor},
+    Fx,
+};
+
+/// Default edge length of the square plot in pixels.
+pub const SPIRAL_SIZE: u32 = 320;
+/// Margin between the unit circle and the image edge.
+pub const SPIRAL_MARGIN: u32 = 28;
+/// Marker radius in pixels.
+pub const MARKER_RADIUS: u32 = 3;
+/// Most epoch labels drawn per run.
+pub const MAX_EPOCH_LABELS: usize = 8;
+/// Trajectory colours assigned to runs in insertion order.
+pub const RUN_COLORS: [Rgb8; 6] = [
+    [31, 119, 180],
+    [214, 39, 40],
+    [44, 160, 44],
+    [148, 103, 189],
+    [255, 127, 14],
+    [23, 190, 207],
+];
+
+const GRID: Rgb8 = [220, 220, 220];
+
+/// One step of a chromatic trajectory.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpiralPoint {
+    pub epoch: u32,
+    /// Circular mean hue in radians.
+    pub hue: Fx,
+    pub saturation: Fx,
+    pub coherence: Fx,
+    /// Plotted radius `S·C`.
+    pub radius: Fx,
+    pub x: Fx,
+    pub y: Fx,
+    /// Quantized mean RGB of the tensor.
+    pub color: Rgb8,
+}
+
+impl SpiralPoint {
+    pub fn from_tensor(epoch: u32, tensor: &ChromaticTensor) -> Self {
+        let (hue, saturation, _) = mean_hsl(tensor);
+        let coherence = coherence_metric(tensor);
+        let radius = (saturation * coherence).clamp(0.0, 1.0);
+        Self {
+            epoch,
+            hue,
+            saturation,
+            coherence,
+            radius,
+            x: radius * hue.cos(),
+            y: radius * hue.sin(),
+            color: quantize_rgb(mean_rgb(tensor)),
+        }
+    }
+}
+
+/// Spiral points for `history`, numbering epochs by position.
+pub fn spiral_points(history: &[ChromaticTensor]) -> Vec<SpiralPoint> {
+    history
+        .iter()
+        .enumerate()
+        .map(|(i, tensor)| SpiralPoint::from_tensor(i as u32, tensor))
+        .collect()
+}
+
+/// Labelled trajectory in a [`SpiralPlot`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpiralRun {
+    pub label: String,
+    pub color: Rgb8,
+    pub points: Vec<SpiralPoint>,
+}
+
+/// One or more trajectories drawn on a shared hue–saturation disc.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpiralPlot {
+    size: u32,
+    runs: Vec<SpiralRun>,
+}
+
+impl Default for SpiralPlot {
+    fn default() -> Self {
+        Self::new(SPIRAL_SIZE)
+    }
+}
+
+impl SpiralPlot {
+    pub fn new(size: u32) -> Self {
+        Self {
+            size: size.max(2 * SPIRAL_MARGIN + 16),
+            runs: Vec::new(),
+        }
+    }
+
+    pub fn runs(&self) -> &[SpiralRun] {
+        &self.runs
+    }
+
+    /// Adds a run whose epochs are the positions in `history`.
+    pub fn add_run(&mut self, label: &str, history: &[ChromaticTensor]) -> &mut Self {
+        self.push_run(label, spiral_points(history))
+    }
+
+    /// Adds a run from dream entries, keeping their epochs and sorting by them.
+    pub fn add_entries(&mut self, label: &str, entries: &[DreamEntry]) -> &mut Self {
+        let mut ordered: Vec<&DreamEntry> = entries.iter().collect();
+        ordered.sort_by_key(|entry| entry.epoch);
+        let points = ordered
+            .into_iter()
+            .map(|entry| SpiralPoint::from_tensor(entry.epoch, &entry.tensor))
+            .collect();
+        self.push_run(label, points)
+    }
+
+    fn push_run(&mut self, label: &str, points: Vec<SpiralPoint>) -> &mut Self {
+        let color = RUN_COLORS[self.runs.len() % RUN_COLORS.len()];
+        self.runs.push(SpiralRun {
+            label: label.to_string(),
+            color,
+            points,
+        });
+        self
+    }
+
+    fn center(&self) -> Fx {
+        self.size as Fx / 2.0
+    }
+
+    fn scale(&self) -> Fx {
+        self.center() - SPIRAL_MARGIN as Fx
+    }
+
+    /// Image coordinates of a point; `y` grows upwards in the plot.
+    fn project(&self, point: &SpiralPoint) -> (Fx, Fx) {
+        let c = self.center();
+        (c + point.x * self.scale(), c - point.y * self.scale())
+    }
+
+    fn labelled(points: &[SpiralPoint]) -> impl Iterator<Item = &SpiralPoint> {
+        let step = points.len().div_ceil(MAX_EPOCH_LABELS).max(1);
+        let last = points.len().saturating_sub(1);
+        points
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| i % step == 0 || *i == last)
+            .map(|(_, point)| point)
+    }
+
+    /// Draws the grid, trajectories, markers, epoch labels, and legend onto
+    /// `surface`.
+    pub fn draw(&self, surface: &mut impl Surface) {
+        let (c, r) = (self.center(), self.scale());
+        surface.stroke_circle(c, c, r, GRID);
+        surface.stroke_circle(c, c, r / 2.0, GRID);
+        surface.stroke_line(c - r, c, c + r, c, GRID);
+        surface.stroke_line(c, c - r, c, c + r, GRID);
+        surface.draw_label(c + r + 3.0, c - 3.0, "0", BLACK, TextAnchor::Start);
+        surface.draw_label(c, c - r - 10.0, "H", BLACK, TextAnchor::Middle);
+
+        for run in &self.runs {
+            let path: Vec<(Fx, Fx)> = run.points.iter().map(|p| self.project(p)).collect();
+            surface.stroke_path(&path, run.color);
+        }
+        for run in &self.runs {
+            for point in &run.points {
+                let (x, y) = self.project(point);
+                surface.draw_marker(x, y, MARKER_RADIUS as Fx, point.color, run.color);
+            }
+            for point in Self::labelled(&run.points) {
+                let (x, y) = self.project(point);
+                let text = point.epoch.to_string();
+                let top = y - GLYPH_HEIGHT as Fx - 2.0;
+                surface.draw_label(x + 6.0, top, &text, run.color, TextAnchor::Start);
+            }
+        }
+        let legend: Vec<(&str, Rgb8)> = self
+            .runs
+            .iter()
+            .map(|run| (run.label.as_str(), run.color))
+            .collect();
+        draw_legend(surface, 4.0, 4.0, &legend);
+    }
+
+    /// Renders the plot as a raster image.
+    pub fn render(&self) -> ImageBuffer {
+        let mut image = ImageBuffer::new(self.size, self.size, WHITE);
+        self.draw(&mut image);
+        image
+    }
+
+    /// Renders the same plot as an SVG document.
+    pub fn render_svg(&self) -> SvgDocument {
+        let mut doc = SvgDocument::new(self.size, self.size);
+        let size = self.size as Fx;
+        doc.rect(0.0, 0.0, size, size, Style::fill(WHITE));
+        self.draw(&mut doc);
+        doc
+    }
+}
+
+/// Renders a single trajectory as a raster spiral.
+pub fn plot_chromatic_spiral(history: &[ChromaticTensor]) -> ImageBuffer {
+    let mut plot = SpiralPlot::default();
+    plot.add_run("run", history);
+    plot.render()
+}
+
+/// Renders a single trajectory as an SVG spiral.
+pub fn plot_chromatic_spiral_svg(history: &[ChromaticTensor]) -> SvgDocument {
+    let mut plot = SpiralPlot::default();
+    plot.add_run("run", history);
+    plot.render_svg()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::visual::quantize_coord;
+    use crate::tensor::{hsl_to_rgb, Shape2D};
+    use std::f32::consts::PI;
+
+    fn solid(h: Fx, s: Fx, l: Fx) -> ChromaticTensor {
+        let (r, g, b) = hsl_to_rgb(h, s, l);
+        let shape = Shape2D::new(2, 2);
+        let rgb = [r, g, b].repeat(shape.cell_count());
+        ChromaticTensor::new(shape, rgb, Some(vec![1.0; shape.cell_count()]))
+    }
+
+    fn sweep(start: Fx, steps: usize) -> Vec<ChromaticTensor> {
+        (0..steps)
+            .map(|i| solid(start + 0.1 * i as Fx, 0.8, 0.5))
+            .collect()
+    }
+
+    #[test]
+    fn points_follow_hue_and_saturation_across_the_seam() {
+        let points = spiral_points(&sweep(2.0 * PI - 0.3, 6));
+        for pair in points.windows(2) {
+            let gap = ((pair[1].x - pair[0].x).powi(2) + (pair[1].y - pair[0].y).powi(2)).sqrt();
+            // A 0.1 rad hue step at radius r moves about 0.1·r.
+            assert!(gap < 0.1 * pair[0].radius + 1e-2, "{:?}", pair);
+        }
+        let p = points[0];
+        assert!((p.radius - p.saturation * p.coherence).abs() < 1e-6);
+        assert!((p.x - p.radius * p.hue.cos()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn markers_use_mean_rgb_and_output_is_stable() {
+        let history = sweep(0.5, 5);
+        let image = plot_chromatic_spiral(&history);
+        let plot = {
+            let mut plot = SpiralPlot::default();
+            plot.add_run("run", &history);
+            plot
+        };
+        let point = plot.runs()[0].points[2];
+        let (x, y) = plot.project(&point);
+        let pixel = image.pixel(quantize_coord(x), quantize_coord(y));
+        assert_eq!(pixel, Some(point.color));
+        assert_eq!(point.color, quantize_rgb(mean_rgb(&history[2])));
+        assert_eq!(image, plot_chromatic_spiral(&history));
+        let svg = plot_chromatic_spiral_svg(&history).to_svg_string();
+        assert_eq!(svg, plot_chromatic_spiral_svg(&history).to_svg_string());
+        assert_eq!(svg.matches("<circle").count(), 2 + history.len());
+    }
+
+    #[test]
+    fn overlays_get_distinct_colours_and_legend_entries() {
+        let mut plot = SpiralPlot::new(200);
+        plot.add_run("baseline", &sweep(0.2, 4))
+            .add_run("dream", &sweep(3.0, 12));
+        assert_eq!(plot.runs()[1].color, RUN_COLORS[1]);
+        let image = plot.render();
+        assert_eq!(image.pixel(5, 15), Some(RUN_COLORS[1]));
+        let svg = plot.render_svg().to_svg_string();
+        assert!(svg.contains(">baseline</text>") && svg.contains(">dream</text>"));
+        // Twelve points keep at most MAX_EPOCH_LABELS labels plus the last epoch.
+        let labels = SpiralPlot::labelled(&plot.runs()[1].points).count();
+        assert!(labels <= MAX_EPOCH_LABELS + 1);
+
+        let entries: Vec<DreamEntry> = [(7, 1.0), (3, 2.0)]
+            .iter()
+            .map(|&(epoch, hue)| DreamEntry::new(solid(hue, 0.6, 0.5), epoch, 0.5))
+            .collect();
+        plot.add_entries("pool", &entries);
+        let epochs: Vec<u32> = plot.runs()[2].points.iter().map(|p| p.epoch).collect();
+        assert_eq!(epochs, vec![3, 7]);
+    }
+}
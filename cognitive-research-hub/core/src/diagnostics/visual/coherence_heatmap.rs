@@ -0,0 +1,239 @@
+//! Time × hue-bin heatmaps of spectral coherence.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/visual/spec.md`
+//!
+//! Cell `(k, t)` holds `C_t(k) = |b_k|·|r_k| / (‖b‖·‖r‖)`, hue bin `k`'s
+//! contribution to the spectral coherence (cosine similarity) between epoch
+//! `t`'s spectrum `b` and a reference spectrum `r`, which defaults to epoch 0
+//! as in the drift plot. Each column therefore sums to that epoch's coherence
+//! with the reference: bins that keep their reference energy stay lit, and a
+//! spectrum that drifts to other bins fades out. Colours come from the
+//! coherence gradient table over its fixed `[min, max]` domain, so the same
+//! value has the same colour in every heatmap.
+
+use super::axes::{draw_colorbar, PlotFrame};
+use super::colormap::{coherence_normalization, Colormap, Normalization};
+use super::renderer::{ImageBuffer, Surface, WHITE};
+use super::svg::{Style, SvgDocument};
+use crate::{diagnostics::metrics::SpectralStats, tensor::SpectralTensor, Fx};
+
+/// Heatmap width in pixels.
+pub const HEATMAP_WIDTH: u32 = 480;
+/// Heatmap height in pixels.
+pub const HEATMAP_HEIGHT: u32 = 260;
+
+const LEFT: Fx = 40.0;
+const TOP: Fx = 24.0;
+const BOTTOM: Fx = 36.0;
+const BAR_SPACE: Fx = 64.0;
+const BAR_WIDTH: Fx = 10.0;
+
+/// Coherence values with one row per bin and one column per epoch.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CoherenceMatrix {
+    rows: usize,
+    cols: usize,
+    values: Vec<Fx>,
+}
+
+impl CoherenceMatrix {
+    /// Per-bin coherence of every epoch with epoch 0.
+    pub fn from_spectra(spectra: &[SpectralTensor]) -> Self {
+        match spectra.first() {
+            Some(reference) => Self::against(spectra, reference),
+            None => Self::default(),
+        }
+    }
+
+    /// Per-bin coherence of every epoch with `reference`; shorter spectra are
+    /// zero-padded, and silent spectra have zero coherence.
+    pub fn against(spectra: &[SpectralTensor], reference: &SpectralTensor) -> Self {
+        let rows = spectra
+            .iter()
+            .map(|s| s.bins.len())
+            .chain([reference.bins.len()])
+            .max()
+            .unwrap_or(0);
+        let cols = spectra.len();
+        let norm = |bins: &[Fx]| bins.iter().map(|b| b * b).sum::<Fx>().sqrt();
+        let reference_norm = norm(&reference.bins);
+        let mut values = vec![0.0; rows * cols];
+        for (t, spectrum) in spectra.iter().enumerate() {
+            let scale = norm(&spectrum.bins) * reference_norm;
+            if scale <= Fx::EPSILON {
+                continue;
+            }
+            for (k, (bin, r)) in spectrum.bins.iter().zip(&reference.bins).enumerate() {
+                values[k * cols + t] = bin.abs() * r.abs() / scale;
+            }
+        }
+        Self { rows, cols, values }
+    }
+
+    /// Single row of the summary coherence index of each epoch.
+    pub fn from_stats(stats: &[SpectralStats]) -> Self {
+        Self {
+            rows: usize::from(!stats.is_empty()),
+            cols: stats.len(),
+            values: stats.iter().map(|s| s.coherence).collect(),
+        }
+    }
+
+    /// Number of bins.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of epochs.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, bin: usize, epoch: usize) -> Option<Fx> {
+        if bin >= self.rows || epoch >= self.cols {
+            return None;
+        }
+        Some(self.values[bin * self.cols + epoch])
+    }
+
+    pub fn values(&self) -> &[Fx] {
+        &self.values
+    }
+}
+
+/// Draws the cells, axes, and colour bar of `matrix` onto `surface`.
+pub fn draw_coherence_heatmap(
+    surface: &mut impl Surface,
+    matrix: &CoherenceMatrix,
+    colormap: &Colormap,
+    norm: &Normalization,
+) {
+    let (width, height) = surface.size();
+    let plot_width = width as Fx - LEFT - BAR_SPACE;
+    let plot_height = height as Fx - TOP - BOTTOM;
+    let x_range = (-0.5, matrix.cols().max(1) as Fx - 0.5);
+    let y_range = (-0.5, matrix.rows().max(1) as Fx - 0.5);
+    let frame = PlotFrame::new(LEFT, TOP, plot_width, plot_height, x_range, y_range)
+        .integer_x_ticks()
+        .integer_y_ticks();
+
+    for bin in 0..matrix.rows() {
+        let (y0, y1) = (frame.map_y(bin as Fx + 0.5), frame.map_y(bin as Fx - 0.5));
+        for epoch in 0..matrix.cols() {
+            let (x0, x1) = (
+                frame.map_x(epoch as Fx - 0.5),
+                frame.map_x(epoch as Fx + 0.5),
+            );
+            let value = matrix.get(bin, epoch).unwrap_or(0.0);
+            surface.fill_area(x0, y0, x1 - x0, y1 - y0, colormap.map(value, norm), 1.0);
+        }
+    }
+    frame.draw_axes(surface, "epoch", "hue bin");
+    let bar = [frame.right() + 14.0, frame.top, BAR_WIDTH, frame.height];
+    draw_colorbar(surface, bar, colormap, norm, "coh");
+}
+
+/// Renders `spectra` as a coherence heatmap image.
+pub fn generate_coherence_heatmap(spectra: &[SpectralTensor]) -> ImageBuffer {
+    let mut image = ImageBuffer::new(HEATMAP_WIDTH, HEATMAP_HEIGHT, WHITE);
+    draw_coherence_heatmap(
+        &mut image,
+        &CoherenceMatrix::from_spectra(spectra),
+        Colormap::coherence(),
+        &coherence_normalization(),
+    );
+    image
+}
+
+/// SVG variant of [`generate_coherence_heatmap`].
+pub fn generate_coherence_heatmap_svg(spectra: &[SpectralTensor]) -> SvgDocument {
+    let mut svg = SvgDocument::new(HEATMAP_WIDTH, HEATMAP_HEIGHT);
+    svg.rect(
+        0.0,
+        0.0,
+        HEATMAP_WIDTH as Fx,
+        HEATMAP_HEIGHT as Fx,
+        Style::fill(WHITE),
+    );
+    draw_coherence_heatmap(
+        &mut svg,
+        &CoherenceMatrix::from_spectra(spectra),
+        Colormap::coherence(),
+        &coherence_normalization(),
+    );
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::visual::quantize_coord;
+
+    fn spectrum(bins: Vec<Fx>) -> SpectralTensor {
+        SpectralTensor::new(bins, None, 100.0, 10.0, false)
+    }
+
+    #[test]
+    fn matrix_holds_per_bin_coherence_with_the_reference() {
+        let spectra = [
+            spectrum(vec![1.0, 0.0, 0.0]),
+            spectrum(vec![0.5, 0.5]),
+            spectrum(vec![0.0, 0.0, 0.0]),
+        ];
+        let matrix = CoherenceMatrix::from_spectra(&spectra);
+        assert_eq!((matrix.rows(), matrix.cols()), (3, 3));
+        assert_eq!(matrix.get(0, 0), Some(1.0));
+        assert!((matrix.get(0, 1).unwrap() - 0.5f32.sqrt()).abs() < 1e-6);
+        assert_eq!(matrix.get(1, 1), Some(0.0));
+        assert_eq!(matrix.get(0, 2), Some(0.0));
+        assert_eq!(matrix.get(3, 0), None);
+
+        // A flat spectrum is not coherent with itself bin by bin, but every
+        // column still sums to the cosine coherence with the reference.
+        let flat = spectrum(vec![0.5, 0.5, 0.5, 0.5]);
+        let own = CoherenceMatrix::against(std::slice::from_ref(&flat), &flat);
+        let column: Fx = (0..own.rows()).filter_map(|k| own.get(k, 0)).sum();
+        assert!((column - 1.0).abs() < 1e-6);
+        assert_eq!(own.get(2, 0), Some(0.25));
+
+        let stats = [SpectralStats {
+            coherence: 0.8,
+            ..SpectralStats::default()
+        }];
+        let row = CoherenceMatrix::from_stats(&stats);
+        assert_eq!((row.rows(), row.cols(), row.get(0, 0)), (1, 1, Some(0.8)));
+        assert_eq!(CoherenceMatrix::from_stats(&[]).rows(), 0);
+    }
+
+    #[test]
+    fn cells_use_the_fixed_coherence_normalization() {
+        let spectra = [spectrum(vec![1.0, 0.0]), spectrum(vec![0.0, 1.0])];
+        let image = generate_coherence_heatmap(&spectra);
+        assert_eq!(image, generate_coherence_heatmap(&spectra));
+        let map = Colormap::coherence();
+        let width = HEATMAP_WIDTH as Fx - LEFT - BAR_SPACE;
+        let height = HEATMAP_HEIGHT as Fx - TOP - BOTTOM;
+        let cell = |fx: Fx, fy: Fx| {
+            let x = quantize_coord(LEFT + fx * width);
+            let y = quantize_coord(TOP + fy * height);
+            image.pixel(x, y)
+        };
+        // Epoch 0 matches itself in bin 0, drawn in the lower half; epoch 1
+        // moved all its energy to bin 1 and shares nothing with it.
+        assert_eq!(cell(0.25, 0.75), Some(map.sample(1.0)));
+        assert_eq!(cell(0.25, 0.25), Some(map.sample(0.0)));
+        assert_eq!(cell(0.75, 0.75), Some(map.sample(0.0)));
+        assert_eq!(cell(0.75, 0.25), Some(map.sample(0.0)));
+
+        let svg = generate_coherence_heatmap_svg(&spectra).to_svg_string();
+        assert_eq!(
+            svg,
+            generate_coherence_heatmap_svg(&spectra).to_svg_string()
+        );
+        assert!(svg.contains(">hue bin</text>") && svg.contains(">coh</text>"));
+    }
+}
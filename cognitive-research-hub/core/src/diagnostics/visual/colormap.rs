@@ -0,0 +1,240 @@
+//! Deterministic colour normalization tables.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/visual/spec.md`
+//!
+//! Colour stops come from `lut/color_palette.tbl` and
+//! `lut/coherence_gradient.tbl`. They are expanded to [`LUT_SIZE`] entries
+//! with integer interpolation. A value is normalized to `[0, 1]`, rounded to
+//! a table index, and looked up, so a value always maps to the same 8-bit
+//! colour.
+
+use std::sync::OnceLock;
+
+use super::renderer::Rgb8;
+use crate::{
+    error::{CoreResult, DreamError},
+    utils::{parse_toml, JsonValue},
+    Fx,
+};
+
+/// Entries in every expanded colour table.
+pub const LUT_SIZE: usize = 256;
+
+const PALETTE_TABLE: &str = include_str!("lut/color_palette.tbl");
+const COHERENCE_TABLE: &str = include_str!("lut/coherence_gradient.tbl");
+
+/// Linear map from a value range onto `[0, 1]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Normalization {
+    pub min: Fx,
+    pub max: Fx,
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Self { min: 0.0, max: 1.0 }
+    }
+}
+
+impl Normalization {
+    pub fn new(min: Fx, max: Fx) -> Self {
+        Self { min, max }
+    }
+
+    /// Range of the finite `values`; `[0, 1]` when there are none.
+    pub fn fit(values: impl IntoIterator<Item = Fx>) -> Self {
+        let mut range: Option<(Fx, Fx)> = None;
+        for v in values.into_iter().filter(|v| v.is_finite()) {
+            range = Some(range.map_or((v, v), |(lo, hi)| (lo.min(v), hi.max(v))));
+        }
+        range.map_or_else(Self::default, |(min, max)| Self { min, max })
+    }
+
+    /// Range `[-m, m]` where `m` is the largest finite magnitude, for diverging maps.
+    pub fn symmetric(values: impl IntoIterator<Item = Fx>) -> Self {
+        let m = values
+            .into_iter()
+            .filter(|v| v.is_finite())
+            .fold(0.0, |acc: Fx, v| acc.max(v.abs()));
+        if m <= Fx::EPSILON {
+            return Self::new(-1.0, 1.0);
+        }
+        Self::new(-m, m)
+    }
+
+    /// Position of `value` in `[0, 1]`; a degenerate range maps everything to 0.5.
+    pub fn unit(&self, value: Fx) -> Fx {
+        let span = self.max - self.min;
+        if !value.is_finite() {
+            return 0.0;
+        }
+        if span.is_nan() || span.abs() <= Fx::EPSILON {
+            return 0.5;
+        }
+        ((value - self.min) / span).clamp(0.0, 1.0)
+    }
+
+    /// Table index of `value`, rounding half up.
+    pub fn index(&self, value: Fx) -> usize {
+        (self.unit(value) * (LUT_SIZE - 1) as Fx + 0.5) as usize
+    }
+}
+
+/// Colour map expanded to [`LUT_SIZE`] entries.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Colormap {
+    name: String,
+    table: Vec<Rgb8>,
+}
+
+fn parse_hex(text: &str) -> Option<Rgb8> {
+    let hex = text.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+impl Colormap {
+    /// Expands evenly spaced `stops` into a full table.
+    pub fn from_stops(name: &str, stops: &[Rgb8]) -> CoreResult<Self> {
+        if stops.len() < 2 {
+            return Err(DreamError::Config(format!(
+                "colormap `{}` needs at least two stops",
+                name
+            )));
+        }
+        let segments = (stops.len() - 1) as u32;
+        let last = (LUT_SIZE - 1) as u32;
+        let table = (0..LUT_SIZE as u32)
+            .map(|i| {
+                let pos = i * segments;
+                let (k, rem) = ((pos / last) as usize, pos % last);
+                let (a, b) = (stops[k], stops[(k + 1).min(stops.len() - 1)]);
+                std::array::from_fn(|c| {
+                    ((a[c] as u32 * (last - rem) + b[c] as u32 * rem + last / 2) / last) as u8
+                })
+            })
+            .collect();
+        Ok(Self {
+            name: name.to_string(),
+            table,
+        })
+    }
+
+    /// Reads the `#rrggbb` string array stored under `key` in a `.tbl` file.
+    pub fn from_tbl_str(text: &str, key: &str) -> CoreResult<Self> {
+        let doc = parse_toml(text)?;
+        let invalid = || DreamError::Config(format!("`{}` must be an array of #rrggbb", key));
+        let stops = doc
+            .get(key)
+            .and_then(JsonValue::as_array)
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|stop| stop.as_str().and_then(parse_hex).ok_or_else(invalid))
+            .collect::<CoreResult<Vec<_>>>()?;
+        Self::from_stops(key, &stops)
+    }
+
+    /// Sequential map for magnitudes, from `lut/color_palette.tbl`.
+    pub fn viridis() -> &'static Colormap {
+        static MAP: OnceLock<Colormap> = OnceLock::new();
+        MAP.get_or_init(|| {
+            Self::from_tbl_str(PALETTE_TABLE, "viridis").expect("embedded viridis is valid")
+        })
+    }
+
+    /// Diverging map for signed values, from `lut/color_palette.tbl`.
+    pub fn diverging() -> &'static Colormap {
+        static MAP: OnceLock<Colormap> = OnceLock::new();
+        MAP.get_or_init(|| {
+            Self::from_tbl_str(PALETTE_TABLE, "diverging").expect("embedded diverging is valid")
+        })
+    }
+
+    /// Coherence gradient from `lut/coherence_gradient.tbl`.
+    pub fn coherence() -> &'static Colormap {
+        static MAP: OnceLock<Colormap> = OnceLock::new();
+        MAP.get_or_init(|| {
+            Self::from_tbl_str(COHERENCE_TABLE, "gradient").expect("embedded gradient is valid")
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn table(&self) -> &[Rgb8] {
+        &self.table
+    }
+
+    /// Colour at `unit` in `[0, 1]`.
+    pub fn sample(&self, unit: Fx) -> Rgb8 {
+        self.table[Normalization::default().index(unit)]
+    }
+
+    /// Colour of `value` under `norm`.
+    pub fn map(&self, value: Fx, norm: &Normalization) -> Rgb8 {
+        self.table[norm.index(value)]
+    }
+}
+
+/// Fixed coherence domain from `lut/coherence_gradient.tbl`.
+pub fn coherence_normalization() -> Normalization {
+    static NORM: OnceLock<Normalization> = OnceLock::new();
+    *NORM.get_or_init(|| {
+        let doc = parse_toml(COHERENCE_TABLE).expect("embedded gradient is valid");
+        let bound = |key: &str| doc.get(key).and_then(JsonValue::as_f64).map(|v| v as Fx);
+        Normalization::new(bound("min").unwrap_or(0.0), bound("max").unwrap_or(1.0))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_hit_their_stops_exactly() {
+        let viridis = Colormap::viridis();
+        assert_eq!(viridis.table().len(), LUT_SIZE);
+        assert_eq!(viridis.sample(0.0), [0x44, 0x01, 0x54]);
+        assert_eq!(viridis.sample(1.0), [0xfd, 0xe7, 0x25]);
+        assert_eq!(Colormap::diverging().sample(0.0), [0x21, 0x66, 0xac]);
+        assert_eq!(Colormap::coherence().sample(0.0), [0, 0, 4]);
+        assert_eq!(coherence_normalization(), Normalization::new(0.0, 1.0));
+        let two = Colormap::from_stops("ramp", &[[0, 0, 0], [255, 255, 255]]).unwrap();
+        assert!(two
+            .table()
+            .iter()
+            .enumerate()
+            .all(|(i, c)| c[0] as usize == i));
+    }
+
+    #[test]
+    fn normalization_is_clamped_and_handles_degenerate_ranges() {
+        let norm = Normalization::fit([0.2, Fx::NAN, 0.6, 0.4]);
+        assert_eq!(norm, Normalization::new(0.2, 0.6));
+        assert_eq!(norm.index(0.3), 64);
+        assert_eq!(norm.index(5.0), LUT_SIZE - 1);
+        assert_eq!(Normalization::new(1.0, 1.0).unit(3.0), 0.5);
+        assert_eq!(
+            Normalization::symmetric([-0.3, 0.1]),
+            Normalization::new(-0.3, 0.3)
+        );
+    }
+
+    #[test]
+    fn malformed_tables_are_config_errors() {
+        assert!(matches!(
+            Colormap::from_tbl_str("map = [\"#fff\", \"#000000\"]", "map"),
+            Err(DreamError::Config(_))
+        ));
+        assert!(Colormap::from_tbl_str("map = [\"#000000\"]", "map").is_err());
+        assert!(Colormap::from_tbl_str("other = 1", "map").is_err());
+    }
+}
@@ -0,0 +1,489 @@
+//! Single-file HTML diagnostics dashboard.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/visual/spec.md`
+//!
+//! A [`DiagnosticsPanel`] collects tables, SVG charts, and raster images and
+//! serializes them into one HTML document. Charts are inlined as `<svg>`,
+//! images as base64 PNG data URIs, and styling as an inline `<style>` block,
+//! so the report opens from an archive with no network access and no
+//! scripts. Numbers are printed with fixed precision, making the output
+//! byte-identical for identical inputs.
+
+use std::fs;
+use std::path::Path;
+
+use super::cell_maps::{
+    render_cell_map, render_tensor, CellMapOptions, DeltaFields, ROUND_TRIP_OUTLINE,
+};
+use super::chromatic_spiral::plot_chromatic_spiral_svg;
+use super::coherence_heatmap::generate_coherence_heatmap_svg;
+use super::renderer::ImageBuffer;
+use super::spectral_drift::{drift_stats, render_energy_drift_plot};
+use super::svg::{escape_xml, SvgDocument};
+use crate::{
+    diagnostics::{
+        continuity::{ActionOutcome, ActionRecord, StabilityClass, TrendModel},
+        metrics::{MetricsSnapshot, TREND_DECAY, TREND_GROWTH, TREND_OSCILLATORY, TREND_STABLE},
+    },
+    error::CoreResult,
+    tensor::{ChromaticTensor, SpectralTensor},
+    Fx,
+};
+
+/// Pixels per cell for tensor images in the dashboard.
+pub const DASHBOARD_CELL_SCALE: u32 = 12;
+/// Heat opacity of the round-trip ΔE overlay.
+pub const DASHBOARD_OVERLAY: Fx = 0.6;
+
+const STYLE: &str = "body{font-family:sans-serif;margin:24px;color:#222}\
+table{border-collapse:collapse;margin-bottom:16px}\
+th,td{border:1px solid #bbb;padding:2px 8px;text-align:right}\
+th{background:#eee}td:first-child,th:first-child{text-align:left}\
+figure{display:inline-block;margin:0 16px 16px 0;vertical-align:top}\
+img{image-rendering:pixelated}";
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard padded base64 of `bytes`.
+pub fn encode_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Name of a metrics `TREND_*` class.
+pub fn trend_class_name(class: i8) -> &'static str {
+    match class {
+        TREND_STABLE => "stable",
+        TREND_GROWTH => "growth",
+        TREND_DECAY => "decay",
+        TREND_OSCILLATORY => "oscillatory",
+        _ => "unknown",
+    }
+}
+
+fn number(value: Fx) -> String {
+    format!("{:.4}", value)
+}
+
+/// Captioned table of preformatted cells.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PanelTable {
+    pub caption: String,
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl PanelTable {
+    pub fn new(caption: &str, header: &[&str]) -> Self {
+        Self {
+            caption: caption.to_string(),
+            header: header.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn row(mut self, cells: Vec<String>) -> Self {
+        self.rows.push(cells);
+        self
+    }
+
+    fn write_html(&self, out: &mut String) {
+        out.push_str(&format!(
+            "<h2>{}</h2>\n<table>\n<tr>",
+            escape_xml(&self.caption)
+        ));
+        for cell in &self.header {
+            out.push_str(&format!("<th>{}</th>", escape_xml(cell)));
+        }
+        out.push_str("</tr>\n");
+        for row in &self.rows {
+            out.push_str("<tr>");
+            for cell in row {
+                out.push_str(&format!("<td>{}</td>", escape_xml(cell)));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+    }
+}
+
+/// Everything shown on one dashboard.
+#[derive(Clone, Debug, Default)]
+pub struct DiagnosticsSnapshot {
+    pub title: String,
+    pub metrics: MetricsSnapshot,
+    pub trend: Option<TrendModel>,
+    /// Planner decisions, usually `planner.trail().records()`.
+    pub actions: Vec<ActionRecord>,
+    /// Spectra per epoch for the drift plot and coherence heatmap.
+    pub spectra: Vec<SpectralTensor>,
+    /// Chromatic tensors per epoch; the last one is shown as an image.
+    pub tensors: Vec<ChromaticTensor>,
+}
+
+/// Composed dashboard: tables, then charts, then images.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DiagnosticsPanel {
+    title: String,
+    tables: Vec<PanelTable>,
+    charts: Vec<(String, SvgDocument)>,
+    images: Vec<(String, ImageBuffer)>,
+}
+
+impl DiagnosticsPanel {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn tables(&self) -> &[PanelTable] {
+        &self.tables
+    }
+
+    pub fn charts(&self) -> &[(String, SvgDocument)] {
+        &self.charts
+    }
+
+    pub fn images(&self) -> &[(String, ImageBuffer)] {
+        &self.images
+    }
+
+    pub fn add_table(&mut self, table: PanelTable) -> &mut Self {
+        self.tables.push(table);
+        self
+    }
+
+    pub fn add_chart(&mut self, caption: &str, chart: SvgDocument) -> &mut Self {
+        self.charts.push((caption.to_string(), chart));
+        self
+    }
+
+    pub fn add_image(&mut self, caption: &str, image: ImageBuffer) -> &mut Self {
+        self.images.push((caption.to_string(), image));
+        self
+    }
+
+    /// Serializes the panel as a self-contained HTML document.
+    pub fn to_html(&self) -> CoreResult<String> {
+        let title = escape_xml(&self.title);
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+        );
+        for table in &self.tables {
+            table.write_html(&mut out);
+        }
+        if !self.charts.is_empty() {
+            out.push_str("<h2>Charts</h2>\n");
+        }
+        for (caption, chart) in &self.charts {
+            out.push_str("<figure>\n");
+            out.push_str(&chart.to_svg_string());
+            out.push_str(&format!(
+                "<figcaption>{}</figcaption>\n</figure>\n",
+                escape_xml(caption)
+            ));
+        }
+        if !self.images.is_empty() {
+            out.push_str("<h2>Tensors</h2>\n");
+        }
+        for (caption, image) in &self.images {
+            out.push_str(&format!(
+                "<figure>\n<img alt=\"{alt}\" width=\"{w}\" height=\"{h}\" \
+                 src=\"data:image/png;base64,{data}\">\n<figcaption>{alt}</figcaption>\n</figure>\n",
+                alt = escape_xml(caption),
+                w = image.width(),
+                h = image.height(),
+                data = encode_base64(&image.to_png()?)
+            ));
+        }
+        out.push_str("</body>\n</html>\n");
+        Ok(out)
+    }
+}
+
+fn metrics_table(metrics: &MetricsSnapshot) -> PanelTable {
+    let (c, s, t) = (&metrics.chromatic, &metrics.spectral, &metrics.continuity);
+    let rows = [
+        ("delta H", number(c.delta_h)),
+        ("delta S", number(c.delta_s)),
+        ("delta L", number(c.delta_l)),
+        ("delta E", number(c.magnitude)),
+        ("energy total", number(s.energy_total)),
+        ("energy drift (dB)", number(s.energy_drift)),
+        ("centroid", number(s.centroid)),
+        ("coherence", number(s.coherence)),
+        ("slope", number(t.slope)),
+        ("stdev", number(t.stdev)),
+        ("oscillation index", number(t.oscillation_index)),
+        ("trend class", trend_class_name(t.trend_class).to_string()),
+    ];
+    rows.into_iter().fold(
+        PanelTable::new("Metrics snapshot", &["metric", "value"]),
+        |table, (name, value)| table.row(vec![name.to_string(), value]),
+    )
+}
+
+fn trend_table(trend: &TrendModel) -> PanelTable {
+    let class = StabilityClass::from_id(trend.class_id).map_or("unknown", StabilityClass::name);
+    PanelTable::new(
+        "Trend",
+        &[
+            "class",
+            "short slope",
+            "long slope",
+            "stdev",
+            "oscillation",
+            "growth rate",
+        ],
+    )
+    .row(vec![
+        class.to_string(),
+        number(trend.short_slope),
+        number(trend.long_slope),
+        number(trend.stdev),
+        number(trend.oscillation_score),
+        number(trend.growth_rate),
+    ])
+}
+
+fn actions_table(actions: &[ActionRecord]) -> PanelTable {
+    let header = [
+        "seq",
+        "cycle",
+        "class",
+        "action",
+        "confidence",
+        "adjustment",
+        "outcome",
+    ];
+    actions.iter().fold(
+        PanelTable::new("Planner actions", &header),
+        |table, record| {
+            let class =
+                StabilityClass::from_id(record.class_id).map_or("unknown", StabilityClass::name);
+            let (action, confidence, adjustment) = match record.action {
+                Some(a) => (
+                    a.kind().map_or("unknown", |k| k.name()).to_string(),
+                    number(a.confidence),
+                    number(a.adjustment),
+                ),
+                None => ("-".to_string(), "-".to_string(), "-".to_string()),
+            };
+            let outcome = match &record.outcome {
+                ActionOutcome::NoAction => "none".to_string(),
+                ActionOutcome::Applied => "applied".to_string(),
+                ActionOutcome::Failed(msg) => format!("failed: {}", msg),
+            };
+            table.row(vec![
+                record.sequence.to_string(),
+                record.cycle_id.to_string(),
+                class.to_string(),
+                action,
+                confidence,
+                adjustment,
+                outcome,
+            ])
+        },
+    )
+}
+
+/// Builds the standard dashboard from `snapshot`.
+///
+/// Sections without data are left out: the drift plot and coherence heatmap
+/// need spectra, the spiral and tensor images need chromatic tensors. Errors
+/// from the round-trip ΔE map are returned rather than dropping the panel.
+pub fn compose_diagnostics_dashboard(
+    snapshot: &DiagnosticsSnapshot,
+) -> CoreResult<DiagnosticsPanel> {
+    let title = if snapshot.title.is_empty() {
+        "Diagnostics report"
+    } else {
+        snapshot.title.as_str()
+    };
+    let mut panel = DiagnosticsPanel::new(title);
+    panel.add_table(metrics_table(&snapshot.metrics));
+    if let Some(trend) = &snapshot.trend {
+        panel.add_table(trend_table(trend));
+    }
+    panel.add_table(actions_table(&snapshot.actions));
+
+    if !snapshot.spectra.is_empty() {
+        panel.add_chart(
+            "Energy and centroid drift",
+            render_energy_drift_plot(&drift_stats(&snapshot.spectra)),
+        );
+        panel.add_chart(
+            "Spectral coherence with epoch 0",
+            generate_coherence_heatmap_svg(&snapshot.spectra),
+        );
+    }
+    if let Some(latest) = snapshot.tensors.last() {
+        panel.add_chart(
+            "Chromatic spiral",
+            plot_chromatic_spiral_svg(&snapshot.tensors),
+        );
+        panel.add_image("Latest tensor", render_tensor(latest, DASHBOARD_CELL_SCALE));
+        let options = CellMapOptions::default()
+            .scale(DASHBOARD_CELL_SCALE)
+            .overlay(DASHBOARD_OVERLAY)
+            .outline_above(ROUND_TRIP_OUTLINE);
+        let deltas = DeltaFields::round_trip(latest);
+        let map = render_cell_map(&deltas.delta_e, Some(latest), &options)?;
+        panel.add_image("Round-trip dE", map);
+    }
+    Ok(panel)
+}
+
+/// Writes `panel` to `path` as a single HTML file, replacing any existing file.
+pub fn export_visual_report(path: impl AsRef<Path>, panel: &DiagnosticsPanel) -> CoreResult<()> {
+    fs::write(path, panel.to_html()?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::continuity::{ActionKind, TemporalAction};
+    use crate::diagnostics::visual::OUTLINE_COLOR;
+    use crate::tensor::Shape2D;
+
+    fn snapshot() -> DiagnosticsSnapshot {
+        let shape = Shape2D::new(2, 2);
+        let tensors = (0..3)
+            .map(|i| {
+                let v = 0.2 + 0.2 * i as Fx;
+                ChromaticTensor::new(shape, [v, 0.3, 0.6].repeat(4), None)
+            })
+            .collect();
+        let spectra = (0..3)
+            .map(|i| {
+                SpectralTensor::new(
+                    vec![0.4, 0.2 + 0.1 * i as Fx, 0.1],
+                    None,
+                    100.0,
+                    10.0,
+                    false,
+                )
+            })
+            .collect();
+        let mut metrics = MetricsSnapshot::default();
+        metrics.continuity.trend_class = TREND_DECAY;
+        DiagnosticsSnapshot {
+            title: "Run <7>".to_string(),
+            metrics,
+            trend: Some(TrendModel {
+                class_id: StabilityClass::Degradation.id(),
+                ..TrendModel::default()
+            }),
+            actions: vec![ActionRecord {
+                sequence: 0,
+                cycle_id: 4,
+                class_id: StabilityClass::Degradation.id(),
+                short_slope: -0.1,
+                long_slope: -0.05,
+                action: Some(TemporalAction::new(ActionKind::Adjust, 0.75, 0.8)),
+                outcome: ActionOutcome::Applied,
+            }],
+            spectra,
+            tensors,
+        }
+    }
+
+    #[test]
+    fn base64_matches_reference_vectors() {
+        assert_eq!(encode_base64(b""), "");
+        assert_eq!(encode_base64(b"f"), "Zg==");
+        assert_eq!(encode_base64(b"fo"), "Zm8=");
+        assert_eq!(encode_base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(encode_base64(&[0xff, 0xfe]), "//4=");
+    }
+
+    #[test]
+    fn dashboard_has_tables_charts_and_images() {
+        let panel = compose_diagnostics_dashboard(&snapshot()).unwrap();
+        assert_eq!(panel.title(), "Run <7>");
+        assert_eq!(panel.tables().len(), 3);
+        assert_eq!(panel.charts().len(), 3);
+        assert_eq!(panel.images().len(), 2);
+        assert_eq!(panel.tables()[0].rows[11][1], "decay");
+        assert_eq!(panel.tables()[1].rows[0][0], "degradation");
+        assert_eq!(
+            panel.tables()[2].rows[0][3..],
+            ["adjust", "0.7500", "0.8000", "applied"]
+        );
+
+        let empty = compose_diagnostics_dashboard(&DiagnosticsSnapshot::default()).unwrap();
+        assert_eq!(empty.title(), "Diagnostics report");
+        assert!(empty.charts().is_empty() && empty.images().is_empty());
+    }
+
+    #[test]
+    fn round_trip_panel_outlines_a_failing_tensor() {
+        let latest =
+            ChromaticTensor::new(Shape2D::new(1, 2), vec![0.1, 0.1, 0.9, 0.0, 0.0, 0.0], None);
+        let snapshot = DiagnosticsSnapshot {
+            tensors: vec![latest],
+            ..DiagnosticsSnapshot::default()
+        };
+        let panel = compose_diagnostics_dashboard(&snapshot).unwrap();
+        let (caption, map) = &panel.images()[1];
+        assert_eq!(caption, "Round-trip dE");
+        let outlined = (0..map.height() as i32)
+            .flat_map(|y| (0..map.width() as i32).map(move |x| (x, y)))
+            .any(|(x, y)| map.pixel(x, y) == Some(OUTLINE_COLOR));
+        assert!(outlined);
+    }
+
+    #[test]
+    fn html_is_self_contained_and_deterministic() {
+        let html = compose_diagnostics_dashboard(&snapshot())
+            .unwrap()
+            .to_html()
+            .unwrap();
+        assert_eq!(
+            html,
+            compose_diagnostics_dashboard(&snapshot())
+                .unwrap()
+                .to_html()
+                .unwrap()
+        );
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Run &lt;7&gt;</title>"));
+        assert_eq!(html.matches("<svg xmlns").count(), 3);
+        assert_eq!(
+            html.matches("src=\"data:image/png;base64,iVBORw0KGgo")
+                .count(),
+            2
+        );
+        assert!(
+            !html.contains("<script") && !html.contains("href=") && !html.contains("src=\"http")
+        );
+    }
+}
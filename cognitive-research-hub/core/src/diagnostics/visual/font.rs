@@ -0,0 +1,114 @@
+//! Embedded 5×7 bitmap font for raster labels.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/visual/spec.md`
+//!
+//! Each glyph is seven rows of five bits, most significant bit on the left.
+//! Lowercase letters render as uppercase and unsupported characters as `?`.
+
+/// Glyph width in pixels at scale 1.
+pub const GLYPH_WIDTH: u32 = 5;
+/// Glyph height in pixels at scale 1.
+pub const GLYPH_HEIGHT: u32 = 7;
+/// Horizontal advance per character at scale 1, including spacing.
+pub const GLYPH_ADVANCE: u32 = GLYPH_WIDTH + 1;
+
+/// Glyph rows sorted by character.
+const GLYPHS: &[(char, [u8; 7])] = &[
+    (' ', [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ('!', [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04]),
+    ('"', [0x0a, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ('#', [0x0a, 0x1f, 0x0a, 0x0a, 0x0a, 0x1f, 0x0a]),
+    ('%', [0x19, 0x19, 0x02, 0x04, 0x08, 0x13, 0x13]),
+    ('\'', [0x04, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ('(', [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02]),
+    (')', [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08]),
+    ('*', [0x00, 0x04, 0x15, 0x0e, 0x15, 0x04, 0x00]),
+    ('+', [0x00, 0x04, 0x04, 0x1f, 0x04, 0x04, 0x00]),
+    (',', [0x00, 0x00, 0x00, 0x00, 0x04, 0x04, 0x08]),
+    ('-', [0x00, 0x00, 0x00, 0x1f, 0x00, 0x00, 0x00]),
+    ('.', [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04]),
+    ('/', [0x01, 0x01, 0x02, 0x04, 0x08, 0x10, 0x10]),
+    ('0', [0x0e, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0e]),
+    ('1', [0x04, 0x0c, 0x04, 0x04, 0x04, 0x04, 0x0e]),
+    ('2', [0x0e, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1f]),
+    ('3', [0x1f, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0e]),
+    ('4', [0x02, 0x06, 0x0a, 0x12, 0x1f, 0x02, 0x02]),
+    ('5', [0x1f, 0x10, 0x1e, 0x01, 0x01, 0x11, 0x0e]),
+    ('6', [0x06, 0x08, 0x10, 0x1e, 0x11, 0x11, 0x0e]),
+    ('7', [0x1f, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08]),
+    ('8', [0x0e, 0x11, 0x11, 0x0e, 0x11, 0x11, 0x0e]),
+    ('9', [0x0e, 0x11, 0x11, 0x0f, 0x01, 0x02, 0x0c]),
+    (':', [0x00, 0x04, 0x04, 0x00, 0x04, 0x04, 0x00]),
+    (';', [0x00, 0x04, 0x04, 0x00, 0x04, 0x04, 0x08]),
+    ('<', [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02]),
+    ('=', [0x00, 0x00, 0x1f, 0x00, 0x1f, 0x00, 0x00]),
+    ('>', [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08]),
+    ('?', [0x0e, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04]),
+    ('A', [0x0e, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11]),
+    ('B', [0x1e, 0x11, 0x11, 0x1e, 0x11, 0x11, 0x1e]),
+    ('C', [0x0e, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0e]),
+    ('D', [0x1c, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1c]),
+    ('E', [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x1f]),
+    ('F', [0x1f, 0x10, 0x10, 0x1e, 0x10, 0x10, 0x10]),
+    ('G', [0x0e, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0f]),
+    ('H', [0x11, 0x11, 0x11, 0x1f, 0x11, 0x11, 0x11]),
+    ('I', [0x0e, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0e]),
+    ('J', [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0c]),
+    ('K', [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11]),
+    ('L', [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1f]),
+    ('M', [0x11, 0x1b, 0x15, 0x15, 0x11, 0x11, 0x11]),
+    ('N', [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11]),
+    ('O', [0x0e, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e]),
+    ('P', [0x1e, 0x11, 0x11, 0x1e, 0x10, 0x10, 0x10]),
+    ('Q', [0x0e, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0d]),
+    ('R', [0x1e, 0x11, 0x11, 0x1e, 0x14, 0x12, 0x11]),
+    ('S', [0x0f, 0x10, 0x10, 0x0e, 0x01, 0x01, 0x1e]),
+    ('T', [0x1f, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04]),
+    ('U', [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0e]),
+    ('V', [0x11, 0x11, 0x11, 0x11, 0x11, 0x0a, 0x04]),
+    ('W', [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0a]),
+    ('X', [0x11, 0x11, 0x0a, 0x04, 0x0a, 0x11, 0x11]),
+    ('Y', [0x11, 0x11, 0x0a, 0x04, 0x04, 0x04, 0x04]),
+    ('Z', [0x1f, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1f]),
+    ('[', [0x0e, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0e]),
+    (']', [0x0e, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0e]),
+    ('_', [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1f]),
+    ('|', [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04]),
+];
+
+/// Row bitmaps for `ch`.
+pub fn glyph(ch: char) -> [u8; 7] {
+    let ch = ch.to_ascii_uppercase();
+    let index = GLYPHS
+        .binary_search_by_key(&ch, |&(c, _)| c)
+        .or_else(|_| GLYPHS.binary_search_by_key(&'?', |&(c, _)| c))
+        .unwrap_or(0);
+    GLYPHS[index].1
+}
+
+/// Rendered width of `text` in pixels at `scale`, without trailing spacing.
+pub fn text_width(text: &str, scale: u32) -> u32 {
+    let count = text.chars().count() as u32;
+    (count * GLYPH_ADVANCE).saturating_sub(1) * scale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_is_sorted_and_falls_back_to_question_mark() {
+        assert!(GLYPHS.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(GLYPHS
+            .iter()
+            .all(|(_, rows)| rows.iter().all(|&row| row < 1 << GLYPH_WIDTH)));
+        assert_eq!(glyph('a'), glyph('A'));
+        assert_eq!(glyph('~'), glyph('?'));
+        assert_eq!(text_width("", 2), 0);
+        assert_eq!(text_width("0.5", 1), 17);
+    }
+}
@@ -0,0 +1,56 @@
+//! Deterministic rendering of diagnostic plots.
+//!
+//! This module provides the raster canvas, bitmap font, PNG encoder, and
+//! SVG builder, and the diagnostic plots drawn with them, as defined in
+//! `visual/spec.md`.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/visual/spec.md`
+
+pub mod axes;
+pub mod cell_maps;
+pub mod chromatic_spiral;
+pub mod coherence_heatmap;
+pub mod colormap;
+pub mod dashboard;
+pub mod font;
+pub mod png;
+pub mod renderer;
+pub mod spectral_drift;
+pub mod svg;
+
+pub use self::axes::{
+    draw_colorbar, draw_legend, format_tick, nice_ticks, tick_step, PlotFrame, AXIS_COLOR,
+    TICK_LENGTH, TICK_TARGET,
+};
+pub use self::cell_maps::{
+    draw_cell_map, render_cell_map, render_cell_map_svg, render_round_trip_maps, render_tensor,
+    CellField, CellMapOptions, DeltaFields, DEFAULT_CELL_SCALE, OUTLINE_COLOR, ROUND_TRIP_OUTLINE,
+};
+pub use self::chromatic_spiral::{
+    plot_chromatic_spiral, plot_chromatic_spiral_svg, spiral_points, SpiralPlot, SpiralPoint,
+    SpiralRun, MARKER_RADIUS, MAX_EPOCH_LABELS, RUN_COLORS, SPIRAL_MARGIN, SPIRAL_SIZE,
+};
+pub use self::coherence_heatmap::{
+    draw_coherence_heatmap, generate_coherence_heatmap, generate_coherence_heatmap_svg,
+    CoherenceMatrix, HEATMAP_HEIGHT, HEATMAP_WIDTH,
+};
+pub use self::colormap::{coherence_normalization, Colormap, Normalization, LUT_SIZE};
+pub use self::dashboard::{
+    compose_diagnostics_dashboard, encode_base64, export_visual_report, trend_class_name,
+    DiagnosticsPanel, DiagnosticsSnapshot, PanelTable, DASHBOARD_CELL_SCALE, DASHBOARD_OVERLAY,
+};
+pub use self::font::{text_width, GLYPH_ADVANCE, GLYPH_HEIGHT, GLYPH_WIDTH};
+pub use self::png::{encode_png, PNG_SIGNATURE};
+pub use self::renderer::{
+    blend, quantize_channel, quantize_coord, quantize_rgb, ImageBuffer, Rgb8, Surface, BLACK, WHITE,
+};
+pub use self::spectral_drift::{
+    draw_energy_drift, drift_stats, render_energy_drift_image, render_energy_drift_plot,
+    DriftSeries, BAND_COLOR, BAND_OPACITY, CENTROID_COLOR, DRIFT_DB_LIMIT, DRIFT_HEIGHT,
+    DRIFT_WIDTH, ENERGY_COLOR, VIOLATION_COLOR,
+};
+pub use self::svg::{escape_xml, format_number, hex_color, Style, SvgDocument, TextAnchor};
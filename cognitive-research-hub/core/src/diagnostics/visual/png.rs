@@ -0,0 +1,148 @@
+//! Minimal deterministic PNG encoder.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/visual/spec.md`
+//!
+//! Writes 8-bit RGB images with filter type 0 on every scanline, wrapped in
+//! stored (uncompressed) deflate blocks. There are no timestamps or optional
+//! chunks, so the output depends only on the pixels.
+
+use crate::{
+    error::{CoreResult, DreamError},
+    utils::Crc32,
+};
+
+/// PNG file signature.
+pub const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+/// Largest payload of a stored deflate block.
+const STORED_BLOCK: usize = 65_535;
+
+fn adler32(bytes: &[u8]) -> u32 {
+    const MOD: u32 = 65_521;
+    let (mut a, mut b) = (1u32, 0u32);
+    // 5552 bytes is the longest run that cannot overflow `b` before reducing.
+    for chunk in bytes.chunks(5_552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    (b << 16) | a
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let mut crc = Crc32::new();
+    crc.update(kind);
+    crc.update(data);
+    out.extend_from_slice(&crc.finish().to_be_bytes());
+}
+
+/// zlib stream of stored deflate blocks.
+fn zlib_stored(raw: &[u8]) -> Vec<u8> {
+    let blocks = raw.len().div_ceil(STORED_BLOCK).max(1);
+    let mut out = Vec::with_capacity(raw.len() + blocks * 5 + 6);
+    out.extend_from_slice(&[0x78, 0x01]);
+    let mut chunks = raw.chunks(STORED_BLOCK).peekable();
+    if chunks.peek().is_none() {
+        out.extend_from_slice(&[0x01, 0x00, 0x00, 0xff, 0xff]);
+    }
+    while let Some(chunk) = chunks.next() {
+        let len = chunk.len() as u16;
+        out.push(u8::from(chunks.peek().is_none()));
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(chunk);
+    }
+    out.extend_from_slice(&adler32(raw).to_be_bytes());
+    out
+}
+
+/// Encodes row-major RGB bytes as a PNG file.
+pub fn encode_png(width: u32, height: u32, rgb: &[u8]) -> CoreResult<Vec<u8>> {
+    let row = width as usize * 3;
+    if width == 0 || height == 0 || rgb.len() != row * height as usize {
+        return Err(DreamError::Validation(format!(
+            "PNG needs {}x{} RGB pixels, got {} bytes",
+            width,
+            height,
+            rgb.len()
+        )));
+    }
+    let mut raw = Vec::with_capacity((row + 1) * height as usize);
+    for line in rgb.chunks(row) {
+        raw.push(0);
+        raw.extend_from_slice(line);
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    // Bit depth 8, colour type 2 (RGB), default compression, filter, no interlace.
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut out = PNG_SIGNATURE.to_vec();
+    write_chunk(&mut out, b"IHDR", &header);
+    write_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut out, b"IEND", &[]);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::crc32;
+
+    /// Walks the chunks, checking every CRC, and returns their types.
+    fn chunk_types(png: &[u8]) -> Vec<[u8; 4]> {
+        let mut types = Vec::new();
+        let mut pos = PNG_SIGNATURE.len();
+        while pos < png.len() {
+            let len = u32::from_be_bytes(png[pos..pos + 4].try_into().unwrap()) as usize;
+            let body = &png[pos + 4..pos + 8 + len];
+            let crc = u32::from_be_bytes(png[pos + 8 + len..pos + 12 + len].try_into().unwrap());
+            assert_eq!(crc32(body), crc);
+            types.push(body[..4].try_into().unwrap());
+            pos += 12 + len;
+        }
+        types
+    }
+
+    #[test]
+    fn adler32_matches_reference_vector() {
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(adler32(b""), 1);
+    }
+
+    #[test]
+    fn encodes_valid_chunks_and_stored_scanlines() {
+        let rgb = [255, 0, 0, 0, 255, 0, 0, 0, 255, 10, 20, 30];
+        let png = encode_png(2, 2, &rgb).unwrap();
+        assert_eq!(&png[..8], &PNG_SIGNATURE);
+        assert_eq!(chunk_types(&png), vec![*b"IHDR", *b"IDAT", *b"IEND"]);
+        // IDAT payload: zlib header, one final stored block, filtered rows.
+        let idat = &png[8 + 25 + 8..];
+        assert_eq!(&idat[..3], &[0x78, 0x01, 0x01]);
+        assert_eq!(&idat[3..7], &[14, 0, !14, 0xff]);
+        assert_eq!(&idat[7..14], &[0, 255, 0, 0, 0, 255, 0]);
+        assert_eq!(encode_png(2, 2, &rgb).unwrap(), png);
+        assert!(encode_png(2, 2, &rgb[..9]).is_err());
+    }
+
+    #[test]
+    fn large_images_split_into_stored_blocks() {
+        let rgb = vec![7u8; 200 * 200 * 3];
+        let zlib = zlib_stored(&rgb);
+        assert_eq!(zlib.len(), 2 + rgb.len() + 5 * 2 + 4);
+        assert_eq!(zlib[2], 0);
+        assert_eq!(zlib[2 + 5 + STORED_BLOCK], 1);
+    }
+}
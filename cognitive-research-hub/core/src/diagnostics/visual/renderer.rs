@@ -0,0 +1,387 @@
+//! Deterministic raster canvas.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/visual/spec.md`
+//!
+//! All drawing is integer-only: colours are quantized to 8 bits and
+//! coordinates to whole pixels before any primitive runs, so the same calls
+//! always produce the same bytes. Primitives clip silently at the edges.
+
+use std::fs;
+use std::path::Path;
+
+use super::font::{glyph, text_width, GLYPH_ADVANCE, GLYPH_WIDTH};
+use super::png::encode_png;
+use super::svg::TextAnchor;
+use crate::{error::CoreResult, Fx};
+
+/// 8-bit RGB colour.
+pub type Rgb8 = [u8; 3];
+
+pub const BLACK: Rgb8 = [0, 0, 0];
+pub const WHITE: Rgb8 = [255, 255, 255];
+
+/// Quantizes a unit-range channel to 8 bits, rounding half up.
+pub fn quantize_channel(value: Fx) -> u8 {
+    if value.is_nan() {
+        return 0;
+    }
+    (value.clamp(0.0, 1.0) * 255.0 + 0.5) as u8
+}
+
+/// Quantizes unit-range RGB to 8 bits per channel.
+pub fn quantize_rgb(rgb: [Fx; 3]) -> Rgb8 {
+    rgb.map(quantize_channel)
+}
+
+/// Rounds a coordinate to the nearest pixel, half up.
+pub fn quantize_coord(value: Fx) -> i32 {
+    if value.is_nan() {
+        return 0;
+    }
+    (value + 0.5).floor() as i32
+}
+
+/// Alpha-blends `top` over `bottom` with 8-bit integer weights.
+pub fn blend(bottom: Rgb8, top: Rgb8, opacity: Fx) -> Rgb8 {
+    let a = quantize_channel(opacity) as u32;
+    std::array::from_fn(|c| ((top[c] as u32 * a + bottom[c] as u32 * (255 - a) + 127) / 255) as u8)
+}
+
+/// Drawing target shared by raster and SVG output, in pixel units.
+///
+/// Plots draw once against this trait; [`ImageBuffer`] quantizes the
+/// coordinates while [`SvgDocument`](super::svg::SvgDocument) keeps them.
+pub trait Surface {
+    fn size(&self) -> (u32, u32);
+
+    /// Fills a rectangle, blending it over the background below `opacity` 1.
+    fn fill_area(&mut self, x: Fx, y: Fx, width: Fx, height: Fx, color: Rgb8, opacity: Fx);
+
+    fn stroke_line(&mut self, x1: Fx, y1: Fx, x2: Fx, y2: Fx, color: Rgb8);
+
+    fn stroke_path(&mut self, points: &[(Fx, Fx)], color: Rgb8);
+
+    /// One-pixel circle outline.
+    fn stroke_circle(&mut self, x: Fx, y: Fx, radius: Fx, color: Rgb8);
+
+    /// Filled circle with a one-pixel outline.
+    fn draw_marker(&mut self, x: Fx, y: Fx, radius: Fx, fill: Rgb8, stroke: Rgb8);
+
+    /// Text whose top edge is at `y`, one glyph height tall.
+    fn draw_label(&mut self, x: Fx, y: Fx, text: &str, color: Rgb8, anchor: TextAnchor);
+}
+
+/// Row-major RGB raster with the origin at the top left.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImageBuffer {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl ImageBuffer {
+    /// Creates a `width × height` image filled with `background`.
+    pub fn new(width: u32, height: u32, background: Rgb8) -> Self {
+        let pixels = background
+            .iter()
+            .copied()
+            .cycle()
+            .take(width as usize * height as usize * 3)
+            .collect();
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Raw row-major RGB bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    fn offset(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x as u32 >= self.width || y as u32 >= self.height {
+            return None;
+        }
+        Some((y as usize * self.width as usize + x as usize) * 3)
+    }
+
+    pub fn pixel(&self, x: i32, y: i32) -> Option<Rgb8> {
+        let i = self.offset(x, y)?;
+        Some([self.pixels[i], self.pixels[i + 1], self.pixels[i + 2]])
+    }
+
+    /// Sets one pixel; coordinates outside the image are ignored.
+    pub fn set_pixel(&mut self, x: i32, y: i32, color: Rgb8) {
+        if let Some(i) = self.offset(x, y) {
+            self.pixels[i..i + 3].copy_from_slice(&color);
+        }
+    }
+
+    /// Fills the `width × height` rectangle whose top-left corner is `(x, y)`.
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: Rgb8) {
+        let x0 = x.max(0);
+        let y0 = y.max(0);
+        let x1 = (x as i64 + width as i64).min(self.width as i64) as i32;
+        let y1 = (y as i64 + height as i64).min(self.height as i64) as i32;
+        for py in y0..y1 {
+            for px in x0..x1 {
+                self.set_pixel(px, py, color);
+            }
+        }
+    }
+
+    /// One-pixel rectangle outline.
+    pub fn draw_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: Rgb8) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        let (x1, y1) = (x + width as i32 - 1, y + height as i32 - 1);
+        self.draw_line(x, y, x1, y, color);
+        self.draw_line(x, y1, x1, y1, color);
+        self.draw_line(x, y, x, y1, color);
+        self.draw_line(x1, y, x1, y1, color);
+    }
+
+    /// Bresenham line including both end points.
+    pub fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: Rgb8) {
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let (mut x, mut y, mut err) = (x0, y0, dx + dy);
+        loop {
+            self.set_pixel(x, y, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Connects consecutive points with lines.
+    pub fn draw_polyline(&mut self, points: &[(i32, i32)], color: Rgb8) {
+        if let [(x, y)] = points {
+            self.set_pixel(*x, *y, color);
+        }
+        for pair in points.windows(2) {
+            self.draw_line(pair[0].0, pair[0].1, pair[1].0, pair[1].1, color);
+        }
+    }
+
+    /// Midpoint circle outline of `radius` around `(cx, cy)`.
+    pub fn draw_circle(&mut self, cx: i32, cy: i32, radius: u32, color: Rgb8) {
+        let (mut x, mut y, mut err) = (radius as i32, 0, 1 - radius as i32);
+        while x >= y {
+            for (px, py) in [
+                (x, y),
+                (y, x),
+                (-y, x),
+                (-x, y),
+                (-x, -y),
+                (-y, -x),
+                (y, -x),
+                (x, -y),
+            ] {
+                self.set_pixel(cx + px, cy + py, color);
+            }
+            y += 1;
+            if err < 0 {
+                err += 2 * y + 1;
+            } else {
+                x -= 1;
+                err += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    /// Disc of every pixel within `radius` of `(cx, cy)`.
+    pub fn fill_circle(&mut self, cx: i32, cy: i32, radius: u32, color: Rgb8) {
+        let r = radius as i32;
+        let limit = r * r + r;
+        for dy in -r..=r {
+            for dx in -r..=r {
+                if dx * dx + dy * dy <= limit {
+                    self.set_pixel(cx + dx, cy + dy, color);
+                }
+            }
+        }
+    }
+
+    /// Draws `text` with its top-left corner at `(x, y)`, each font pixel
+    /// enlarged to a `scale × scale` block.
+    pub fn draw_text(&mut self, x: i32, y: i32, text: &str, color: Rgb8, scale: u32) {
+        let scale = scale.max(1);
+        let mut cursor = x;
+        for ch in text.chars() {
+            for (row, bits) in glyph(ch).iter().enumerate() {
+                for col in 0..GLYPH_WIDTH {
+                    if bits & (1 << (GLYPH_WIDTH - 1 - col)) != 0 {
+                        self.fill_rect(
+                            cursor + (col * scale) as i32,
+                            y + (row as u32 * scale) as i32,
+                            scale,
+                            scale,
+                            color,
+                        );
+                    }
+                }
+            }
+            cursor += (GLYPH_ADVANCE * scale) as i32;
+        }
+    }
+
+    /// Encodes the image as PNG.
+    pub fn to_png(&self) -> CoreResult<Vec<u8>> {
+        encode_png(self.width, self.height, &self.pixels)
+    }
+
+    /// Writes the image as a PNG file, replacing any existing file.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> CoreResult<()> {
+        fs::write(path, self.to_png()?)?;
+        Ok(())
+    }
+}
+
+impl Surface for ImageBuffer {
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn fill_area(&mut self, x: Fx, y: Fx, width: Fx, height: Fx, color: Rgb8, opacity: Fx) {
+        let (x0, y0) = (quantize_coord(x), quantize_coord(y));
+        let (x1, y1) = (quantize_coord(x + width), quantize_coord(y + height));
+        if opacity >= 1.0 {
+            let (w, h) = ((x1 - x0).max(0) as u32, (y1 - y0).max(0) as u32);
+            self.fill_rect(x0, y0, w, h, color);
+            return;
+        }
+        for py in y0..y1 {
+            for px in x0..x1 {
+                if let Some(below) = self.pixel(px, py) {
+                    self.set_pixel(px, py, blend(below, color, opacity));
+                }
+            }
+        }
+    }
+
+    fn stroke_line(&mut self, x1: Fx, y1: Fx, x2: Fx, y2: Fx, color: Rgb8) {
+        let (a, b) = (quantize_coord(x1), quantize_coord(y1));
+        self.draw_line(a, b, quantize_coord(x2), quantize_coord(y2), color);
+    }
+
+    fn stroke_path(&mut self, points: &[(Fx, Fx)], color: Rgb8) {
+        let pixels: Vec<(i32, i32)> = points
+            .iter()
+            .map(|&(x, y)| (quantize_coord(x), quantize_coord(y)))
+            .collect();
+        self.draw_polyline(&pixels, color);
+    }
+
+    fn stroke_circle(&mut self, x: Fx, y: Fx, radius: Fx, color: Rgb8) {
+        let r = quantize_coord(radius).max(0) as u32;
+        self.draw_circle(quantize_coord(x), quantize_coord(y), r, color);
+    }
+
+    fn draw_marker(&mut self, x: Fx, y: Fx, radius: Fx, fill: Rgb8, stroke: Rgb8) {
+        let (cx, cy) = (quantize_coord(x), quantize_coord(y));
+        let r = quantize_coord(radius).max(0) as u32;
+        self.fill_circle(cx, cy, r, fill);
+        self.draw_circle(cx, cy, r + 1, stroke);
+    }
+
+    fn draw_label(&mut self, x: Fx, y: Fx, text: &str, color: Rgb8, anchor: TextAnchor) {
+        let width = text_width(text, 1) as i32;
+        let left = match anchor {
+            TextAnchor::Start => quantize_coord(x),
+            TextAnchor::Middle => quantize_coord(x) - width / 2,
+            TextAnchor::End => quantize_coord(x) - width,
+        };
+        self.draw_text(left, quantize_coord(y), text, color, 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(image: &ImageBuffer, color: Rgb8) -> usize {
+        image.as_bytes().chunks(3).filter(|px| *px == color).count()
+    }
+
+    #[test]
+    fn quantization_rounds_half_up_and_clamps() {
+        assert_eq!(quantize_rgb([0.0, 0.5, 1.2]), [0, 128, 255]);
+        assert_eq!(quantize_channel(Fx::NAN), 0);
+        assert_eq!(quantize_coord(2.5), 3);
+        assert_eq!(quantize_coord(-0.5), 0);
+    }
+
+    #[test]
+    fn primitives_hit_expected_pixels_and_clip() {
+        let mut image = ImageBuffer::new(16, 16, WHITE);
+        image.draw_line(0, 0, 15, 15, BLACK);
+        assert_eq!(count(&image, BLACK), 16);
+        assert_eq!(image.pixel(7, 7), Some(BLACK));
+
+        let red = [255, 0, 0];
+        image.fill_rect(-4, -4, 6, 6, red);
+        assert_eq!(count(&image, red), 4);
+        assert_eq!(image.pixel(16, 0), None);
+
+        let mut circle = ImageBuffer::new(21, 21, WHITE);
+        circle.draw_circle(10, 10, 8, BLACK);
+        for (x, y) in [(18, 10), (2, 10), (10, 18), (10, 2)] {
+            assert_eq!(circle.pixel(x, y), Some(BLACK));
+        }
+        assert_eq!(circle.pixel(10, 10), Some(WHITE));
+        circle.fill_circle(10, 10, 3, red);
+        assert_eq!(circle.pixel(13, 10), Some(red));
+        assert_eq!(circle.pixel(13, 13), Some(WHITE));
+    }
+
+    #[test]
+    fn surface_blends_and_anchors_labels() {
+        assert_eq!(blend(WHITE, BLACK, 0.5), [127, 127, 127]);
+        assert_eq!(blend(WHITE, BLACK, 1.0), BLACK);
+        let mut image = ImageBuffer::new(20, 10, WHITE);
+        image.fill_area(0.0, 0.0, 4.0, 4.0, BLACK, 0.5);
+        assert_eq!(image.pixel(3, 3), Some([127, 127, 127]));
+        assert_eq!(image.pixel(4, 4), Some(WHITE));
+        image.draw_label(19.0, 2.0, "1", BLACK, TextAnchor::End);
+        // The right column of "1" ends one pixel before the anchor.
+        assert_eq!(image.pixel(17, 8), Some(BLACK));
+        assert_eq!(image.pixel(18, 8), Some(WHITE));
+    }
+
+    #[test]
+    fn text_uses_the_bitmap_font() {
+        let mut image = ImageBuffer::new(12, 16, WHITE);
+        image.draw_text(0, 0, "1", BLACK, 2);
+        // The glyph for "1" lights 10 font pixels, each a 2×2 block.
+        assert_eq!(count(&image, BLACK), 10 * 4);
+        assert_eq!(image.pixel(4, 0), Some(BLACK));
+        assert_eq!(image.pixel(0, 0), Some(WHITE));
+    }
+}
@@ -0,0 +1,299 @@
+//! Energy and centroid drift plots over a spectral series.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/visual/spec.md`
+//!
+//! Both traces are measured against epoch 0. Energy drift is the power ratio
+//! `10·log10(Σb_t² / Σb_0²)` computed by
+//! [`energy_drift_db`](crate::diagnostics::metrics::energy_drift_db), so the
+//! plot shows the same decibels the metrics report, drawn over the shaded
+//! ±[`ENERGY_TOLERANCE_DB`] band. Centroid drift is `(f_t − f_0) / f_0`. Energy appearing
+//! from or vanishing into silence is infinite in dB, so it is pinned to
+//! ±[`DRIFT_DB_LIMIT`] for plotting.
+
+use super::axes::{draw_legend, PlotFrame};
+use super::renderer::{ImageBuffer, Rgb8, Surface, WHITE};
+use super::svg::{Style, SvgDocument};
+use crate::{
+    diagnostics::metrics::{spectral_energy_balance_against, SpectralStats, ENERGY_TOLERANCE_DB},
+    tensor::SpectralTensor,
+    Fx,
+};
+
+/// Plot width in pixels.
+pub const DRIFT_WIDTH: u32 = 480;
+/// Plot height in pixels.
+pub const DRIFT_HEIGHT: u32 = 320;
+/// Largest magnitude drawn for energy drift in dB.
+pub const DRIFT_DB_LIMIT: Fx = 12.0;
+/// Energy trace colour.
+pub const ENERGY_COLOR: Rgb8 = [31, 119, 180];
+/// Centroid trace colour.
+pub const CENTROID_COLOR: Rgb8 = [255, 127, 14];
+/// Tolerance band colour, drawn at [`BAND_OPACITY`].
+pub const BAND_COLOR: Rgb8 = [44, 160, 44];
+/// Marker colour of samples outside the tolerance band.
+pub const VIOLATION_COLOR: Rgb8 = [214, 39, 40];
+/// Opacity of the tolerance band.
+pub const BAND_OPACITY: Fx = 0.25;
+
+const LEFT: Fx = 48.0;
+const RIGHT: Fx = 16.0;
+const PANEL_GAP: Fx = 44.0;
+const TOP: Fx = 24.0;
+const BOTTOM: Fx = 36.0;
+
+/// Drift of each epoch relative to epoch 0.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DriftSeries {
+    /// Energy drift in dB; may be infinite when one side is silent.
+    pub energy_db: Vec<Fx>,
+    /// Relative centroid shift `(f_t − f_0) / f_0`; zero when `f_0` is zero.
+    pub centroid_drift: Vec<Fx>,
+}
+
+impl DriftSeries {
+    /// Series from precomputed stats, taking each `energy_drift` as is.
+    ///
+    /// Build the stats with [`spectral_energy_balance_against`] and the first
+    /// spectrum as baseline for drift relative to epoch 0.
+    pub fn from_stats(stats: &[SpectralStats]) -> Self {
+        let Some(first) = stats.first() else {
+            return Self::default();
+        };
+        let energy_db = stats.iter().map(|s| s.energy_drift).collect();
+        let centroid_drift = stats
+            .iter()
+            .map(|s| {
+                if first.centroid.abs() <= Fx::EPSILON {
+                    0.0
+                } else {
+                    (s.centroid - first.centroid) / first.centroid
+                }
+            })
+            .collect();
+        Self {
+            energy_db,
+            centroid_drift,
+        }
+    }
+
+    /// Series of `spectra` with energy drift measured against the first spectrum.
+    pub fn from_spectra(spectra: &[SpectralTensor]) -> Self {
+        Self::from_stats(&drift_stats(spectra))
+    }
+
+    pub fn len(&self) -> usize {
+        self.energy_db.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.energy_db.is_empty()
+    }
+
+    /// Epochs whose energy drift exceeds `tolerance_db` in magnitude.
+    pub fn violations(&self, tolerance_db: Fx) -> Vec<usize> {
+        self.energy_db
+            .iter()
+            .enumerate()
+            .filter(|(_, db)| db.is_nan() || db.abs() > tolerance_db)
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/// Stats of `spectra` with energy drift measured against the first spectrum.
+pub fn drift_stats(spectra: &[SpectralTensor]) -> Vec<SpectralStats> {
+    match spectra.first() {
+        Some(first) => spectra
+            .iter()
+            .map(|s| spectral_energy_balance_against(s, first))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+fn clamp_db(db: Fx) -> Fx {
+    if db.is_nan() {
+        return 0.0;
+    }
+    db.clamp(-DRIFT_DB_LIMIT, DRIFT_DB_LIMIT)
+}
+
+fn padded_range(values: impl Iterator<Item = Fx>, floor: Fx) -> (Fx, Fx) {
+    let extent = values
+        .filter(|v| v.is_finite())
+        .fold(floor, |acc, v| acc.max(v.abs()));
+    (-extent * 1.1, extent * 1.1)
+}
+
+/// Draws both drift panels, the tolerance band, and the legend onto `surface`.
+pub fn draw_energy_drift(surface: &mut impl Surface, series: &DriftSeries, tolerance_db: Fx) {
+    let (width, height) = surface.size();
+    let plot_width = width as Fx - LEFT - RIGHT;
+    let panel_height = (height as Fx - TOP - BOTTOM - PANEL_GAP) / 2.0;
+    let x_range = (0.0, series.len().saturating_sub(1).max(1) as Fx);
+
+    let energy: Vec<Fx> = series.energy_db.iter().map(|&db| clamp_db(db)).collect();
+    let y_range = padded_range(energy.iter().copied(), tolerance_db * 2.0);
+    let top =
+        PlotFrame::new(LEFT, TOP, plot_width, panel_height, x_range, y_range).integer_x_ticks();
+    let band_top = top.map_y(tolerance_db);
+    let band_height = top.map_y(-tolerance_db) - band_top;
+    surface.fill_area(
+        top.left,
+        band_top,
+        top.width,
+        band_height,
+        BAND_COLOR,
+        BAND_OPACITY,
+    );
+    let zero = top.map_y(0.0);
+    surface.stroke_line(top.left, zero, top.right(), zero, BAND_COLOR);
+    top.draw_axes(surface, "", "energy drift (dB)");
+    let points: Vec<(Fx, Fx)> = energy
+        .iter()
+        .enumerate()
+        .map(|(i, &db)| (top.map_x(i as Fx), top.map_y_clamped(db)))
+        .collect();
+    surface.stroke_path(&points, ENERGY_COLOR);
+    let outside = series.violations(tolerance_db);
+    for (i, &(x, y)) in points.iter().enumerate() {
+        let fill = if outside.contains(&i) {
+            VIOLATION_COLOR
+        } else {
+            ENERGY_COLOR
+        };
+        surface.draw_marker(x, y, 2.0, fill, fill);
+    }
+
+    let y_range = padded_range(series.centroid_drift.iter().copied(), 0.01);
+    let lower_top = TOP + panel_height + PANEL_GAP;
+    let bottom = PlotFrame::new(LEFT, lower_top, plot_width, panel_height, x_range, y_range)
+        .integer_x_ticks();
+    let zero = bottom.map_y(0.0);
+    surface.stroke_line(bottom.left, zero, bottom.right(), zero, BAND_COLOR);
+    bottom.draw_axes(surface, "epoch", "centroid drift (rel.)");
+    let points: Vec<(Fx, Fx)> = series
+        .centroid_drift
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| (bottom.map_x(i as Fx), bottom.map_y_clamped(d)))
+        .collect();
+    surface.stroke_path(&points, CENTROID_COLOR);
+    for &(x, y) in &points {
+        surface.draw_marker(x, y, 2.0, CENTROID_COLOR, CENTROID_COLOR);
+    }
+
+    let band_label = format!("+/-{} dB", tolerance_db);
+    let entries = [
+        ("energy", ENERGY_COLOR),
+        (band_label.as_str(), BAND_COLOR),
+        ("out of band", VIOLATION_COLOR),
+        ("centroid", CENTROID_COLOR),
+    ];
+    draw_legend(surface, top.right() - 96.0, top.top + 4.0, &entries);
+}
+
+/// Renders `stats` as SVG; see [`DriftSeries::from_stats`] for the energy trace.
+pub fn render_energy_drift_plot(stats: &[SpectralStats]) -> SvgDocument {
+    let mut svg = SvgDocument::new(DRIFT_WIDTH, DRIFT_HEIGHT);
+    svg.rect(
+        0.0,
+        0.0,
+        DRIFT_WIDTH as Fx,
+        DRIFT_HEIGHT as Fx,
+        Style::fill(WHITE),
+    );
+    draw_energy_drift(
+        &mut svg,
+        &DriftSeries::from_stats(stats),
+        ENERGY_TOLERANCE_DB,
+    );
+    svg
+}
+
+/// Raster variant of [`render_energy_drift_plot`].
+pub fn render_energy_drift_image(stats: &[SpectralStats]) -> ImageBuffer {
+    let mut image = ImageBuffer::new(DRIFT_WIDTH, DRIFT_HEIGHT, WHITE);
+    draw_energy_drift(
+        &mut image,
+        &DriftSeries::from_stats(stats),
+        ENERGY_TOLERANCE_DB,
+    );
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::metrics::energy_drift_db;
+
+    fn stats(drift_db: Fx, centroid: Fx) -> SpectralStats {
+        SpectralStats {
+            energy_drift: drift_db,
+            centroid,
+            ..SpectralStats::default()
+        }
+    }
+
+    fn spectrum(bins: Vec<Fx>) -> SpectralTensor {
+        SpectralTensor::new(bins, None, 100.0, 10.0, false)
+    }
+
+    #[test]
+    fn drift_is_relative_to_the_first_epoch() {
+        let series = DriftSeries::from_stats(&[
+            stats(0.0, 100.0),
+            stats(0.0, 110.0),
+            stats(10.0, 90.0),
+            stats(Fx::NEG_INFINITY, 100.0),
+        ]);
+        assert_eq!(series.energy_db[0], 0.0);
+        assert_eq!(series.energy_db[2], 10.0);
+        assert_eq!(series.energy_db[3], Fx::NEG_INFINITY);
+        assert!((series.centroid_drift[1] - 0.1).abs() < 1e-6);
+        assert!((series.centroid_drift[2] + 0.1).abs() < 1e-6);
+        assert_eq!(series.violations(ENERGY_TOLERANCE_DB), vec![2, 3]);
+        assert!(DriftSeries::from_stats(&[]).is_empty());
+    }
+
+    #[test]
+    fn spectra_and_stats_give_the_same_series() {
+        let spectra = [spectrum(vec![0.2, 0.4, 0.1]), spectrum(vec![0.2, 0.1, 0.4])];
+        let series = DriftSeries::from_spectra(&spectra);
+        assert_eq!(series, DriftSeries::from_stats(&drift_stats(&spectra)));
+        assert!(series.centroid_drift[1] > 0.0);
+        assert!(series.energy_db[1].abs() < 1e-5);
+    }
+
+    #[test]
+    fn plotted_drift_matches_the_energy_metric() {
+        let spectra = [spectrum(vec![1.0, 1.0]), spectrum(vec![2.0, 2.0])];
+        let series = DriftSeries::from_spectra(&spectra);
+        let metric = energy_drift_db(&spectra[1], &spectra[0]);
+        assert_eq!(series.energy_db[1], metric);
+        assert!((metric - 6.0206).abs() < 1e-3);
+    }
+
+    #[test]
+    fn plot_shades_the_band_and_flags_violations() {
+        let input = [stats(0.0, 100.0), stats(0.1, 101.0), stats(6.0, 99.0)];
+        let svg = render_energy_drift_plot(&input).to_svg_string();
+        assert_eq!(svg, render_energy_drift_plot(&input).to_svg_string());
+        assert!(svg.contains("opacity=\"0.25\""));
+        assert!(svg.contains("#d62728"));
+        assert!(svg.contains(">energy drift (dB)</text>"));
+
+        let image = render_energy_drift_image(&input);
+        assert_eq!(image, render_energy_drift_image(&input));
+        let frame_top = TOP as i32;
+        let banded = (frame_top..frame_top + 100)
+            .filter_map(|y| image.pixel(LEFT as i32 + 2, y))
+            .any(|c| c != WHITE && c != [0, 0, 0]);
+        assert!(banded);
+    }
+}
@@ -0,0 +1,351 @@
+//! Minimal SVG document builder.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/visual/spec.md`
+//!
+//! Coordinates are written with at most two decimals and colours as `#rrggbb`,
+//! so documents built from the same calls are byte-identical. Elements are
+//! emitted in insertion order.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use super::renderer::{quantize_rgb, Rgb8, Surface};
+use crate::{error::CoreResult, Fx};
+
+/// Formats a coordinate with at most two decimals and no trailing zeros.
+pub fn format_number(value: Fx) -> String {
+    if !value.is_finite() {
+        return "0".to_string();
+    }
+    let mut text = format!("{:.2}", value);
+    while text.ends_with('0') {
+        text.pop();
+    }
+    if text.ends_with('.') {
+        text.pop();
+    }
+    if text == "-0" {
+        text = "0".to_string();
+    }
+    text
+}
+
+/// `#rrggbb` form of a colour.
+pub fn hex_color(color: Rgb8) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+/// Escapes `&`, `<`, `>`, and quotes for text and attribute values.
+pub fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Fill and stroke of a shape.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Style {
+    pub fill: Option<Rgb8>,
+    pub stroke: Option<Rgb8>,
+    pub stroke_width: Fx,
+    /// Overall opacity in `[0, 1]`.
+    pub opacity: Fx,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            fill: None,
+            stroke: None,
+            stroke_width: 1.0,
+            opacity: 1.0,
+        }
+    }
+}
+
+impl Style {
+    pub fn fill(color: Rgb8) -> Self {
+        Self {
+            fill: Some(color),
+            ..Self::default()
+        }
+    }
+
+    pub fn stroke(color: Rgb8, width: Fx) -> Self {
+        Self {
+            stroke: Some(color),
+            stroke_width: width,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_opacity(mut self, opacity: Fx) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    fn attributes(&self) -> String {
+        let paint = |color: Option<Rgb8>| color.map_or("none".to_string(), hex_color);
+        let mut out = format!(" fill=\"{}\"", paint(self.fill));
+        if let Some(color) = self.stroke {
+            let _ = write!(
+                out,
+                " stroke=\"{}\" stroke-width=\"{}\"",
+                hex_color(color),
+                format_number(self.stroke_width)
+            );
+        }
+        if self.opacity < 1.0 {
+            // Quantized like a colour channel so opacity stays byte-stable.
+            let alpha = quantize_rgb([self.opacity; 3])[0] as Fx / 255.0;
+            let _ = write!(out, " opacity=\"{}\"", format_number(alpha));
+        }
+        out
+    }
+}
+
+/// Horizontal alignment of text relative to its anchor point.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextAnchor {
+    #[default]
+    Start,
+    Middle,
+    End,
+}
+
+impl TextAnchor {
+    fn as_str(self) -> &'static str {
+        match self {
+            TextAnchor::Start => "start",
+            TextAnchor::Middle => "middle",
+            TextAnchor::End => "end",
+        }
+    }
+}
+
+/// SVG document assembled element by element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgDocument {
+    width: u32,
+    height: u32,
+    elements: Vec<String>,
+}
+
+impl SvgDocument {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            elements: Vec::new(),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Number of elements added so far.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn rect(&mut self, x: Fx, y: Fx, width: Fx, height: Fx, style: Style) -> &mut Self {
+        self.elements.push(format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"{}/>",
+            format_number(x),
+            format_number(y),
+            format_number(width.max(0.0)),
+            format_number(height.max(0.0)),
+            style.attributes()
+        ));
+        self
+    }
+
+    pub fn line(&mut self, x1: Fx, y1: Fx, x2: Fx, y2: Fx, style: Style) -> &mut Self {
+        self.elements.push(format!(
+            "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"{}/>",
+            format_number(x1),
+            format_number(y1),
+            format_number(x2),
+            format_number(y2),
+            style.attributes()
+        ));
+        self
+    }
+
+    pub fn circle(&mut self, cx: Fx, cy: Fx, r: Fx, style: Style) -> &mut Self {
+        self.elements.push(format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"{}/>",
+            format_number(cx),
+            format_number(cy),
+            format_number(r.max(0.0)),
+            style.attributes()
+        ));
+        self
+    }
+
+    /// Open path through `points`; empty input adds nothing.
+    pub fn polyline(&mut self, points: &[(Fx, Fx)], style: Style) -> &mut Self {
+        if points.is_empty() {
+            return self;
+        }
+        let coords: Vec<String> = points
+            .iter()
+            .map(|&(x, y)| format!("{},{}", format_number(x), format_number(y)))
+            .collect();
+        self.elements.push(format!(
+            "<polyline points=\"{}\"{}/>",
+            coords.join(" "),
+            style.attributes()
+        ));
+        self
+    }
+
+    pub fn text(
+        &mut self,
+        x: Fx,
+        y: Fx,
+        size: Fx,
+        anchor: TextAnchor,
+        color: Rgb8,
+        content: &str,
+    ) -> &mut Self {
+        self.elements.push(format!(
+            "<text x=\"{}\" y=\"{}\" font-family=\"monospace\" font-size=\"{}\" \
+             text-anchor=\"{}\" fill=\"{}\">{}</text>",
+            format_number(x),
+            format_number(y),
+            format_number(size),
+            anchor.as_str(),
+            hex_color(color),
+            escape_xml(content)
+        ));
+        self
+    }
+
+    /// Serializes the document, one element per line.
+    pub fn to_svg_string(&self) -> String {
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\">\n",
+            w = self.width,
+            h = self.height
+        );
+        for element in &self.elements {
+            out.push_str(element);
+            out.push('\n');
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    /// Writes the document as an SVG file, replacing any existing file.
+    pub fn save(&self, path: impl AsRef<Path>) -> CoreResult<()> {
+        fs::write(path, self.to_svg_string())?;
+        Ok(())
+    }
+}
+
+impl Surface for SvgDocument {
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn fill_area(&mut self, x: Fx, y: Fx, width: Fx, height: Fx, color: Rgb8, opacity: Fx) {
+        self.rect(
+            x,
+            y,
+            width,
+            height,
+            Style::fill(color).with_opacity(opacity),
+        );
+    }
+
+    fn stroke_line(&mut self, x1: Fx, y1: Fx, x2: Fx, y2: Fx, color: Rgb8) {
+        self.line(x1, y1, x2, y2, Style::stroke(color, 1.0));
+    }
+
+    fn stroke_path(&mut self, points: &[(Fx, Fx)], color: Rgb8) {
+        self.polyline(points, Style::stroke(color, 1.0));
+    }
+
+    fn stroke_circle(&mut self, x: Fx, y: Fx, radius: Fx, color: Rgb8) {
+        self.circle(x, y, radius, Style::stroke(color, 1.0));
+    }
+
+    fn draw_marker(&mut self, x: Fx, y: Fx, radius: Fx, fill: Rgb8, stroke: Rgb8) {
+        let style = Style {
+            fill: Some(fill),
+            ..Style::stroke(stroke, 1.0)
+        };
+        self.circle(x, y, radius + 0.5, style);
+    }
+
+    fn draw_label(&mut self, x: Fx, y: Fx, text: &str, color: Rgb8, anchor: TextAnchor) {
+        // The raster glyphs are 7 px tall; an 8 px font puts its baseline there too.
+        self.text(x, y + 7.0, 8.0, anchor, color, text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::visual::renderer::{BLACK, WHITE};
+
+    #[test]
+    fn numbers_and_text_are_normalized() {
+        assert_eq!(format_number(1.0), "1");
+        assert_eq!(format_number(0.126), "0.13");
+        assert_eq!(format_number(-0.001), "0");
+        assert_eq!(format_number(Fx::NAN), "0");
+        assert_eq!(hex_color([255, 16, 0]), "#ff1000");
+        assert_eq!(escape_xml("a<b & \"c\""), "a&lt;b &amp; &quot;c&quot;");
+    }
+
+    #[test]
+    fn document_serializes_elements_in_order() {
+        let mut doc = SvgDocument::new(40, 20);
+        doc.rect(0.0, 0.0, 40.0, 20.0, Style::fill(WHITE))
+            .polyline(&[(0.0, 10.0), (20.5, 5.25)], Style::stroke(BLACK, 1.5))
+            .circle(30.0, 10.0, 2.0, Style::fill(BLACK).with_opacity(0.5))
+            .text(20.0, 18.0, 8.0, TextAnchor::Middle, BLACK, "ΔE < 1");
+        let svg = doc.to_svg_string();
+        let lines: Vec<&str> = svg.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"40\""));
+        assert_eq!(
+            lines[1],
+            "<rect x=\"0\" y=\"0\" width=\"40\" height=\"20\" fill=\"#ffffff\"/>"
+        );
+        assert_eq!(
+            lines[2],
+            "<polyline points=\"0,10 20.5,5.25\" fill=\"none\" stroke=\"#000000\" stroke-width=\"1.5\"/>"
+        );
+        assert!(lines[3].ends_with("opacity=\"0.5\"/>"));
+        assert!(lines[4].contains(">ΔE &lt; 1</text>"));
+        assert_eq!(lines[5], "</svg>");
+        assert_eq!(doc.clone().to_svg_string(), svg);
+    }
+}
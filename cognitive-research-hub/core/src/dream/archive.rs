@@ -0,0 +1,763 @@
+//! On-disk dream archive and pool snapshots.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/dream/spec.md`
+//!
+//! A [`DreamArchive`] is a directory holding:
+//!
+//! | File | Contents |
+//! | --- | --- |
+//! | `entries.log` | `CDRL` header, then `u32` length + entry body + CRC-32 frames |
+//! | `entries.idx` | `CDRX` header, then 24-byte records (offset, length, epoch, score, coherence) |
+//! | `pool.snapshot` | `CDPS` snapshot of a full [`SimpleDreamPool`] with CRC-32 trailer |
+//!
+//! The log is append-only; the index lets ranked retrieval run without
+//! decoding tensors and is rebuilt from the log whenever it is missing or does
+//! not cover the log's length. A torn trailing frame left by an interrupted
+//! write is dropped during that rebuild. All values are little-endian and
+//! floats are stored bit-exactly, so restored pools retrieve identically.
+//! Non-finite entries are refused on save, and bad magic, checksum mismatches
+//! and truncated records all surface as [`DreamError::Validation`].
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use super::{DreamEntry, EvictionPolicy, SimpleDreamPool};
+use crate::{
+    error::{CoreResult, DreamError},
+    tensor::{ChromaticTensor, Shape2D},
+    utils::crc32,
+    Fx,
+};
+
+/// Magic bytes opening the entry log.
+pub const DREAM_LOG_MAGIC: [u8; 4] = *b"CDRL";
+/// Magic bytes opening the index file.
+pub const DREAM_INDEX_MAGIC: [u8; 4] = *b"CDRX";
+/// Magic bytes opening a pool snapshot.
+pub const DREAM_SNAPSHOT_MAGIC: [u8; 4] = *b"CDPS";
+/// Layout version shared by the archive files.
+pub const DREAM_ARCHIVE_VERSION: u16 = 1;
+
+const FILE_HEADER_LEN: usize = 8;
+const INDEX_RECORD_LEN: usize = 24;
+const LOG_FILE: &str = "entries.log";
+const INDEX_FILE: &str = "entries.idx";
+const SNAPSHOT_FILE: &str = "pool.snapshot";
+
+fn file_header(magic: [u8; 4]) -> [u8; FILE_HEADER_LEN] {
+    let mut header = [0u8; FILE_HEADER_LEN];
+    header[..4].copy_from_slice(&magic);
+    header[4..6].copy_from_slice(&DREAM_ARCHIVE_VERSION.to_le_bytes());
+    header
+}
+
+fn check_header(bytes: &[u8], magic: [u8; 4], what: &str) -> CoreResult<()> {
+    if bytes.len() < FILE_HEADER_LEN || bytes[..4] != magic {
+        return Err(DreamError::Validation(format!(
+            "missing {} magic bytes",
+            what
+        )));
+    }
+    let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+    if version != DREAM_ARCHIVE_VERSION {
+        return Err(DreamError::Validation(format!(
+            "unsupported {} version {} (expected {})",
+            what, version, DREAM_ARCHIVE_VERSION
+        )));
+    }
+    Ok(())
+}
+
+/// Little-endian reader over a byte slice.
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, len: usize) -> CoreResult<&'a [u8]> {
+        let end = self.pos.saturating_add(len);
+        let slice = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or_else(|| DreamError::Validation("truncated dream record".to_string()))?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> CoreResult<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> CoreResult<u32> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u64(&mut self) -> CoreResult<u64> {
+        let b = self.take(8)?;
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(b);
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn f32(&mut self) -> CoreResult<Fx> {
+        Ok(f32::from_bits(self.u32()?))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+}
+
+fn check_finite(entry: &DreamEntry) -> CoreResult<()> {
+    if !entry.score.is_finite() || !entry.coherence.is_finite() {
+        return Err(DreamError::Validation(format!(
+            "dream entry at epoch {} has non-finite score {} or coherence {}",
+            entry.epoch, entry.score, entry.coherence
+        )));
+    }
+    let tensor = &entry.tensor;
+    let mut values = tensor.rgb.iter().chain(tensor.coh.iter().flatten());
+    if values.any(|value| !value.is_finite()) {
+        return Err(DreamError::Validation(format!(
+            "dream entry at epoch {} has non-finite tensor data",
+            entry.epoch
+        )));
+    }
+    Ok(())
+}
+
+/// Serializes an entry body: epoch, score, coherence, shape, RGB and optional coherence map.
+fn encode_entry(entry: &DreamEntry, out: &mut Vec<u8>) {
+    let tensor = &entry.tensor;
+    out.extend_from_slice(&entry.epoch.to_le_bytes());
+    out.extend_from_slice(&entry.score.to_bits().to_le_bytes());
+    out.extend_from_slice(&entry.coherence.to_bits().to_le_bytes());
+    out.extend_from_slice(&(tensor.shape.h as u32).to_le_bytes());
+    out.extend_from_slice(&(tensor.shape.w as u32).to_le_bytes());
+    out.push(u8::from(tensor.coh.is_some()));
+    for value in &tensor.rgb {
+        out.extend_from_slice(&value.to_bits().to_le_bytes());
+    }
+    if let Some(coh) = &tensor.coh {
+        for value in coh {
+            out.extend_from_slice(&value.to_bits().to_le_bytes());
+        }
+    }
+}
+
+fn decode_entry(reader: &mut ByteReader) -> CoreResult<DreamEntry> {
+    let epoch = reader.u32()?;
+    let score = reader.f32()?;
+    let coherence = reader.f32()?;
+    let h = reader.u32()? as usize;
+    let w = reader.u32()? as usize;
+    let has_coh = match reader.u8()? {
+        0 => false,
+        1 => true,
+        other => {
+            return Err(DreamError::Validation(format!(
+                "invalid coherence flag {} in dream record",
+                other
+            )))
+        }
+    };
+    if h == 0 || w == 0 {
+        return Err(DreamError::Validation(format!(
+            "dream record has empty {}x{} shape",
+            h, w
+        )));
+    }
+    let shape = Shape2D::new(h, w);
+    let remaining = reader.bytes.len().saturating_sub(reader.pos);
+    let needed = shape
+        .rgb_len()
+        .saturating_add(if has_coh { shape.cell_count() } else { 0 })
+        .saturating_mul(4);
+    if needed > remaining {
+        return Err(DreamError::Validation(format!(
+            "dream record for {}x{} tensor is truncated",
+            h, w
+        )));
+    }
+    let rgb = (0..shape.rgb_len())
+        .map(|_| reader.f32())
+        .collect::<CoreResult<Vec<_>>>()?;
+    let coh = if has_coh {
+        Some(
+            (0..shape.cell_count())
+                .map(|_| reader.f32())
+                .collect::<CoreResult<Vec<_>>>()?,
+        )
+    } else {
+        None
+    };
+    Ok(DreamEntry {
+        tensor: ChromaticTensor::new(shape, rgb, coh),
+        epoch,
+        score,
+        coherence,
+    })
+}
+
+/// Index row describing one record in the entry log.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArchiveRecord {
+    /// Byte offset of the frame inside `entries.log`.
+    pub offset: u64,
+    /// Length of the entry body in bytes.
+    pub len: u32,
+    pub epoch: u32,
+    pub score: Fx,
+    pub coherence: Fx,
+}
+
+impl ArchiveRecord {
+    fn frame_end(&self) -> u64 {
+        self.offset + 8 + self.len as u64
+    }
+
+    fn to_bytes(self) -> [u8; INDEX_RECORD_LEN] {
+        let mut bytes = [0u8; INDEX_RECORD_LEN];
+        bytes[0..8].copy_from_slice(&self.offset.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.len.to_le_bytes());
+        bytes[12..16].copy_from_slice(&self.epoch.to_le_bytes());
+        bytes[16..20].copy_from_slice(&self.score.to_bits().to_le_bytes());
+        bytes[20..24].copy_from_slice(&self.coherence.to_bits().to_le_bytes());
+        bytes
+    }
+
+    fn parse(reader: &mut ByteReader) -> CoreResult<Self> {
+        Ok(Self {
+            offset: reader.u64()?,
+            len: reader.u32()?,
+            epoch: reader.u32()?,
+            score: reader.f32()?,
+            coherence: reader.f32()?,
+        })
+    }
+}
+
+/// Append-only on-disk store of dream entries.
+#[derive(Debug)]
+pub struct DreamArchive {
+    dir: PathBuf,
+    records: Vec<ArchiveRecord>,
+    log_len: u64,
+}
+
+impl DreamArchive {
+    /// Opens (creating if necessary) the archive stored in `dir`.
+    pub fn open(dir: impl AsRef<Path>) -> CoreResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let log_path = dir.join(LOG_FILE);
+        if !log_path.exists() {
+            fs::write(&log_path, file_header(DREAM_LOG_MAGIC))?;
+            fs::write(dir.join(INDEX_FILE), file_header(DREAM_INDEX_MAGIC))?;
+        }
+        let log_len = fs::metadata(&log_path)?.len();
+        let mut archive = Self {
+            dir,
+            records: Vec::new(),
+            log_len,
+        };
+        match archive.read_index() {
+            Ok(records) if Self::index_covers(&records, log_len) => archive.records = records,
+            _ => archive.rebuild_index()?,
+        }
+        Ok(archive)
+    }
+
+    fn log_path(&self) -> PathBuf {
+        self.dir.join(LOG_FILE)
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.dir.join(INDEX_FILE)
+    }
+
+    /// Returns the path of the pool snapshot inside the archive directory.
+    pub fn snapshot_path(&self) -> PathBuf {
+        self.dir.join(SNAPSHOT_FILE)
+    }
+
+    fn index_covers(records: &[ArchiveRecord], log_len: u64) -> bool {
+        let end = records
+            .last()
+            .map_or(FILE_HEADER_LEN as u64, ArchiveRecord::frame_end);
+        end == log_len
+    }
+
+    fn read_index(&self) -> CoreResult<Vec<ArchiveRecord>> {
+        let bytes = fs::read(self.index_path())?;
+        check_header(&bytes, DREAM_INDEX_MAGIC, "dream index")?;
+        let body = &bytes[FILE_HEADER_LEN..];
+        if body.len() % INDEX_RECORD_LEN != 0 {
+            return Err(DreamError::Validation(
+                "dream index has a partial record".to_string(),
+            ));
+        }
+        let mut reader = ByteReader::new(body);
+        let mut records = Vec::with_capacity(body.len() / INDEX_RECORD_LEN);
+        while !reader.is_empty() {
+            records.push(ArchiveRecord::parse(&mut reader)?);
+        }
+        Ok(records)
+    }
+
+    fn write_index(&self) -> CoreResult<()> {
+        let mut bytes = Vec::with_capacity(FILE_HEADER_LEN + self.records.len() * INDEX_RECORD_LEN);
+        bytes.extend_from_slice(&file_header(DREAM_INDEX_MAGIC));
+        for record in &self.records {
+            bytes.extend_from_slice(&record.to_bytes());
+        }
+        fs::write(self.index_path(), bytes)?;
+        Ok(())
+    }
+
+    /// Rebuilds the index by scanning the log, truncating any torn trailing frame.
+    pub fn rebuild_index(&mut self) -> CoreResult<()> {
+        let bytes = fs::read(self.log_path())?;
+        check_header(&bytes, DREAM_LOG_MAGIC, "dream log")?;
+        let mut records = Vec::new();
+        let mut pos = FILE_HEADER_LEN;
+        while let Some((record, end)) = Self::scan_frame(&bytes, pos) {
+            records.push(record);
+            pos = end;
+        }
+        if pos < bytes.len() {
+            OpenOptions::new()
+                .write(true)
+                .open(self.log_path())?
+                .set_len(pos as u64)?;
+        }
+        self.records = records;
+        self.log_len = pos as u64;
+        self.write_index()
+    }
+
+    fn scan_frame(bytes: &[u8], pos: usize) -> Option<(ArchiveRecord, usize)> {
+        let len_bytes = bytes.get(pos..pos + 4)?;
+        let len = u32::from_le_bytes([len_bytes[0], len_bytes[1], len_bytes[2], len_bytes[3]]);
+        let body_start = pos + 4;
+        let body_end = body_start.checked_add(len as usize)?;
+        let body = bytes.get(body_start..body_end)?;
+        let crc_bytes = bytes.get(body_end..body_end + 4)?;
+        let stored = u32::from_le_bytes([crc_bytes[0], crc_bytes[1], crc_bytes[2], crc_bytes[3]]);
+        if stored != crc32(body) {
+            return None;
+        }
+        let entry = decode_entry(&mut ByteReader::new(body)).ok()?;
+        let record = ArchiveRecord {
+            offset: pos as u64,
+            len,
+            epoch: entry.epoch,
+            score: entry.score,
+            coherence: entry.coherence,
+        };
+        Some((record, body_end + 4))
+    }
+
+    /// Returns the number of stored entries.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns true when the archive holds no entries.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Returns the index rows in append order.
+    pub fn records(&self) -> &[ArchiveRecord] {
+        &self.records
+    }
+
+    /// Appends an entry to the log and index, returning its record id.
+    ///
+    /// Entries with a non-finite score, coherence or tensor value are reported
+    /// as [`DreamError::Validation`] and leave the archive untouched.
+    pub fn save_entry(&mut self, entry: &DreamEntry) -> CoreResult<usize> {
+        check_finite(entry)?;
+        let mut body = Vec::new();
+        encode_entry(entry, &mut body);
+        let len = u32::try_from(body.len())
+            .map_err(|_| DreamError::Validation("dream entry too large to archive".to_string()))?;
+        let mut frame = Vec::with_capacity(body.len() + 8);
+        frame.extend_from_slice(&len.to_le_bytes());
+        frame.extend_from_slice(&body);
+        frame.extend_from_slice(&crc32(&body).to_le_bytes());
+        let mut log = OpenOptions::new().append(true).open(self.log_path())?;
+        log.write_all(&frame)?;
+        log.flush()?;
+        let record = ArchiveRecord {
+            offset: self.log_len,
+            len,
+            epoch: entry.epoch,
+            score: entry.score,
+            coherence: entry.coherence,
+        };
+        let mut index = OpenOptions::new().append(true).open(self.index_path())?;
+        index.write_all(&record.to_bytes())?;
+        index.flush()?;
+        self.log_len = record.frame_end();
+        self.records.push(record);
+        Ok(self.records.len() - 1)
+    }
+
+    /// Reads and verifies the entry with record id `id`.
+    pub fn load_entry(&self, id: usize) -> CoreResult<DreamEntry> {
+        let record = self
+            .records
+            .get(id)
+            .ok_or_else(|| DreamError::Dream(format!("no archived dream with id {}", id)))?;
+        let mut log = File::open(self.log_path())?;
+        log.seek(SeekFrom::Start(record.offset + 4))?;
+        let mut body = vec![0u8; record.len as usize];
+        log.read_exact(&mut body)?;
+        let mut crc = [0u8; 4];
+        log.read_exact(&mut crc)?;
+        if u32::from_le_bytes(crc) != crc32(&body) {
+            return Err(DreamError::Validation(format!(
+                "checksum mismatch for archived dream {}",
+                id
+            )));
+        }
+        decode_entry(&mut ByteReader::new(&body))
+    }
+
+    /// Loads only the tensor of the entry with record id `id`.
+    pub fn entry_to_tensor(&self, id: usize) -> CoreResult<ChromaticTensor> {
+        self.load_entry(id).map(|entry| entry.tensor)
+    }
+
+    /// Returns record ids ordered by descending score, then ascending epoch, then id.
+    pub fn ranked_ids(&self) -> Vec<usize> {
+        let mut ids: Vec<usize> = (0..self.records.len()).collect();
+        ids.sort_by(|&a, &b| {
+            let (ra, rb) = (&self.records[a], &self.records[b]);
+            rb.score
+                .total_cmp(&ra.score)
+                .then(ra.epoch.cmp(&rb.epoch))
+                .then(a.cmp(&b))
+        });
+        ids
+    }
+
+    /// Loads the `limit` best entries in rank order.
+    pub fn retrieve_ranked(&self, limit: usize) -> CoreResult<Vec<DreamEntry>> {
+        self.ranked_ids()
+            .into_iter()
+            .take(limit)
+            .map(|id| self.load_entry(id))
+            .collect()
+    }
+
+    /// Rewrites the log keeping only the `keep` best-ranked entries in their
+    /// original append order. Returns the number of entries removed.
+    pub fn compact(&mut self, keep: usize) -> CoreResult<usize> {
+        let mut kept: Vec<usize> = self.ranked_ids().into_iter().take(keep).collect();
+        kept.sort_unstable();
+        let removed = self.records.len() - kept.len();
+        if removed == 0 {
+            return Ok(0);
+        }
+        let source = fs::read(self.log_path())?;
+        let mut bytes = Vec::with_capacity(source.len());
+        bytes.extend_from_slice(&file_header(DREAM_LOG_MAGIC));
+        let mut records = Vec::with_capacity(kept.len());
+        for id in kept {
+            let record = self.records[id];
+            let start = record.offset as usize;
+            let end = record.frame_end() as usize;
+            let frame = source.get(start..end).ok_or_else(|| {
+                DreamError::Validation(format!("archived dream {} lies beyond the log", id))
+            })?;
+            records.push(ArchiveRecord {
+                offset: bytes.len() as u64,
+                ..record
+            });
+            bytes.extend_from_slice(frame);
+        }
+        let tmp = self.dir.join(format!("{}.tmp", LOG_FILE));
+        fs::write(&tmp, &bytes)?;
+        fs::rename(&tmp, self.log_path())?;
+        self.log_len = bytes.len() as u64;
+        self.records = records;
+        self.write_index()?;
+        Ok(removed)
+    }
+
+    /// Writes a snapshot of `pool` to `pool.snapshot` in the archive directory.
+    pub fn snapshot(&self, pool: &SimpleDreamPool) -> CoreResult<()> {
+        pool.save_snapshot(self.snapshot_path())
+    }
+
+    /// Restores the pool stored by [`DreamArchive::snapshot`].
+    pub fn restore(&self) -> CoreResult<SimpleDreamPool> {
+        SimpleDreamPool::load_snapshot(self.snapshot_path())
+    }
+}
+
+fn encode_policy(policy: EvictionPolicy, out: &mut Vec<u8>) {
+    let (tag, weight) = match policy {
+        EvictionPolicy::ScoreThenOldest => (0u8, 0.0),
+        EvictionPolicy::NoveltyWeighted { weight } => (1, weight),
+        EvictionPolicy::HueNiches => (2, 0.0),
+    };
+    out.push(tag);
+    out.extend_from_slice(&weight.to_bits().to_le_bytes());
+}
+
+fn decode_policy(reader: &mut ByteReader) -> CoreResult<EvictionPolicy> {
+    let tag = reader.u8()?;
+    let weight = reader.f32()?;
+    match tag {
+        0 => Ok(EvictionPolicy::ScoreThenOldest),
+        1 => Ok(EvictionPolicy::NoveltyWeighted { weight }),
+        2 => Ok(EvictionPolicy::HueNiches),
+        other => Err(DreamError::Validation(format!(
+            "unknown eviction policy tag {}",
+            other
+        ))),
+    }
+}
+
+impl SimpleDreamPool {
+    /// Serializes the complete pool state, including `epoch_cursor`, threshold and policy.
+    pub fn to_snapshot_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&file_header(DREAM_SNAPSHOT_MAGIC));
+        bytes.extend_from_slice(&(self.max_size as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.coherence_threshold.to_bits().to_le_bytes());
+        bytes.extend_from_slice(&self.epoch_cursor.to_le_bytes());
+        encode_policy(self.eviction, &mut bytes);
+        bytes.extend_from_slice(&(self.entries.len() as u32).to_le_bytes());
+        for entry in &self.entries {
+            encode_entry(entry, &mut bytes);
+        }
+        let checksum = crc32(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    /// Restores a pool from [`SimpleDreamPool::to_snapshot_bytes`] output.
+    pub fn from_snapshot_bytes(bytes: &[u8]) -> CoreResult<Self> {
+        if bytes.len() < FILE_HEADER_LEN + 4 {
+            return Err(DreamError::Validation(format!(
+                "dream pool snapshot truncated at {} bytes",
+                bytes.len()
+            )));
+        }
+        check_header(bytes, DREAM_SNAPSHOT_MAGIC, "dream pool snapshot")?;
+        let body_end = bytes.len() - 4;
+        let stored = u32::from_le_bytes([
+            bytes[body_end],
+            bytes[body_end + 1],
+            bytes[body_end + 2],
+            bytes[body_end + 3],
+        ]);
+        if stored != crc32(&bytes[..body_end]) {
+            return Err(DreamError::Validation(
+                "dream pool snapshot checksum mismatch".to_string(),
+            ));
+        }
+        let mut reader = ByteReader::new(&bytes[FILE_HEADER_LEN..body_end]);
+        let max_size = reader.u32()? as usize;
+        let coherence_threshold = reader.f32()?;
+        let epoch_cursor = reader.u32()?;
+        let eviction = decode_policy(&mut reader)?;
+        let count = reader.u32()? as usize;
+        if max_size == 0 || count > max_size {
+            return Err(DreamError::Validation(format!(
+                "snapshot holds {} entries for capacity {}",
+                count, max_size
+            )));
+        }
+        let entries = (0..count)
+            .map(|_| decode_entry(&mut reader))
+            .collect::<CoreResult<Vec<_>>>()?;
+        if !reader.is_empty() {
+            return Err(DreamError::Validation(
+                "trailing bytes in dream pool snapshot".to_string(),
+            ));
+        }
+        Ok(Self {
+            entries,
+            max_size,
+            coherence_threshold,
+            epoch_cursor,
+            eviction,
+        })
+    }
+
+    /// Writes a snapshot to `path`.
+    pub fn save_snapshot(&self, path: impl AsRef<Path>) -> CoreResult<()> {
+        fs::write(path, self.to_snapshot_bytes())?;
+        Ok(())
+    }
+
+    /// Reads a snapshot from `path`.
+    pub fn load_snapshot(path: impl AsRef<Path>) -> CoreResult<Self> {
+        let bytes = fs::read(path)?;
+        Self::from_snapshot_bytes(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("dream_archive_{}_{}", name, std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        dir
+    }
+
+    fn entry(value: Fx, epoch: u32, score: Fx) -> DreamEntry {
+        let shape = Shape2D::new(2, 2);
+        let rgb = vec![value; shape.rgb_len()];
+        DreamEntry::new(
+            ChromaticTensor::new(shape, rgb, Some(vec![0.75; 4])),
+            epoch,
+            score,
+        )
+    }
+
+    #[test]
+    fn entries_survive_reopen_and_rank_deterministically() {
+        let dir = temp_dir("reopen");
+        {
+            let mut archive = DreamArchive::open(&dir).unwrap();
+            for (idx, score) in [0.4, 0.9, 0.4, 0.7].iter().enumerate() {
+                archive
+                    .save_entry(&entry(0.1 * idx as Fx, idx as u32, *score))
+                    .unwrap();
+            }
+        }
+        let archive = DreamArchive::open(&dir).unwrap();
+        assert_eq!(archive.len(), 4);
+        assert_eq!(archive.ranked_ids(), vec![1, 3, 0, 2]);
+        let best = archive.retrieve_ranked(1).unwrap();
+        assert_eq!(best[0].epoch, 1);
+        assert_eq!(best[0].tensor.coh.as_deref(), Some(&[0.75; 4][..]));
+        let tensor = archive.entry_to_tensor(3).unwrap();
+        assert!(tensor.rgb.iter().all(|&v| (v - 0.3).abs() <= 1e-6));
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn torn_tail_and_missing_index_are_recovered() {
+        let dir = temp_dir("torn");
+        {
+            let mut archive = DreamArchive::open(&dir).unwrap();
+            archive.save_entry(&entry(0.2, 0, 0.5)).unwrap();
+            archive.save_entry(&entry(0.4, 1, 0.6)).unwrap();
+        }
+        let log = dir.join(LOG_FILE);
+        let len = fs::metadata(&log).unwrap().len();
+        OpenOptions::new()
+            .write(true)
+            .open(&log)
+            .unwrap()
+            .set_len(len - 3)
+            .unwrap();
+        fs::remove_file(dir.join(INDEX_FILE)).unwrap();
+        let mut archive = DreamArchive::open(&dir).unwrap();
+        assert_eq!(archive.len(), 1);
+        assert_eq!(archive.load_entry(0).unwrap().epoch, 0);
+        archive.save_entry(&entry(0.6, 2, 0.7)).unwrap();
+        let reopened = DreamArchive::open(&dir).unwrap();
+        assert_eq!(reopened.records(), archive.records());
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn compaction_keeps_best_entries_in_append_order() {
+        let dir = temp_dir("compact");
+        let mut archive = DreamArchive::open(&dir).unwrap();
+        for (epoch, score) in [0.1, 0.8, 0.3, 0.9, 0.2].iter().enumerate() {
+            archive
+                .save_entry(&entry(0.5, epoch as u32, *score))
+                .unwrap();
+        }
+        assert_eq!(archive.compact(2).unwrap(), 3);
+        let epochs: Vec<u32> = archive.records().iter().map(|r| r.epoch).collect();
+        assert_eq!(epochs, vec![1, 3]);
+        let reopened = DreamArchive::open(&dir).unwrap();
+        assert_eq!(reopened.records(), archive.records());
+        assert_eq!(reopened.load_entry(1).unwrap().score, 0.9);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn snapshot_round_trips_full_pool_state() {
+        let mut pool = SimpleDreamPool::new(3, 0.25)
+            .with_eviction(EvictionPolicy::NoveltyWeighted { weight: 0.5 });
+        for value in [0.2, 0.5] {
+            let epoch = pool.next_epoch();
+            crate::dream::add_dream_to_pool(&mut pool, entry(value, epoch, value));
+        }
+        pool.next_epoch();
+        let mut bytes = pool.to_snapshot_bytes();
+        let restored = SimpleDreamPool::from_snapshot_bytes(&bytes).unwrap();
+        assert_eq!(restored.to_snapshot_bytes(), bytes);
+        assert_eq!(restored.epoch_cursor, 3);
+        assert_eq!(restored.eviction(), pool.eviction());
+        let last = bytes.len() - 6;
+        bytes[last] ^= 0x01;
+        assert!(SimpleDreamPool::from_snapshot_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn truncated_snapshots_are_rejected_without_panicking() {
+        let bytes = SimpleDreamPool::new(2, 0.25).to_snapshot_bytes();
+        for len in 0..=12 {
+            let result = SimpleDreamPool::from_snapshot_bytes(&bytes[..len]);
+            assert!(
+                matches!(result, Err(DreamError::Validation(_))),
+                "length {}",
+                len
+            );
+        }
+    }
+
+    #[test]
+    fn non_finite_entries_and_corrupt_frames_are_validation_errors() {
+        let dir = temp_dir("non_finite");
+        let mut archive = DreamArchive::open(&dir).unwrap();
+        archive.save_entry(&entry(0.5, 0, 0.4)).unwrap();
+        let mut poisoned = entry(0.5, 2, 0.6);
+        poisoned.tensor.rgb[1] = Fx::INFINITY;
+        for bad in [entry(0.5, 1, Fx::NAN), poisoned] {
+            assert!(matches!(
+                archive.save_entry(&bad),
+                Err(DreamError::Validation(_))
+            ));
+        }
+        assert_eq!(archive.len(), 1);
+        assert_eq!(archive.ranked_ids(), vec![0]);
+        assert_eq!(DreamArchive::open(&dir).unwrap().len(), 1);
+
+        let log = dir.join(LOG_FILE);
+        let mut bytes = fs::read(&log).unwrap();
+        bytes[FILE_HEADER_LEN + 8] ^= 0x01;
+        fs::write(&log, &bytes).unwrap();
+        assert!(matches!(
+            archive.load_entry(0),
+            Err(DreamError::Validation(_))
+        ));
+        fs::remove_dir_all(&dir).ok();
+    }
+}
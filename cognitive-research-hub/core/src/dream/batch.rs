@@ -0,0 +1,247 @@
+//! Batched dream cycles over many targets with deterministic parallelism.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/dream/spec.md`
+//!
+//! Target `i` always runs on its own pool with the RNG stream
+//! `ChaCha8Rng::seed_from_u64(base_seed).split(i)`. Workers only decide *when*
+//! a target runs, never *how*, and results are written back by index, so the
+//! output is bitwise identical for any thread count. The optional shared pool
+//! is merged afterwards in target order, then per-pool entry order.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use super::{
+    add_dream_to_pool, dream_cycle_observed, CycleSummary, DreamConfig, EvictionPolicy,
+    SimpleDreamPool,
+};
+use crate::{tensor::ChromaticTensor, utils::ChaCha8Rng, Fx};
+
+/// Settings shared by every cycle in a batch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchConfig {
+    /// Root seed; target `i` draws from `split(i)`.
+    pub base_seed: u64,
+    pub epochs: u32,
+    pub pool_size: usize,
+    pub coherence_threshold: Fx,
+    pub eviction: EvictionPolicy,
+    /// Worker threads; `0` uses the available parallelism.
+    pub threads: usize,
+    /// Capacity of a merged pool collecting every target's entries, if wanted.
+    pub shared_pool_size: Option<usize>,
+    pub dream: DreamConfig,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            base_seed: 0,
+            epochs: 8,
+            pool_size: 8,
+            coherence_threshold: 0.0,
+            eviction: EvictionPolicy::default(),
+            threads: 0,
+            shared_pool_size: None,
+            dream: DreamConfig::default(),
+        }
+    }
+}
+
+impl BatchConfig {
+    /// Returns the RNG used for the target at `index`.
+    pub fn target_rng(&self, index: usize) -> ChaCha8Rng {
+        ChaCha8Rng::seed_from_u64(self.base_seed).split(index as u64)
+    }
+
+    fn worker_count(&self, jobs: usize) -> usize {
+        let threads = if self.threads == 0 {
+            thread::available_parallelism().map_or(1, |n| n.get())
+        } else {
+            self.threads
+        };
+        threads.clamp(1, jobs.max(1))
+    }
+}
+
+/// Result of one target's cycle.
+#[derive(Debug)]
+pub struct TargetOutcome {
+    pub target_index: usize,
+    pub pool: SimpleDreamPool,
+    pub summary: CycleSummary,
+}
+
+/// Output of [`run_dream_batch`], ordered by target index.
+#[derive(Debug)]
+pub struct BatchResult {
+    pub outcomes: Vec<TargetOutcome>,
+    /// Entries of all pools merged in target order, when requested.
+    pub shared_pool: Option<SimpleDreamPool>,
+}
+
+fn run_target(target: &ChromaticTensor, index: usize, config: &BatchConfig) -> TargetOutcome {
+    let mut pool = SimpleDreamPool::new(config.pool_size, config.coherence_threshold)
+        .with_eviction(config.eviction);
+    let mut rng = config.target_rng(index);
+    let summary = dream_cycle_observed(
+        target,
+        &mut pool,
+        config.epochs,
+        &config.dream,
+        &mut rng,
+        &mut (),
+    );
+    TargetOutcome {
+        target_index: index,
+        pool,
+        summary,
+    }
+}
+
+/// Runs an independent dream cycle per target on a scoped thread pool.
+pub fn run_dream_batch(targets: &[ChromaticTensor], config: &BatchConfig) -> BatchResult {
+    let workers = config.worker_count(targets.len());
+    let slots: Vec<Mutex<Option<TargetOutcome>>> =
+        targets.iter().map(|_| Mutex::new(None)).collect();
+    if workers <= 1 {
+        for (index, target) in targets.iter().enumerate() {
+            *lock(&slots[index]) = Some(run_target(target, index, config));
+        }
+    } else {
+        let next = AtomicUsize::new(0);
+        thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(target) = targets.get(index) else {
+                        break;
+                    };
+                    let outcome = run_target(target, index, config);
+                    *lock(&slots[index]) = Some(outcome);
+                });
+            }
+        });
+    }
+    let outcomes: Vec<TargetOutcome> = slots
+        .into_iter()
+        .map(|slot| {
+            slot.into_inner()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .expect("every target slot is filled before the scope ends")
+        })
+        .collect();
+    let shared_pool = config
+        .shared_pool_size
+        .map(|capacity| merge_pools(&outcomes, capacity, config));
+    BatchResult {
+        outcomes,
+        shared_pool,
+    }
+}
+
+fn lock<T>(slot: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    slot.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Offers every entry to one pool in target order; original epochs are kept.
+fn merge_pools(
+    outcomes: &[TargetOutcome],
+    capacity: usize,
+    config: &BatchConfig,
+) -> SimpleDreamPool {
+    let mut shared =
+        SimpleDreamPool::new(capacity, config.coherence_threshold).with_eviction(config.eviction);
+    for outcome in outcomes {
+        for entry in outcome.pool.entries() {
+            add_dream_to_pool(&mut shared, entry.clone());
+        }
+    }
+    shared.epoch_cursor = outcomes
+        .iter()
+        .map(|o| o.pool.epoch_cursor)
+        .max()
+        .unwrap_or(0);
+    shared
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::Shape2D;
+
+    fn targets() -> Vec<ChromaticTensor> {
+        let shape = Shape2D::new(2, 3);
+        (0..5)
+            .map(|i| {
+                let v = 0.15 * i as Fx + 0.1;
+                let rgb = [v, 0.5, 1.0 - v]
+                    .iter()
+                    .copied()
+                    .cycle()
+                    .take(shape.rgb_len())
+                    .collect();
+                ChromaticTensor::new(shape, rgb, None)
+            })
+            .collect()
+    }
+
+    fn fingerprint(result: &BatchResult) -> Vec<Vec<u8>> {
+        let mut out: Vec<Vec<u8>> = result
+            .outcomes
+            .iter()
+            .map(|o| o.pool.to_snapshot_bytes())
+            .collect();
+        out.extend(result.shared_pool.iter().map(|p| p.to_snapshot_bytes()));
+        out
+    }
+
+    #[test]
+    fn output_is_independent_of_thread_count() {
+        let base = BatchConfig {
+            base_seed: 17,
+            shared_pool_size: Some(6),
+            ..BatchConfig::default()
+        };
+        let serial = run_dream_batch(
+            &targets(),
+            &BatchConfig {
+                threads: 1,
+                ..base.clone()
+            },
+        );
+        let parallel = run_dream_batch(&targets(), &BatchConfig { threads: 4, ..base });
+        assert_eq!(fingerprint(&serial), fingerprint(&parallel));
+        let indices: Vec<usize> = parallel.outcomes.iter().map(|o| o.target_index).collect();
+        assert_eq!(indices, vec![0, 1, 2, 3, 4]);
+        let shared = parallel.shared_pool.unwrap();
+        assert_eq!(shared.len(), 6);
+        assert_eq!(shared.epoch_cursor, 8);
+    }
+
+    #[test]
+    fn target_streams_match_single_cycles() {
+        let config = BatchConfig {
+            base_seed: 3,
+            threads: 2,
+            ..BatchConfig::default()
+        };
+        let targets = targets();
+        let batch = run_dream_batch(&targets, &config);
+        let mut pool = SimpleDreamPool::new(config.pool_size, config.coherence_threshold);
+        crate::dream::dream_cycle(
+            &targets[2],
+            &mut pool,
+            config.epochs,
+            &mut config.target_rng(2),
+        );
+        assert_eq!(
+            batch.outcomes[2].pool.to_snapshot_bytes(),
+            pool.to_snapshot_bytes()
+        );
+    }
+}
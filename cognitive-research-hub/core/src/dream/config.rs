@@ -0,0 +1,517 @@
+//! Dream cycle configuration loaded from `config/dream_params.toml`.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/dream/spec.md`
+//!
+//! The generation rule is `D' = αD + (1 − α)N + β·F(t)`: `alpha` and `beta`
+//! are the blend coefficients, the [`NoiseSchedule`] sets the amplitude of the
+//! LUT noise tensor `N` per epoch, and the [`SeedingStrategy`] picks which pool
+//! entry seeds the next epoch. The `[evaluator]` table picks the scorer and
+//! its weights. Keys missing from the file keep their defaults.
+
+use std::path::Path;
+
+use super::evaluator::{EvaluatorConfig, LegacyEvaluator, SpectralEvaluator};
+use super::noise::NoiseProfile;
+use crate::{
+    error::{CoreResult, DreamError},
+    utils::{parse_toml, JsonValue},
+    Fx,
+};
+
+/// Per-epoch amplitude of the LUT noise tensor.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NoiseSchedule {
+    /// Fixed amplitude every epoch.
+    Constant { level: Fx },
+    /// Cosine annealing from `max` to `min`, restarting every `period` epochs.
+    Cosine { max: Fx, min: Fx, period: u32 },
+    /// Sawtooth `base + increment × (step mod period)`.
+    Step {
+        base: Fx,
+        increment: Fx,
+        period: u32,
+    },
+    /// Shrinks by `gain` after an improving epoch and grows by `gain` otherwise.
+    Adaptive {
+        initial: Fx,
+        min: Fx,
+        max: Fx,
+        gain: Fx,
+    },
+}
+
+impl Default for NoiseSchedule {
+    fn default() -> Self {
+        NoiseSchedule::Step {
+            base: 0.05,
+            increment: 0.02,
+            period: 7,
+        }
+    }
+}
+
+impl NoiseSchedule {
+    /// Returns the configuration name of the schedule.
+    pub fn name(&self) -> &'static str {
+        match self {
+            NoiseSchedule::Constant { .. } => "constant",
+            NoiseSchedule::Cosine { .. } => "cosine",
+            NoiseSchedule::Step { .. } => "step",
+            NoiseSchedule::Adaptive { .. } => "adaptive",
+        }
+    }
+
+    /// Returns the amplitude used before any epoch has run.
+    pub fn initial_level(&self) -> Fx {
+        self.level(0, 0.0, None)
+    }
+
+    /// Returns the amplitude for `step`.
+    ///
+    /// `previous` is the amplitude of the prior epoch and `improvement` the
+    /// change in best pool score it produced; only the adaptive schedule reads them.
+    pub fn level(&self, step: u32, previous: Fx, improvement: Option<Fx>) -> Fx {
+        let level = match *self {
+            NoiseSchedule::Constant { level } => level,
+            NoiseSchedule::Cosine { max, min, period } => {
+                let period = period.max(1);
+                let phase = (step % period) as Fx / period as Fx;
+                min + 0.5 * (max - min) * (1.0 + (std::f32::consts::PI * phase).cos())
+            }
+            NoiseSchedule::Step {
+                base,
+                increment,
+                period,
+            } => base + increment * (step % period.max(1)) as Fx,
+            NoiseSchedule::Adaptive {
+                initial,
+                min,
+                max,
+                gain,
+            } => match improvement {
+                None => initial.clamp(min, max),
+                Some(delta) if delta > 0.0 => (previous * (1.0 - gain)).clamp(min, max),
+                Some(_) => (previous * (1.0 + gain)).clamp(min, max),
+            },
+        };
+        level.clamp(0.0, 1.0)
+    }
+
+    /// Returns the schedule with every amplitude multiplied by `factor`,
+    /// clamped to `[0, 1]`; periods and the adaptive gain are unchanged.
+    pub fn scaled(&self, factor: Fx) -> Self {
+        let scale = |value: Fx| (value * factor).clamp(0.0, 1.0);
+        match *self {
+            NoiseSchedule::Constant { level } => NoiseSchedule::Constant {
+                level: scale(level),
+            },
+            NoiseSchedule::Cosine { max, min, period } => NoiseSchedule::Cosine {
+                max: scale(max),
+                min: scale(min),
+                period,
+            },
+            NoiseSchedule::Step {
+                base,
+                increment,
+                period,
+            } => NoiseSchedule::Step {
+                base: scale(base),
+                increment: scale(increment),
+                period,
+            },
+            NoiseSchedule::Adaptive {
+                initial,
+                min,
+                max,
+                gain,
+            } => NoiseSchedule::Adaptive {
+                initial: scale(initial),
+                min: scale(min),
+                max: scale(max),
+                gain,
+            },
+        }
+    }
+
+    fn validate(&self) -> CoreResult<()> {
+        let unit = |name: &str, value: Fx| {
+            if (0.0..=1.0).contains(&value) {
+                Ok(())
+            } else {
+                Err(DreamError::Config(format!(
+                    "noise_schedule.{} must lie in [0, 1], got {}",
+                    name, value
+                )))
+            }
+        };
+        let positive = |value: u32| {
+            if value > 0 {
+                Ok(())
+            } else {
+                Err(DreamError::Config(
+                    "noise_schedule.period must be positive".to_string(),
+                ))
+            }
+        };
+        match *self {
+            NoiseSchedule::Constant { level } => unit("level", level),
+            NoiseSchedule::Cosine { max, min, period } => {
+                unit("max", max)?;
+                unit("min", min)?;
+                positive(period)
+            }
+            NoiseSchedule::Step {
+                base,
+                increment,
+                period,
+            } => {
+                unit("base", base)?;
+                unit("increment", increment)?;
+                positive(period)
+            }
+            NoiseSchedule::Adaptive {
+                initial,
+                min,
+                max,
+                gain,
+            } => {
+                unit("initial", initial)?;
+                unit("min", min)?;
+                unit("max", max)?;
+                unit("gain", gain)?;
+                if min > max {
+                    return Err(DreamError::Config(
+                        "noise_schedule.min must not exceed max".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn from_table(table: &JsonValue) -> CoreResult<Self> {
+        let kind = str_field(table, "kind", Some("noise_schedule"))?.unwrap_or("step");
+        let schedule = match kind {
+            "constant" => NoiseSchedule::Constant {
+                level: fx_field(table, "level", Some("noise_schedule"), 0.1)?,
+            },
+            "cosine" => NoiseSchedule::Cosine {
+                max: fx_field(table, "max", Some("noise_schedule"), 0.2)?,
+                min: fx_field(table, "min", Some("noise_schedule"), 0.02)?,
+                period: u32_field(table, "period", Some("noise_schedule"), 16)?,
+            },
+            "step" => NoiseSchedule::Step {
+                base: fx_field(table, "base", Some("noise_schedule"), 0.05)?,
+                increment: fx_field(table, "increment", Some("noise_schedule"), 0.02)?,
+                period: u32_field(table, "period", Some("noise_schedule"), 7)?,
+            },
+            "adaptive" => NoiseSchedule::Adaptive {
+                initial: fx_field(table, "initial", Some("noise_schedule"), 0.1)?,
+                min: fx_field(table, "min", Some("noise_schedule"), 0.02)?,
+                max: fx_field(table, "max", Some("noise_schedule"), 0.3)?,
+                gain: fx_field(table, "gain", Some("noise_schedule"), 0.2)?,
+            },
+            other => {
+                return Err(DreamError::Config(format!(
+                    "unknown noise schedule `{}`",
+                    other
+                )))
+            }
+        };
+        schedule.validate()?;
+        Ok(schedule)
+    }
+}
+
+/// Rule choosing the pool entry that seeds the next epoch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SeedingStrategy {
+    /// Always reseed from the highest-scoring entry.
+    #[default]
+    Best,
+    /// Sample uniformly among the `k` highest-scoring entries.
+    TopKSample { k: usize },
+    /// Draw `size` entries with replacement and keep the best of them.
+    Tournament { size: usize },
+}
+
+impl SeedingStrategy {
+    /// Returns the configuration name of the strategy.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SeedingStrategy::Best => "best",
+            SeedingStrategy::TopKSample { .. } => "top_k",
+            SeedingStrategy::Tournament { .. } => "tournament",
+        }
+    }
+
+    fn from_table(table: &JsonValue) -> CoreResult<Self> {
+        let strategy = match str_field(table, "strategy", Some("seeding"))?.unwrap_or("best") {
+            "best" => SeedingStrategy::Best,
+            "top_k" => SeedingStrategy::TopKSample {
+                k: u32_field(table, "k", Some("seeding"), 3)? as usize,
+            },
+            "tournament" => SeedingStrategy::Tournament {
+                size: u32_field(table, "size", Some("seeding"), 2)? as usize,
+            },
+            other => {
+                return Err(DreamError::Config(format!(
+                    "unknown seeding strategy `{}`",
+                    other
+                )))
+            }
+        };
+        match strategy {
+            SeedingStrategy::TopKSample { k: 0 } | SeedingStrategy::Tournament { size: 0 } => Err(
+                DreamError::Config("seeding sample size must be positive".to_string()),
+            ),
+            _ => Ok(strategy),
+        }
+    }
+}
+
+/// Parameters controlling dream generation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DreamConfig {
+    /// Retention weight `α` of the current dream.
+    pub alpha: Fx,
+    /// Gain `β` of the spectral feedback term `F(t)`.
+    pub beta: Fx,
+    /// Noise LUT sampled when perturbing seeds.
+    pub noise_profile: NoiseProfile,
+    /// Per-epoch noise amplitude.
+    pub noise_schedule: NoiseSchedule,
+    /// How the next epoch's seed is chosen from the pool.
+    pub seeding: SeedingStrategy,
+    /// Scorer applied to every generated dream.
+    pub evaluator: EvaluatorConfig,
+}
+
+impl Default for DreamConfig {
+    fn default() -> Self {
+        Self {
+            alpha: 0.9,
+            beta: 0.05,
+            noise_profile: NoiseProfile::default(),
+            noise_schedule: NoiseSchedule::default(),
+            seeding: SeedingStrategy::default(),
+            evaluator: EvaluatorConfig::default(),
+        }
+    }
+}
+
+impl DreamConfig {
+    /// Returns a copy of the configuration using `profile` for perturbation.
+    pub fn with_noise_profile(mut self, profile: NoiseProfile) -> Self {
+        self.noise_profile = profile;
+        self
+    }
+
+    /// Parses a `dream_params.toml` document.
+    pub fn from_toml_str(text: &str) -> CoreResult<Self> {
+        let doc = parse_toml(text)?;
+        let defaults = Self::default();
+        let noise_profile = match str_field(&doc, "noise_profile", None)? {
+            Some(name) => NoiseProfile::from_name(name)
+                .ok_or_else(|| DreamError::Config(format!("unknown noise profile `{}`", name)))?,
+            None => defaults.noise_profile,
+        };
+        let noise_schedule = match doc.get("noise_schedule") {
+            Some(table) => NoiseSchedule::from_table(table)?,
+            None => defaults.noise_schedule,
+        };
+        let seeding = match doc.get("seeding") {
+            Some(table) => SeedingStrategy::from_table(table)?,
+            None => defaults.seeding,
+        };
+        let evaluator = match doc.get("evaluator") {
+            Some(table) => evaluator_from_table(table)?,
+            None => defaults.evaluator,
+        };
+        let config = Self {
+            alpha: fx_field(&doc, "alpha", None, defaults.alpha)?,
+            beta: fx_field(&doc, "beta", None, defaults.beta)?,
+            noise_profile,
+            noise_schedule,
+            seeding,
+            evaluator,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Loads and validates a `dream_params.toml` file.
+    pub fn load(path: impl AsRef<Path>) -> CoreResult<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Self::from_toml_str(&text)
+    }
+
+    /// Checks coefficient ranges.
+    pub fn validate(&self) -> CoreResult<()> {
+        if !(0.0..=1.0).contains(&self.alpha) {
+            return Err(DreamError::Config(format!(
+                "alpha must lie in [0, 1], got {}",
+                self.alpha
+            )));
+        }
+        if !(-1.0..=1.0).contains(&self.beta) {
+            return Err(DreamError::Config(format!(
+                "beta must lie in [-1, 1], got {}",
+                self.beta
+            )));
+        }
+        self.noise_schedule.validate()?;
+        self.evaluator.validate()
+    }
+}
+
+fn evaluator_from_table(table: &JsonValue) -> CoreResult<EvaluatorConfig> {
+    let evaluator = match str_field(table, "kind", Some("evaluator"))?.unwrap_or("legacy") {
+        "legacy" => {
+            let d = LegacyEvaluator::default();
+            EvaluatorConfig::Legacy(LegacyEvaluator {
+                hsl_weight: fx_field(table, "hsl_weight", Some("evaluator"), d.hsl_weight)?,
+                profile_weight: fx_field(
+                    table,
+                    "profile_weight",
+                    Some("evaluator"),
+                    d.profile_weight,
+                )?,
+            })
+        }
+        "spectral" => {
+            let d = SpectralEvaluator::default();
+            EvaluatorConfig::Spectral(SpectralEvaluator {
+                hsl_weight: fx_field(table, "hsl_weight", Some("evaluator"), d.hsl_weight)?,
+                magnitude_weight: fx_field(
+                    table,
+                    "magnitude_weight",
+                    Some("evaluator"),
+                    d.magnitude_weight,
+                )?,
+                phase_weight: fx_field(table, "phase_weight", Some("evaluator"), d.phase_weight)?,
+            })
+        }
+        other => return Err(DreamError::Config(format!("unknown evaluator `{}`", other))),
+    };
+    evaluator.validate()?;
+    Ok(evaluator)
+}
+
+/// Dotted path of `key`, prefixed by its table when it has one.
+fn key_path(section: Option<&str>, key: &str) -> String {
+    match section {
+        Some(section) => format!("{}.{}", section, key),
+        None => key.to_string(),
+    }
+}
+
+fn str_field<'a>(
+    table: &'a JsonValue,
+    key: &str,
+    section: Option<&str>,
+) -> CoreResult<Option<&'a str>> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(value) => value.as_str().map(Some).ok_or_else(|| {
+            DreamError::Config(format!("{} must be a string", key_path(section, key)))
+        }),
+    }
+}
+
+fn fx_field(table: &JsonValue, key: &str, section: Option<&str>, default: Fx) -> CoreResult<Fx> {
+    match table.get(key) {
+        None => Ok(default),
+        Some(value) => value.as_f64().map(|v| v as Fx).ok_or_else(|| {
+            DreamError::Config(format!("{} must be a number", key_path(section, key)))
+        }),
+    }
+}
+
+fn u32_field(table: &JsonValue, key: &str, section: Option<&str>, default: u32) -> CoreResult<u32> {
+    match table.get(key) {
+        None => Ok(default),
+        Some(value) => value
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| {
+                DreamError::Config(format!(
+                    "{} must be a non-negative integer",
+                    key_path(section, key)
+                ))
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_params_match_defaults() {
+        let config = DreamConfig::from_toml_str(include_str!("config/dream_params.toml")).unwrap();
+        assert_eq!(config, DreamConfig::default());
+    }
+
+    #[test]
+    fn parses_alternate_schedules_and_strategies() {
+        let config = DreamConfig::from_toml_str(
+            "alpha = 0.8\nnoise_profile = \"blue\"\n[noise_schedule]\nkind = \"cosine\"\nmax = 0.3\nmin = 0.1\nperiod = 4\n[seeding]\nstrategy = \"tournament\"\nsize = 3\n",
+        )
+        .unwrap();
+        assert_eq!(config.noise_profile, NoiseProfile::BlueNoise);
+        assert_eq!(config.seeding, SeedingStrategy::Tournament { size: 3 });
+        assert_eq!(config.evaluator, EvaluatorConfig::default());
+        let schedule = config.noise_schedule;
+        assert!((schedule.level(0, 0.0, None) - 0.3).abs() <= 1e-6);
+        assert!((schedule.level(2, 0.0, None) - 0.2).abs() <= 1e-6);
+        assert!((schedule.level(4, 0.0, None) - 0.3).abs() <= 1e-6);
+    }
+
+    #[test]
+    fn adaptive_schedule_reacts_to_improvement() {
+        let schedule = NoiseSchedule::Adaptive {
+            initial: 0.1,
+            min: 0.05,
+            max: 0.2,
+            gain: 0.5,
+        };
+        let start = schedule.initial_level();
+        assert!((schedule.level(1, start, Some(0.1)) - 0.05).abs() <= 1e-6);
+        assert!((schedule.level(1, start, Some(0.0)) - 0.15).abs() <= 1e-6);
+        assert!((schedule.level(2, 0.18, Some(-0.1)) - 0.2).abs() <= 1e-6);
+    }
+
+    #[test]
+    fn rejects_invalid_parameters() {
+        assert!(DreamConfig::from_toml_str("alpha = 1.5").is_err());
+        assert!(DreamConfig::from_toml_str("noise_profile = \"pink\"").is_err());
+        assert!(DreamConfig::from_toml_str("[noise_schedule]\nkind = \"linear\"").is_err());
+        assert!(DreamConfig::from_toml_str("[seeding]\nstrategy = \"top_k\"\nk = 0").is_err());
+        assert!(DreamConfig::from_toml_str("[evaluator]\nkind = \"fourier\"").is_err());
+        assert!(
+            DreamConfig::from_toml_str("[evaluator]\nkind = \"spectral\"\nphase_weight = -1")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn errors_name_the_offending_key_path() {
+        let message = |text: &str| match DreamConfig::from_toml_str(text) {
+            Err(DreamError::Config(message)) => message,
+            other => panic!("expected a config error, got {:?}", other),
+        };
+        assert_eq!(message("alpha = \"high\""), "alpha must be a number");
+        assert_eq!(
+            message("noise_profile = 3"),
+            "noise_profile must be a string"
+        );
+        assert_eq!(
+            message("[seeding]\nstrategy = \"top_k\"\nk = -1"),
+            "seeding.k must be a non-negative integer"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+//! Applies continuity planner decisions to a running dream cycle.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/dream/spec.md`
+//! - `cognitive-research-hub/core/src/diagnostics/continuity/spec.md`
+//!
+//! | Action | Effect |
+//! | --- | --- |
+//! | Hold | nothing |
+//! | Reset | empties the pool |
+//! | Adjust | scales the noise schedule |
+//! | Relearn | restores the pool from the archive snapshot |
+
+use super::{DreamArchive, DreamConfig, SimpleDreamPool};
+use crate::{
+    diagnostics::continuity::planner::ActionExecutor,
+    error::{CoreResult, DreamError},
+    Fx,
+};
+
+/// [`ActionExecutor`] over a dream pool and its configuration.
+#[derive(Debug)]
+pub struct DreamActionExecutor<'a> {
+    pool: &'a mut SimpleDreamPool,
+    config: &'a mut DreamConfig,
+    archive: Option<&'a DreamArchive>,
+}
+
+impl<'a> DreamActionExecutor<'a> {
+    pub fn new(pool: &'a mut SimpleDreamPool, config: &'a mut DreamConfig) -> Self {
+        Self {
+            pool,
+            config,
+            archive: None,
+        }
+    }
+
+    /// Uses the snapshot stored in `archive` as the rollback checkpoint.
+    pub fn with_archive(mut self, archive: &'a DreamArchive) -> Self {
+        self.archive = Some(archive);
+        self
+    }
+}
+
+impl ActionExecutor for DreamActionExecutor<'_> {
+    fn reset(&mut self) -> CoreResult<()> {
+        self.pool.clear();
+        Ok(())
+    }
+
+    fn adjust(&mut self, factor: Fx) -> CoreResult<()> {
+        self.config.noise_schedule = self.config.noise_schedule.scaled(factor);
+        Ok(())
+    }
+
+    fn relearn(&mut self) -> CoreResult<()> {
+        let archive = self.archive.ok_or_else(|| {
+            DreamError::Dream("relearn needs an archive snapshot to roll back to".to_string())
+        })?;
+        *self.pool = archive.restore()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::continuity::planner::{execute_action, ActionKind, TemporalAction};
+    use crate::dream::{add_dream_to_pool, DreamEntry, NoiseSchedule};
+    use crate::tensor::{ChromaticTensor, Shape2D};
+
+    fn pool() -> SimpleDreamPool {
+        let mut pool = SimpleDreamPool::new(4, 0.0);
+        let shape = Shape2D::new(2, 2);
+        for score in [0.4, 0.7] {
+            let epoch = pool.next_epoch();
+            let tensor = ChromaticTensor::new(shape, vec![score; shape.rgb_len()], None);
+            add_dream_to_pool(&mut pool, DreamEntry::new(tensor, epoch, score));
+        }
+        pool
+    }
+
+    #[test]
+    fn actions_reset_pool_and_scale_noise() {
+        let mut pool = pool();
+        let mut config = DreamConfig {
+            noise_schedule: NoiseSchedule::Constant { level: 0.2 },
+            ..DreamConfig::default()
+        };
+        let mut executor = DreamActionExecutor::new(&mut pool, &mut config);
+        execute_action(
+            &mut executor,
+            &TemporalAction::new(ActionKind::Adjust, 1.0, 0.5),
+        )
+        .unwrap();
+        execute_action(
+            &mut executor,
+            &TemporalAction::new(ActionKind::Reset, 1.0, 1.0),
+        )
+        .unwrap();
+        assert!(execute_action(
+            &mut executor,
+            &TemporalAction::new(ActionKind::Relearn, 1.0, 1.0)
+        )
+        .is_err());
+        assert!(pool.is_empty());
+        assert_eq!(pool.next_epoch(), 2);
+        assert_eq!(
+            config.noise_schedule,
+            NoiseSchedule::Constant { level: 0.1 }
+        );
+    }
+
+    #[test]
+    fn relearn_restores_the_archived_pool() {
+        let dir =
+            std::env::temp_dir().join(format!("dream_control_relearn_{}", std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        let archive = DreamArchive::open(&dir).unwrap();
+        let mut pool = pool();
+        archive.snapshot(&pool).unwrap();
+        pool.clear();
+        let mut config = DreamConfig::default();
+        let mut executor = DreamActionExecutor::new(&mut pool, &mut config).with_archive(&archive);
+        execute_action(
+            &mut executor,
+            &TemporalAction::new(ActionKind::Relearn, 1.0, 1.0),
+        )
+        .unwrap();
+        assert_eq!(pool.len(), 2);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
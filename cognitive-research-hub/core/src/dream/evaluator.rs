@@ -0,0 +1,273 @@
+//! Pluggable dream scoring, including FFT-based spectral coherence.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/dream/spec.md`
+//!
+//! Scores follow `score(D') = w₁·(1 − ΔE_HSL) + w₂·C_spec`, normalised by the
+//! weight sum. [`LegacyEvaluator`] keeps the original column-profile cosine
+//! as `C_spec`; [`SpectralEvaluator`] takes the 2D FFT of every RGB channel
+//! and combines magnitude correlation with energy-weighted phase coherence.
+
+use super::{clamp_unit, frequency_profile, hsl_distance_field, spectral_similarity};
+use crate::{
+    error::{CoreResult, DreamError},
+    tensor::{channel_spectrum, ChromaticTensor},
+    Fx,
+};
+
+/// Scores a dream against its target; higher is better.
+pub trait DreamEvaluator {
+    fn evaluate(&self, dream: &ChromaticTensor, target: &ChromaticTensor) -> Fx;
+}
+
+impl<T: DreamEvaluator + ?Sized> DreamEvaluator for &T {
+    fn evaluate(&self, dream: &ChromaticTensor, target: &ChromaticTensor) -> Fx {
+        (**self).evaluate(dream, target)
+    }
+}
+
+fn weighted(parts: &[(Fx, Fx)]) -> Fx {
+    let total: Fx = parts.iter().map(|(w, _)| w).sum();
+    if total <= Fx::EPSILON {
+        return 0.0;
+    }
+    parts.iter().map(|(w, v)| w * v).sum::<Fx>() / total
+}
+
+fn check_weights(weights: &[(&str, Fx)]) -> CoreResult<()> {
+    for &(name, weight) in weights {
+        if !(weight.is_finite() && weight >= 0.0) {
+            return Err(DreamError::Config(format!(
+                "evaluator.{} must be a non-negative number, got {}",
+                name, weight
+            )));
+        }
+    }
+    if weights.iter().map(|(_, w)| w).sum::<Fx>() <= Fx::EPSILON {
+        return Err(DreamError::Config(
+            "evaluator weights must not all be zero".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Original scorer: ΔHSL plus cosine similarity of column luminance profiles.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LegacyEvaluator {
+    pub hsl_weight: Fx,
+    pub profile_weight: Fx,
+}
+
+impl Default for LegacyEvaluator {
+    fn default() -> Self {
+        Self {
+            hsl_weight: 0.6,
+            profile_weight: 0.4,
+        }
+    }
+}
+
+impl LegacyEvaluator {
+    /// Rejects negative or all-zero weights.
+    pub fn validate(&self) -> CoreResult<()> {
+        check_weights(&[
+            ("hsl_weight", self.hsl_weight),
+            ("profile_weight", self.profile_weight),
+        ])
+    }
+}
+
+impl DreamEvaluator for LegacyEvaluator {
+    fn evaluate(&self, dream: &ChromaticTensor, target: &ChromaticTensor) -> Fx {
+        let hsl_score = clamp_unit(1.0 - hsl_distance_field(dream, target));
+        let spectral_score =
+            spectral_similarity(&frequency_profile(dream), &frequency_profile(target));
+        weighted(&[
+            (self.hsl_weight, hsl_score),
+            (self.profile_weight, spectral_score),
+        ])
+    }
+}
+
+/// Channel-averaged spectral agreement between two tensors, each term in `[0, 1]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpectralCoherence {
+    /// Cosine similarity of the 2D magnitude spectra.
+    pub magnitude: Fx,
+    /// `(1 + Σ|A||B|cos Δφ / Σ|A||B|) / 2`, so aligned phases score 1.
+    pub phase: Fx,
+}
+
+/// Compares the 2D FFTs of every RGB channel of `a` and `b`.
+pub fn spectral_coherence(a: &ChromaticTensor, b: &ChromaticTensor) -> SpectralCoherence {
+    assert_eq!(a.shape, b.shape, "spectral coherence needs equal shapes");
+    let mut magnitude = 0.0;
+    let mut phase = 0.0;
+    for channel in 0..3 {
+        let spec_a = channel_spectrum(a, channel);
+        let spec_b = channel_spectrum(b, channel);
+        let mut dot = 0.0;
+        let mut norm_a = 0.0;
+        let mut norm_b = 0.0;
+        let mut cross_re = 0.0;
+        for (za, zb) in spec_a.iter().zip(&spec_b) {
+            let (ma, mb) = (za.norm(), zb.norm());
+            dot += ma * mb;
+            norm_a += ma * ma;
+            norm_b += mb * mb;
+            // Re(A · conj(B)) = |A||B| cos(φA − φB).
+            cross_re += za.re * zb.re + za.im * zb.im;
+        }
+        let (silent_a, silent_b) = (norm_a <= Fx::EPSILON, norm_b <= Fx::EPSILON);
+        if silent_a || silent_b {
+            let agree = if silent_a && silent_b { 1.0 } else { 0.0 };
+            magnitude += agree;
+            phase += agree;
+            continue;
+        }
+        magnitude += clamp_unit(dot / (norm_a.sqrt() * norm_b.sqrt()));
+        phase += clamp_unit(0.5 * (1.0 + cross_re / dot.max(Fx::EPSILON)));
+    }
+    SpectralCoherence {
+        magnitude: magnitude / 3.0,
+        phase: phase / 3.0,
+    }
+}
+
+/// Spec scorer with `C_spec` taken from 2D FFT magnitude and phase agreement.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpectralEvaluator {
+    pub hsl_weight: Fx,
+    pub magnitude_weight: Fx,
+    pub phase_weight: Fx,
+}
+
+impl Default for SpectralEvaluator {
+    fn default() -> Self {
+        Self {
+            hsl_weight: 0.6,
+            magnitude_weight: 0.2,
+            phase_weight: 0.2,
+        }
+    }
+}
+
+impl SpectralEvaluator {
+    /// Rejects negative or all-zero weights.
+    pub fn validate(&self) -> CoreResult<()> {
+        check_weights(&[
+            ("hsl_weight", self.hsl_weight),
+            ("magnitude_weight", self.magnitude_weight),
+            ("phase_weight", self.phase_weight),
+        ])
+    }
+}
+
+impl DreamEvaluator for SpectralEvaluator {
+    fn evaluate(&self, dream: &ChromaticTensor, target: &ChromaticTensor) -> Fx {
+        let hsl_score = clamp_unit(1.0 - hsl_distance_field(dream, target));
+        let coherence = spectral_coherence(dream, target);
+        weighted(&[
+            (self.hsl_weight, hsl_score),
+            (self.magnitude_weight, coherence.magnitude),
+            (self.phase_weight, coherence.phase),
+        ])
+    }
+}
+
+/// Evaluator selected by [`super::DreamConfig`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EvaluatorConfig {
+    Legacy(LegacyEvaluator),
+    Spectral(SpectralEvaluator),
+}
+
+impl Default for EvaluatorConfig {
+    fn default() -> Self {
+        EvaluatorConfig::Legacy(LegacyEvaluator::default())
+    }
+}
+
+impl EvaluatorConfig {
+    /// Returns the configuration name of the evaluator.
+    pub fn name(&self) -> &'static str {
+        match self {
+            EvaluatorConfig::Legacy(_) => "legacy",
+            EvaluatorConfig::Spectral(_) => "spectral",
+        }
+    }
+
+    /// Rejects negative or all-zero weights.
+    pub fn validate(&self) -> CoreResult<()> {
+        match self {
+            EvaluatorConfig::Legacy(evaluator) => evaluator.validate(),
+            EvaluatorConfig::Spectral(evaluator) => evaluator.validate(),
+        }
+    }
+}
+
+impl DreamEvaluator for EvaluatorConfig {
+    fn evaluate(&self, dream: &ChromaticTensor, target: &ChromaticTensor) -> Fx {
+        match self {
+            EvaluatorConfig::Legacy(evaluator) => evaluator.evaluate(dream, target),
+            EvaluatorConfig::Spectral(evaluator) => evaluator.evaluate(dream, target),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::Shape2D;
+
+    fn stripes(shape: Shape2D, phase: usize) -> ChromaticTensor {
+        let mut rgb = Vec::with_capacity(shape.rgb_len());
+        for _row in 0..shape.h {
+            for col in 0..shape.w {
+                let v = [0.8, 0.2][(col + phase) % 2];
+                rgb.extend_from_slice(&[v, 0.5, 1.0 - v]);
+            }
+        }
+        ChromaticTensor::new(shape, rgb, None)
+    }
+
+    #[test]
+    fn phase_term_detects_shifted_patterns() {
+        let shape = Shape2D::new(4, 4);
+        let a = stripes(shape, 0);
+        let shifted = stripes(shape, 1);
+        let same = spectral_coherence(&a, &a);
+        assert!((same.magnitude - 1.0).abs() <= 1e-5);
+        assert!((same.phase - 1.0).abs() <= 1e-5);
+        let moved = spectral_coherence(&a, &shifted);
+        // Identical magnitudes, but the stripe harmonic is in antiphase.
+        assert!((moved.magnitude - 1.0).abs() <= 1e-5);
+        assert!(moved.phase < same.phase - 0.05);
+        let spectral = SpectralEvaluator::default();
+        assert!(spectral.evaluate(&a, &a) > spectral.evaluate(&shifted, &a));
+    }
+
+    #[test]
+    fn legacy_weights_match_original_formula() {
+        let shape = Shape2D::new(2, 2);
+        let a = stripes(shape, 0);
+        let b = stripes(shape, 1);
+        let legacy = LegacyEvaluator::default();
+        let hsl = clamp_unit(1.0 - hsl_distance_field(&a, &b));
+        let profile = spectral_similarity(&frequency_profile(&a), &frequency_profile(&b));
+        assert!((legacy.evaluate(&a, &b) - (0.6 * hsl + 0.4 * profile)).abs() <= 1e-6);
+        let hsl_only = LegacyEvaluator {
+            hsl_weight: 2.0,
+            profile_weight: 0.0,
+        };
+        assert!((hsl_only.evaluate(&a, &b) - hsl).abs() <= 1e-6);
+        assert!(LegacyEvaluator {
+            hsl_weight: 0.0,
+            profile_weight: 0.0
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -0,0 +1,216 @@
+//! Eviction policies and diversity reporting for the dream pool.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/dream/spec.md`
+//!
+//! All comparisons use `f32::total_cmp` with the epoch as a secondary key, so
+//! the chosen victim never depends on entry order. Non-finite scores are
+//! rejected before a policy is consulted.
+
+use std::cmp::Ordering;
+
+use super::{hsl_distance_field, DreamEntry};
+use crate::{bridge::hue_to_bin_weights, bridge::mean_hsl, tensor::ChromaticTensor, Fx};
+
+/// Rule deciding which entry leaves a full pool.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum EvictionPolicy {
+    /// Replace the lowest score, then the oldest epoch (spec default).
+    #[default]
+    ScoreThenOldest,
+    /// Rank by `score + weight × novelty`, where novelty is the ΔHSL distance
+    /// to the nearest other entry; near-duplicates are evicted first.
+    NoveltyWeighted { weight: Fx },
+    /// MAP-Elites style: one elite per dominant hue bin where possible.
+    HueNiches,
+}
+
+/// Outcome of offering a candidate to a pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Placement {
+    Append,
+    Replace(usize),
+    Reject,
+}
+
+/// Diversity summary reported by [`super::SimpleDreamPool::diversity`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PoolDiversity {
+    /// Mean ΔHSL distance over all entry pairs.
+    pub mean_pairwise_delta: Fx,
+    /// Smallest ΔHSL distance between any two entries.
+    pub min_pairwise_delta: Fx,
+    /// Number of distinct dominant hue bins covered.
+    pub niche_count: usize,
+}
+
+/// Returns the hue bin (of `HUE_CATEGORIES`) holding the tensor's mean hue.
+pub fn hue_niche(tensor: &ChromaticTensor) -> usize {
+    let (hue, _, _) = mean_hsl(tensor);
+    let (idx_a, w_a, idx_b, w_b) = hue_to_bin_weights(hue);
+    if w_b > w_a {
+        idx_b
+    } else {
+        idx_a
+    }
+}
+
+/// ΔHSL distance between two entries; tensors of different shape count as maximally distant.
+pub(super) fn entry_distance(a: &ChromaticTensor, b: &ChromaticTensor) -> Fx {
+    if a.shape != b.shape {
+        return 1.0;
+    }
+    hsl_distance_field(a, b)
+}
+
+/// Orders entries from weakest to strongest: lower score, then older epoch.
+fn weakest_first(a: &DreamEntry, b: &DreamEntry) -> Ordering {
+    a.score.total_cmp(&b.score).then(a.epoch.cmp(&b.epoch))
+}
+
+/// True when `candidate` should displace `incumbent`: a higher score, or an
+/// equal score with a newer epoch, so ties evict the oldest entry.
+fn outranks(candidate: &DreamEntry, incumbent: &DreamEntry) -> bool {
+    weakest_first(incumbent, candidate) == Ordering::Less
+}
+
+fn weakest_index<'a>(entries: impl Iterator<Item = (usize, &'a DreamEntry)>) -> Option<usize> {
+    entries
+        .min_by(|a, b| weakest_first(a.1, b.1).then(a.0.cmp(&b.0)))
+        .map(|(idx, _)| idx)
+}
+
+/// Chooses where `candidate` goes in a pool holding `entries` with `max_size` slots.
+pub(super) fn place(
+    entries: &[DreamEntry],
+    max_size: usize,
+    candidate: &DreamEntry,
+    policy: EvictionPolicy,
+) -> Placement {
+    if entries.len() < max_size {
+        return Placement::Append;
+    }
+    match policy {
+        EvictionPolicy::ScoreThenOldest => place_by_score(entries, candidate),
+        EvictionPolicy::NoveltyWeighted { weight } => place_by_novelty(entries, candidate, weight),
+        EvictionPolicy::HueNiches => place_by_niche(entries, candidate),
+    }
+}
+
+fn place_by_score(entries: &[DreamEntry], candidate: &DreamEntry) -> Placement {
+    match weakest_index(entries.iter().enumerate()) {
+        Some(idx) if outranks(candidate, &entries[idx]) => Placement::Replace(idx),
+        _ => Placement::Reject,
+    }
+}
+
+fn place_by_novelty(entries: &[DreamEntry], candidate: &DreamEntry, weight: Fx) -> Placement {
+    // Index `entries.len()` stands for the candidate.
+    let count = entries.len() + 1;
+    let tensor = |idx: usize| {
+        if idx == entries.len() {
+            &candidate.tensor
+        } else {
+            &entries[idx].tensor
+        }
+    };
+    let member = |idx: usize| {
+        if idx == entries.len() {
+            candidate
+        } else {
+            &entries[idx]
+        }
+    };
+    let mut nearest = vec![Fx::INFINITY; count];
+    for i in 0..count {
+        for j in (i + 1)..count {
+            let d = entry_distance(tensor(i), tensor(j));
+            nearest[i] = nearest[i].min(d);
+            nearest[j] = nearest[j].min(d);
+        }
+    }
+    let fitness = |idx: usize| member(idx).score + weight * nearest[idx].min(1.0);
+    let victim = (0..count)
+        .min_by(|&a, &b| {
+            fitness(a)
+                .total_cmp(&fitness(b))
+                .then(member(a).epoch.cmp(&member(b).epoch))
+                .then(a.cmp(&b))
+        })
+        .unwrap_or(entries.len());
+    if victim == entries.len() {
+        Placement::Reject
+    } else {
+        Placement::Replace(victim)
+    }
+}
+
+fn place_by_niche(entries: &[DreamEntry], candidate: &DreamEntry) -> Placement {
+    let niche = hue_niche(&candidate.tensor);
+    let niches: Vec<usize> = entries
+        .iter()
+        .map(|entry| hue_niche(&entry.tensor))
+        .collect();
+    let same_niche = weakest_index(
+        entries
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| niches[*idx] == niche),
+    );
+    if let Some(idx) = same_niche {
+        return if outranks(candidate, &entries[idx]) {
+            Placement::Replace(idx)
+        } else {
+            Placement::Reject
+        };
+    }
+    // New niche: evict the weakest member of the most crowded niche.
+    let mut counts = [0usize; crate::HUE_CATEGORIES];
+    for &n in &niches {
+        counts[n] += 1;
+    }
+    let crowded = counts.iter().copied().max().unwrap_or(0);
+    if crowded <= 1 {
+        return place_by_score(entries, candidate);
+    }
+    let victim = weakest_index(
+        entries
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| counts[niches[*idx]] == crowded),
+    );
+    victim.map_or(Placement::Reject, Placement::Replace)
+}
+
+/// Computes pairwise ΔHSL spread and hue-niche coverage.
+pub(super) fn diversity(entries: &[DreamEntry]) -> PoolDiversity {
+    let mut niches = [false; crate::HUE_CATEGORIES];
+    for entry in entries {
+        niches[hue_niche(&entry.tensor)] = true;
+    }
+    let niche_count = niches.iter().filter(|&&covered| covered).count();
+    let mut sum = 0.0;
+    let mut pairs = 0usize;
+    let mut min = Fx::INFINITY;
+    for i in 0..entries.len() {
+        for j in (i + 1)..entries.len() {
+            let d = entry_distance(&entries[i].tensor, &entries[j].tensor);
+            sum += d;
+            pairs += 1;
+            min = min.min(d);
+        }
+    }
+    if pairs == 0 {
+        return PoolDiversity {
+            niche_count,
+            ..PoolDiversity::default()
+        };
+    }
+    PoolDiversity {
+        mean_pairwise_delta: sum / pairs as Fx,
+        min_pairwise_delta: min,
+        niche_count,
+    }
+}
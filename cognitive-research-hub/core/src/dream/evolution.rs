@@ -0,0 +1,532 @@
+//! Genetic-style search operators over the dream pool.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/dream/spec.md`
+//!
+//! A generation keeps the `elite_count` best entries untouched and replaces the
+//! rest of the pool with offspring bred from selected parents through spatial
+//! crossover and hue/saturation mutation, each scored by the configured
+//! [`EvaluatorConfig`]. Every random decision is drawn from the caller's RNG so
+//! generations are reproducible.
+
+use std::cmp::Ordering;
+
+use super::{
+    add_dream_to_pool, clamp_unit, coherence_map_from_rgb, DreamEntry, DreamEvaluator,
+    EvaluatorConfig, SimpleDreamPool,
+};
+use crate::{
+    error::{CoreResult, DreamError},
+    tensor::{hsl_to_rgb, mask_inject, normalize_hue, rgb_to_hsl, ChromaticTensor, Shape2D},
+    utils::{ChaCha8Rng, RngCore},
+    Fx,
+};
+
+/// Spatial layout of the mask used to combine two parents.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CrossoverMask {
+    /// Each cell independently inherits from either parent.
+    #[default]
+    Uniform,
+    /// A horizontal or vertical cut splits the tensor into two regions.
+    HalfPlane,
+    /// A random rectangle is taken from the second parent.
+    Block,
+}
+
+/// Parent selection scheme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Selection {
+    /// Best of `size` entries drawn with replacement.
+    Tournament { size: usize },
+    /// Roulette wheel proportional to (non-negative) score.
+    FitnessProportional,
+}
+
+impl Default for Selection {
+    fn default() -> Self {
+        Selection::Tournament { size: 3 }
+    }
+}
+
+/// Parameters of one evolutionary generation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EvolutionConfig {
+    /// Entries copied unchanged into the next generation.
+    pub elite_count: usize,
+    /// Parent selection scheme.
+    pub selection: Selection,
+    /// Probability that a child is produced by crossover rather than cloning.
+    pub crossover_rate: Fx,
+    /// Mask layout used for crossover.
+    pub crossover_mask: CrossoverMask,
+    /// Independent probability of applying each mutation operator.
+    pub mutation_rate: Fx,
+    /// Largest hue rotation in radians.
+    pub max_hue_rotation: Fx,
+    /// Largest relative saturation change.
+    pub max_saturation_scale: Fx,
+    /// Scorer applied to every offspring.
+    pub evaluator: EvaluatorConfig,
+}
+
+impl Default for EvolutionConfig {
+    fn default() -> Self {
+        Self {
+            elite_count: 1,
+            selection: Selection::default(),
+            crossover_rate: 0.7,
+            crossover_mask: CrossoverMask::default(),
+            mutation_rate: 0.3,
+            max_hue_rotation: 0.35,
+            max_saturation_scale: 0.2,
+            evaluator: EvaluatorConfig::default(),
+        }
+    }
+}
+
+/// Draws a crossover mask with one weight per cell (`1` selects the second parent).
+pub fn crossover_mask(shape: Shape2D, kind: CrossoverMask, rng: &mut impl RngCore) -> Vec<Fx> {
+    let cells = shape.cell_count();
+    if cells == 0 {
+        return Vec::new();
+    }
+    match kind {
+        CrossoverMask::Uniform => (0..cells)
+            .map(|_| if rng.next_u32() & 1 == 1 { 1.0 } else { 0.0 })
+            .collect(),
+        CrossoverMask::HalfPlane => {
+            let vertical = rng.next_u32() & 1 == 1;
+            let span = if vertical { shape.w } else { shape.h };
+            let cut = rng.next_below(span as u32 + 1) as usize;
+            let mut mask = Vec::with_capacity(cells);
+            for row in 0..shape.h {
+                for col in 0..shape.w {
+                    let coord = if vertical { col } else { row };
+                    mask.push(if coord >= cut { 1.0 } else { 0.0 });
+                }
+            }
+            mask
+        }
+        CrossoverMask::Block => {
+            let r0 = rng.next_below(shape.h as u32) as usize;
+            let c0 = rng.next_below(shape.w as u32) as usize;
+            let r1 = r0 + 1 + rng.next_below((shape.h - r0) as u32) as usize;
+            let c1 = c0 + 1 + rng.next_below((shape.w - c0) as u32) as usize;
+            let mut mask = Vec::with_capacity(cells);
+            for row in 0..shape.h {
+                for col in 0..shape.w {
+                    let inside = (r0..r1).contains(&row) && (c0..c1).contains(&col);
+                    mask.push(if inside { 1.0 } else { 0.0 });
+                }
+            }
+            mask
+        }
+    }
+}
+
+fn with_coherence(shape: Shape2D, rgb: Vec<Fx>) -> ChromaticTensor {
+    let coherence = coherence_map_from_rgb(shape, &rgb);
+    ChromaticTensor::new(shape, rgb, Some(coherence))
+}
+
+/// Combines two parents cell-wise through [`mask_inject`].
+///
+/// Parents of different shapes have no cell correspondence and are reported
+/// as [`DreamError::Validation`] before any RNG draw.
+pub fn crossover(
+    a: &ChromaticTensor,
+    b: &ChromaticTensor,
+    kind: CrossoverMask,
+    rng: &mut impl RngCore,
+) -> CoreResult<ChromaticTensor> {
+    if a.shape != b.shape {
+        return Err(DreamError::Validation(format!(
+            "crossover parents differ in shape: {:?} vs {:?}",
+            a.shape, b.shape
+        )));
+    }
+    let mask = crossover_mask(a.shape, kind, rng);
+    let mut out = a.clone();
+    mask_inject(&mut out, a, b, &mask);
+    Ok(with_coherence(a.shape, out.rgb))
+}
+
+fn map_hsl(tensor: &ChromaticTensor, f: impl Fn(Fx, Fx, Fx) -> (Fx, Fx, Fx)) -> ChromaticTensor {
+    let mut rgb = Vec::with_capacity(tensor.rgb.len());
+    for chunk in tensor.rgb.chunks(3) {
+        let (h, s, l) = rgb_to_hsl(chunk[0], chunk[1], chunk[2]);
+        let (h, s, l) = f(h, s, l);
+        let (r, g, b) = hsl_to_rgb(h, s, l);
+        rgb.extend_from_slice(&[clamp_unit(r), clamp_unit(g), clamp_unit(b)]);
+    }
+    with_coherence(tensor.shape, rgb)
+}
+
+/// Rotates every hue by one RNG-drawn angle in `[-max_rotation, max_rotation)`.
+pub fn mutate_hue(
+    tensor: &ChromaticTensor,
+    max_rotation: Fx,
+    rng: &mut impl RngCore,
+) -> ChromaticTensor {
+    let delta = rng.next_signed() * max_rotation;
+    map_hsl(tensor, |h, s, l| (normalize_hue(h + delta), s, l))
+}
+
+/// Scales every saturation by `1 + δ` with `δ` drawn from `[-max_scale, max_scale)`.
+pub fn mutate_saturation(
+    tensor: &ChromaticTensor,
+    max_scale: Fx,
+    rng: &mut impl RngCore,
+) -> ChromaticTensor {
+    let factor = 1.0 + rng.next_signed() * max_scale;
+    map_hsl(tensor, |h, s, l| (h, clamp_unit(s * factor), l))
+}
+
+/// Returns the index of the best of `size` entries drawn with replacement.
+pub fn tournament_select(
+    entries: &[DreamEntry],
+    size: usize,
+    rng: &mut impl RngCore,
+) -> Option<usize> {
+    if entries.is_empty() {
+        return None;
+    }
+    let len = entries.len() as u32;
+    let mut winner = rng.next_below(len) as usize;
+    for _ in 1..size.max(1) {
+        let challenger = rng.next_below(len) as usize;
+        if entries[challenger].score > entries[winner].score {
+            winner = challenger;
+        }
+    }
+    Some(winner)
+}
+
+/// Returns an index drawn with probability proportional to non-negative score.
+///
+/// Falls back to a uniform draw when every score is zero.
+pub fn fitness_proportional_select(
+    entries: &[DreamEntry],
+    rng: &mut impl RngCore,
+) -> Option<usize> {
+    if entries.is_empty() {
+        return None;
+    }
+    let total = entries
+        .iter()
+        .fold(0.0, |acc, entry| acc + entry.score.max(0.0));
+    if total <= 0.0 {
+        return Some(rng.next_below(entries.len() as u32) as usize);
+    }
+    let mut ticket = rng.next_f32() * total;
+    for (idx, entry) in entries.iter().enumerate() {
+        ticket -= entry.score.max(0.0);
+        if ticket < 0.0 {
+            return Some(idx);
+        }
+    }
+    entries.iter().rposition(|entry| entry.score > 0.0)
+}
+
+fn select(entries: &[DreamEntry], selection: Selection, rng: &mut impl RngCore) -> Option<usize> {
+    match selection {
+        Selection::Tournament { size } => tournament_select(entries, size, rng),
+        Selection::FitnessProportional => fitness_proportional_select(entries, rng),
+    }
+}
+
+/// Orders entries by descending score, breaking ties by ascending epoch.
+fn rank_order(a: &DreamEntry, b: &DreamEntry) -> Ordering {
+    b.score.total_cmp(&a.score).then(a.epoch.cmp(&b.epoch))
+}
+
+/// Breeds one generation in place and returns the number of offspring admitted.
+///
+/// The best `elite_count` entries survive unchanged; the remaining slots are
+/// offered to the offspring, then to the previous non-elite entries in rank
+/// order if offspring fall short. Every entry goes back through
+/// [`add_dream_to_pool`], so the coherence threshold, non-finite rejection,
+/// capacity, and eviction policy all apply. Only entries shaped like `target`
+/// are bred, since offspring must be scored against it; others can still
+/// survive as elites or fill leftover slots.
+pub fn evolve_generation(
+    pool: &mut SimpleDreamPool,
+    target: &ChromaticTensor,
+    config: &EvolutionConfig,
+    rng: &mut impl RngCore,
+) -> usize {
+    if pool.is_empty() {
+        return 0;
+    }
+    let mut ranked = pool.entries().to_vec();
+    ranked.sort_by(rank_order);
+    let elite_count = config.elite_count.min(pool.max_size).min(ranked.len());
+    let slots = pool.max_size - elite_count;
+    let parents: Vec<DreamEntry> = ranked
+        .iter()
+        .filter(|entry| entry.tensor.shape == target.shape)
+        .cloned()
+        .collect();
+    let breeding_slots = if parents.is_empty() { 0 } else { slots };
+    let mut offspring = Vec::with_capacity(breeding_slots);
+    for _ in 0..breeding_slots {
+        let first = select(&parents, config.selection, rng).unwrap_or(0);
+        let second = select(&parents, config.selection, rng).unwrap_or(0);
+        let mut child = if rng.next_f32() < config.crossover_rate {
+            crossover(
+                &parents[first].tensor,
+                &parents[second].tensor,
+                config.crossover_mask,
+                rng,
+            )
+            // Unreachable while parents share the target's shape.
+            .unwrap_or_else(|_| parents[first].tensor.clone())
+        } else {
+            parents[first].tensor.clone()
+        };
+        if rng.next_f32() < config.mutation_rate {
+            child = mutate_hue(&child, config.max_hue_rotation, rng);
+        }
+        if rng.next_f32() < config.mutation_rate {
+            child = mutate_saturation(&child, config.max_saturation_scale, rng);
+        }
+        let score = config.evaluator.evaluate(&child, target);
+        offspring.push(DreamEntry::new(child, pool.next_epoch(), score));
+    }
+
+    let survivors = ranked.split_off(elite_count);
+    pool.clear();
+    for elite in ranked {
+        add_dream_to_pool(pool, elite);
+    }
+    let mut admitted = 0;
+    for child in offspring {
+        if add_dream_to_pool(pool, child) {
+            admitted += 1;
+        }
+    }
+    for entry in survivors {
+        if pool.len() >= pool.max_size {
+            break;
+        }
+        add_dream_to_pool(pool, entry);
+    }
+    admitted
+}
+
+/// Runs `generations` evolutionary generations against `target`.
+///
+/// Like [`super::dream_cycle`], each generation draws from `rng.split(e)`,
+/// where `e` is the first epoch it assigns (the pool's epoch cursor).
+pub fn evolve_cycle(
+    target: &ChromaticTensor,
+    pool: &mut SimpleDreamPool,
+    generations: u32,
+    config: &EvolutionConfig,
+    rng: &mut ChaCha8Rng,
+) {
+    for _ in 0..generations {
+        let mut generation_rng = rng.split(pool.epoch_cursor as u64);
+        evolve_generation(pool, target, config, &mut generation_rng);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dream::{add_dream_to_pool, dream_cycle, SpectralEvaluator};
+
+    fn gradient_tensor(shape: Shape2D, offset: Fx) -> ChromaticTensor {
+        let mut rgb = Vec::with_capacity(shape.rgb_len());
+        for idx in 0..shape.cell_count() {
+            let t = idx as Fx / shape.cell_count().max(1) as Fx;
+            rgb.extend_from_slice(&[clamp_unit(t + offset), 0.4, clamp_unit(0.8 - t)]);
+        }
+        ChromaticTensor::new(shape, rgb, None)
+    }
+
+    #[test]
+    fn crossover_takes_cells_from_both_parents() {
+        let shape = Shape2D::new(4, 4);
+        let a = ChromaticTensor::new(shape, vec![0.0; shape.rgb_len()], None);
+        let b = ChromaticTensor::new(shape, vec![1.0; shape.rgb_len()], None);
+        for kind in [
+            CrossoverMask::Uniform,
+            CrossoverMask::HalfPlane,
+            CrossoverMask::Block,
+        ] {
+            let mut rng = ChaCha8Rng::seed_from_u64(12);
+            let mask = crossover_mask(shape, kind, &mut rng.clone());
+            let child = crossover(&a, &b, kind, &mut rng).unwrap();
+            for (cell, weight) in mask.iter().enumerate() {
+                assert_eq!(child.rgb[cell * 3], *weight);
+            }
+        }
+        let block = crossover_mask(
+            shape,
+            CrossoverMask::Block,
+            &mut ChaCha8Rng::seed_from_u64(1),
+        );
+        assert!(block.contains(&1.0));
+    }
+
+    #[test]
+    fn mutations_preserve_range_and_change_colour() {
+        let shape = Shape2D::new(2, 3);
+        let tensor = gradient_tensor(shape, 0.1);
+        let mut rng = ChaCha8Rng::seed_from_u64(8);
+        let hue = mutate_hue(&tensor, 1.0, &mut rng);
+        let sat = mutate_saturation(&tensor, 0.5, &mut rng);
+        for mutated in [&hue, &sat] {
+            assert!(mutated.rgb.iter().all(|&v| (0.0..=1.0).contains(&v)));
+            assert_ne!(mutated.rgb, tensor.rgb);
+            assert_eq!(mutated.coh.as_ref().unwrap().len(), shape.cell_count());
+        }
+    }
+
+    #[test]
+    fn selection_prefers_fitter_entries() {
+        let shape = Shape2D::new(1, 1);
+        let entries: Vec<DreamEntry> = [0.0, 0.1, 0.9]
+            .iter()
+            .enumerate()
+            .map(|(epoch, &score)| {
+                let tensor = ChromaticTensor::new(shape, vec![0.5; 3], Some(vec![1.0]));
+                DreamEntry::new(tensor, epoch as u32, score)
+            })
+            .collect();
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        let mut tournament_wins = 0;
+        let mut roulette_wins = 0;
+        for _ in 0..200 {
+            if tournament_select(&entries, 3, &mut rng) == Some(2) {
+                tournament_wins += 1;
+            }
+            let pick = fitness_proportional_select(&entries, &mut rng).unwrap();
+            assert_ne!(pick, 0, "zero-score entry must never be drawn");
+            if pick == 2 {
+                roulette_wins += 1;
+            }
+        }
+        assert!(tournament_wins > 100);
+        assert!(roulette_wins > 150);
+    }
+
+    #[test]
+    fn evolution_keeps_elites_and_is_reproducible() {
+        let shape = Shape2D::new(3, 3);
+        let target = gradient_tensor(shape, 0.2);
+        let run = || {
+            let config = EvolutionConfig {
+                elite_count: 2,
+                ..EvolutionConfig::default()
+            };
+            let mut pool = SimpleDreamPool::new(6, 0.0);
+            let mut rng = ChaCha8Rng::seed_from_u64(30);
+            dream_cycle(&target, &mut pool, 6, &mut rng);
+            let seeded = gradient_tensor(shape, 0.0);
+            let score = config.evaluator.evaluate(&seeded, &target);
+            let epoch = pool.next_epoch();
+            add_dream_to_pool(&mut pool, DreamEntry::new(seeded, epoch, score));
+            let best_before = pool.best_entry().unwrap().score;
+            evolve_cycle(&target, &mut pool, 4, &config, &mut rng);
+            assert!(pool.best_entry().unwrap().score >= best_before);
+            assert_eq!(pool.len(), 6);
+            pool.entries()
+                .iter()
+                .map(|entry| (entry.epoch, entry.score))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn offspring_below_the_pool_threshold_are_refused() {
+        let shape = Shape2D::new(2, 2);
+        let mut pool = SimpleDreamPool::new(4, 0.999);
+        for offset in [0.0, 0.1, 0.2] {
+            let mut tensor = gradient_tensor(shape, offset);
+            tensor.coh = Some(vec![1.0; shape.cell_count()]);
+            let epoch = pool.next_epoch();
+            assert!(add_dream_to_pool(
+                &mut pool,
+                DreamEntry::new(tensor, epoch, offset)
+            ));
+        }
+        let config = EvolutionConfig {
+            elite_count: 1,
+            crossover_rate: 1.0,
+            ..EvolutionConfig::default()
+        };
+        let target = gradient_tensor(shape, 0.3);
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        assert_eq!(evolve_generation(&mut pool, &target, &config, &mut rng), 0);
+        let epochs: Vec<u32> = pool.entries().iter().map(|entry| entry.epoch).collect();
+        assert_eq!(epochs, vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn mixed_shape_pools_breed_only_target_shaped_parents() {
+        let small = gradient_tensor(Shape2D::new(2, 2), 0.0);
+        let wide = gradient_tensor(Shape2D::new(2, 3), 0.1);
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        assert!(matches!(
+            crossover(&small, &wide, CrossoverMask::Uniform, &mut rng),
+            Err(DreamError::Validation(_))
+        ));
+
+        let mut pool = SimpleDreamPool::new(4, 0.0);
+        for (tensor, score) in [(small, 0.2), (wide, 0.9)] {
+            let epoch = pool.next_epoch();
+            add_dream_to_pool(&mut pool, DreamEntry::new(tensor, epoch, score));
+        }
+        let config = EvolutionConfig {
+            elite_count: 1,
+            crossover_rate: 1.0,
+            mutation_rate: 0.0,
+            ..EvolutionConfig::default()
+        };
+        let target = gradient_tensor(Shape2D::new(2, 2), 0.2);
+        // The fitter wide entry is never bred, so scoring cannot see a mismatch.
+        assert!(evolve_generation(&mut pool, &target, &config, &mut rng) > 0);
+        assert!(pool
+            .entries()
+            .iter()
+            .filter(|entry| entry.epoch >= 2)
+            .all(|entry| entry.tensor.shape == target.shape));
+        assert!(pool.entries().iter().any(|entry| entry.epoch == 1));
+    }
+
+    #[test]
+    fn offspring_are_scored_by_the_configured_evaluator() {
+        let shape = Shape2D::new(3, 3);
+        let target = gradient_tensor(shape, 0.2);
+        let config = EvolutionConfig {
+            elite_count: 1,
+            evaluator: EvaluatorConfig::Spectral(SpectralEvaluator::default()),
+            ..EvolutionConfig::default()
+        };
+        let mut pool = SimpleDreamPool::new(4, 0.0);
+        for offset in [0.0, 0.1, 0.3] {
+            let tensor = gradient_tensor(shape, offset);
+            let score = config.evaluator.evaluate(&tensor, &target);
+            let epoch = pool.next_epoch();
+            add_dream_to_pool(&mut pool, DreamEntry::new(tensor, epoch, score));
+        }
+        let mut rng = ChaCha8Rng::seed_from_u64(6);
+        assert!(evolve_generation(&mut pool, &target, &config, &mut rng) > 0);
+        let legacy = EvaluatorConfig::default();
+        let mut differs = false;
+        for entry in pool.entries() {
+            assert_eq!(
+                entry.score,
+                config.evaluator.evaluate(&entry.tensor, &target)
+            );
+            differs |= entry.score != legacy.evaluate(&entry.tensor, &target);
+        }
+        assert!(differs);
+    }
+}
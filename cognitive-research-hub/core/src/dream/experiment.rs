@@ -0,0 +1,485 @@
+//! A/B harness comparing random seeding with retrieval seeding.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/dream/spec.md`
+//!
+//! Checkpoint 4C expects `retrieve_similar` seeding to raise dream coherence by
+//! at least 5% over random seeding. For every target both arms replay the same
+//! per-epoch RNG streams: each epoch draws a uniformly random seed tensor, and
+//! the retrieval arm replaces it with the pool entry closest to the target once
+//! one exists. Differences between arms therefore come from seeding alone.
+//! Confidence intervals use a percentile bootstrap over per-target differences.
+
+use super::{
+    add_dream_to_pool, generate_dream_with_config, retrieve_similar, DreamConfig, DreamEntry,
+    DreamEvaluator, SimpleDreamPool,
+};
+use crate::{
+    error::{CoreResult, DreamError},
+    tensor::ChromaticTensor,
+    utils::{ChaCha8Rng, JsonValue, RngCore},
+    Fx,
+};
+
+/// Minimum relative coherence gain required by checkpoint 4C.
+pub const RETRIEVAL_GAIN_TARGET: Fx = 0.05;
+
+/// Stream tag reserved for bootstrap resampling.
+const BOOTSTRAP_STREAM: u64 = u64::MAX;
+
+/// Seeding rule applied between epochs of one experiment arm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeedingArm {
+    /// Every epoch starts from a freshly drawn random tensor.
+    Random,
+    /// Every epoch starts from `retrieve_similar(pool, target, 1)`.
+    Retrieval,
+}
+
+impl SeedingArm {
+    /// Returns the identifier used in reports.
+    pub fn name(self) -> &'static str {
+        match self {
+            SeedingArm::Random => "random",
+            SeedingArm::Retrieval => "retrieval",
+        }
+    }
+}
+
+/// Parameters of a paired seeding experiment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeedingExperiment {
+    /// Dream epochs run per arm and target.
+    pub epochs: u32,
+    /// Capacity of each arm's dream pool.
+    pub pool_size: usize,
+    /// Coherence threshold of each arm's dream pool.
+    pub coherence_threshold: Fx,
+    /// Root RNG seed; target `i` uses `split(i)` in both arms.
+    pub rng_seed: u64,
+    /// Number of bootstrap resamples.
+    pub bootstrap_samples: usize,
+    /// Two-sided confidence level of the bootstrap interval.
+    pub confidence: Fx,
+    /// Blend, noise profile, and schedule shared by both arms.
+    pub dream: DreamConfig,
+}
+
+impl Default for SeedingExperiment {
+    fn default() -> Self {
+        Self {
+            epochs: 12,
+            pool_size: 8,
+            coherence_threshold: 0.0,
+            rng_seed: 42,
+            bootstrap_samples: 1000,
+            confidence: 0.95,
+            dream: DreamConfig::default(),
+        }
+    }
+}
+
+impl SeedingExperiment {
+    /// Checks that the experiment can produce a meaningful report.
+    pub fn validate(&self) -> CoreResult<()> {
+        if self.epochs == 0 {
+            return Err(DreamError::Config(
+                "experiment needs at least one epoch".to_string(),
+            ));
+        }
+        if self.bootstrap_samples == 0 {
+            return Err(DreamError::Config(
+                "experiment needs at least one bootstrap sample".to_string(),
+            ));
+        }
+        if !(self.confidence > 0.0 && self.confidence < 1.0) {
+            return Err(DreamError::Config(format!(
+                "confidence must lie in (0, 1), got {}",
+                self.confidence
+            )));
+        }
+        self.dream.validate()
+    }
+
+    /// Runs both arms over `targets` and summarises the paired differences.
+    pub fn run(&self, targets: &[ChromaticTensor]) -> CoreResult<SeedingReport> {
+        self.validate()?;
+        if targets.is_empty() {
+            return Err(DreamError::Validation(
+                "seeding experiment needs at least one target".to_string(),
+            ));
+        }
+        let root = ChaCha8Rng::seed_from_u64(self.rng_seed);
+        let trials: Vec<PairedTrial> = targets
+            .iter()
+            .enumerate()
+            .map(|(idx, target)| {
+                let rng = root.split(idx as u64);
+                PairedTrial {
+                    target_index: idx,
+                    random: self.run_arm(target, SeedingArm::Random, &rng),
+                    retrieval: self.run_arm(target, SeedingArm::Retrieval, &rng),
+                }
+            })
+            .collect();
+        let differences: Vec<Fx> = trials
+            .iter()
+            .map(PairedTrial::coherence_difference)
+            .collect();
+        let mean_random_coherence = mean(trials.iter().map(|t| t.random.mean_coherence()));
+        let mean_retrieval_coherence = mean(trials.iter().map(|t| t.retrieval.mean_coherence()));
+        let mean_difference = mean(differences.iter().copied());
+        let relative_gain = if mean_random_coherence.abs() <= Fx::EPSILON {
+            0.0
+        } else {
+            mean_difference / mean_random_coherence
+        };
+        let (ci_low, ci_high) =
+            bootstrap_interval(&differences, self, &mut root.split(BOOTSTRAP_STREAM));
+        Ok(SeedingReport {
+            experiment: self.clone(),
+            trials,
+            mean_random_coherence,
+            mean_retrieval_coherence,
+            mean_difference,
+            relative_gain,
+            effect_size: cohens_dz(&differences),
+            ci_low,
+            ci_high,
+        })
+    }
+
+    /// Runs one arm, drawing epoch `e` from `rng.split(e)` as
+    /// [`super::dream_cycle`] does, and records the generated dream per epoch.
+    fn run_arm(
+        &self,
+        target: &ChromaticTensor,
+        arm: SeedingArm,
+        rng: &ChaCha8Rng,
+    ) -> ArmTrajectory {
+        let mut pool = SimpleDreamPool::new(self.pool_size, self.coherence_threshold);
+        let schedule = self.dream.noise_schedule;
+        let mut noise = schedule.initial_level();
+        let mut improvement = None;
+        let mut trajectory = ArmTrajectory {
+            arm,
+            coherence: Vec::with_capacity(self.epochs as usize),
+            score: Vec::with_capacity(self.epochs as usize),
+        };
+        let mut best: Option<Fx> = None;
+        for step in 0..self.epochs {
+            noise = schedule.level(step, noise, improvement);
+            let epoch = pool.next_epoch();
+            let mut epoch_rng = rng.split(epoch as u64);
+            // Always drawn so both arms consume identical streams.
+            let random_seed = random_tensor(target, &mut epoch_rng);
+            let seed = match arm {
+                SeedingArm::Random => &random_seed,
+                SeedingArm::Retrieval => retrieve_similar(&pool, target, 1)
+                    .into_iter()
+                    .next()
+                    .unwrap_or(&random_seed),
+            };
+            let dream = generate_dream_with_config(seed, noise, &self.dream, &mut epoch_rng);
+            let score = self.dream.evaluator.evaluate(&dream, target);
+            let entry = DreamEntry::new(dream, epoch, score);
+            trajectory.coherence.push(entry.coherence);
+            trajectory.score.push(score);
+            add_dream_to_pool(&mut pool, entry);
+            let next_best = best.map_or(score, |b| b.max(score));
+            improvement = Some(next_best - best.unwrap_or(0.0));
+            best = Some(next_best);
+        }
+        trajectory
+    }
+}
+
+fn random_tensor(like: &ChromaticTensor, rng: &mut impl RngCore) -> ChromaticTensor {
+    let rgb = (0..like.shape.rgb_len()).map(|_| rng.next_f32()).collect();
+    ChromaticTensor::new(like.shape, rgb, None)
+}
+
+fn mean(values: impl Iterator<Item = Fx>) -> Fx {
+    let (sum, count) = values.fold((0.0, 0usize), |(s, c), v| (s + v, c + 1));
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as Fx
+    }
+}
+
+/// Paired effect size `mean(d) / sd(d)`; zero when fewer than two targets or no spread.
+fn cohens_dz(differences: &[Fx]) -> Fx {
+    if differences.len() < 2 {
+        return 0.0;
+    }
+    let m = mean(differences.iter().copied());
+    let var =
+        differences.iter().map(|d| (d - m) * (d - m)).sum::<Fx>() / (differences.len() - 1) as Fx;
+    let sd = var.sqrt();
+    if sd <= Fx::EPSILON {
+        0.0
+    } else {
+        m / sd
+    }
+}
+
+/// Percentile bootstrap interval of the mean difference.
+fn bootstrap_interval(
+    differences: &[Fx],
+    experiment: &SeedingExperiment,
+    rng: &mut impl RngCore,
+) -> (Fx, Fx) {
+    let n = differences.len() as u32;
+    let mut means: Vec<Fx> = (0..experiment.bootstrap_samples)
+        .map(|_| mean((0..n).map(|_| differences[rng.next_below(n) as usize])))
+        .collect();
+    means.sort_by(|a, b| a.total_cmp(b));
+    let tail = (1.0 - experiment.confidence) / 2.0;
+    let last = means.len() - 1;
+    let at = |q: Fx| means[((q * last as Fx).round() as usize).min(last)];
+    (at(tail), at(1.0 - tail))
+}
+
+/// Per-epoch coherence and score of one arm on one target.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArmTrajectory {
+    pub arm: SeedingArm,
+    pub coherence: Vec<Fx>,
+    pub score: Vec<Fx>,
+}
+
+impl ArmTrajectory {
+    /// Mean coherence across all epochs.
+    pub fn mean_coherence(&self) -> Fx {
+        mean(self.coherence.iter().copied())
+    }
+
+    /// Mean score across all epochs.
+    pub fn mean_score(&self) -> Fx {
+        mean(self.score.iter().copied())
+    }
+
+    fn to_json(&self) -> JsonValue {
+        JsonValue::object()
+            .with("arm", self.arm.name())
+            .with("coherence", self.coherence.clone())
+            .with("score", self.score.clone())
+            .with("mean_coherence", self.mean_coherence())
+            .with("mean_score", self.mean_score())
+    }
+}
+
+/// Both arms run against one target.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PairedTrial {
+    pub target_index: usize,
+    pub random: ArmTrajectory,
+    pub retrieval: ArmTrajectory,
+}
+
+impl PairedTrial {
+    /// Retrieval minus random mean coherence.
+    pub fn coherence_difference(&self) -> Fx {
+        self.retrieval.mean_coherence() - self.random.mean_coherence()
+    }
+}
+
+/// Deterministic outcome of [`SeedingExperiment::run`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeedingReport {
+    pub experiment: SeedingExperiment,
+    pub trials: Vec<PairedTrial>,
+    pub mean_random_coherence: Fx,
+    pub mean_retrieval_coherence: Fx,
+    /// Mean of per-target retrieval − random coherence.
+    pub mean_difference: Fx,
+    /// `mean_difference / mean_random_coherence`.
+    pub relative_gain: Fx,
+    /// Paired Cohen's d (`d_z`) of the per-target differences.
+    pub effect_size: Fx,
+    /// Lower bound of the bootstrap interval of `mean_difference`.
+    pub ci_low: Fx,
+    /// Upper bound of the bootstrap interval of `mean_difference`.
+    pub ci_high: Fx,
+}
+
+impl SeedingReport {
+    /// Returns true when the gain reaches [`RETRIEVAL_GAIN_TARGET`] and the interval excludes zero.
+    pub fn meets_checkpoint(&self) -> bool {
+        self.relative_gain >= RETRIEVAL_GAIN_TARGET && self.ci_low > 0.0
+    }
+
+    /// Builds the JSON document for this report; `rng_seed` is written as a hex string.
+    pub fn to_json(&self) -> JsonValue {
+        let experiment = &self.experiment;
+        let config = JsonValue::object()
+            .with("epochs", experiment.epochs)
+            .with("pool_size", experiment.pool_size)
+            .with("coherence_threshold", experiment.coherence_threshold)
+            .with("rng_seed", format!("{:016x}", experiment.rng_seed))
+            .with("bootstrap_samples", experiment.bootstrap_samples)
+            .with("confidence", experiment.confidence)
+            .with("alpha", experiment.dream.alpha)
+            .with("beta", experiment.dream.beta)
+            .with("noise_profile", experiment.dream.noise_profile.name())
+            .with("noise_schedule", experiment.dream.noise_schedule.name())
+            .with("evaluator", experiment.dream.evaluator.name());
+        let trials: Vec<JsonValue> = self
+            .trials
+            .iter()
+            .map(|trial| {
+                JsonValue::object()
+                    .with("target", trial.target_index)
+                    .with("random", trial.random.to_json())
+                    .with("retrieval", trial.retrieval.to_json())
+                    .with("coherence_difference", trial.coherence_difference())
+            })
+            .collect();
+        let summary = JsonValue::object()
+            .with("mean_random_coherence", self.mean_random_coherence)
+            .with("mean_retrieval_coherence", self.mean_retrieval_coherence)
+            .with("mean_difference", self.mean_difference)
+            .with("relative_gain", self.relative_gain)
+            .with("effect_size", self.effect_size)
+            .with("ci_low", self.ci_low)
+            .with("ci_high", self.ci_high)
+            .with("meets_checkpoint", self.meets_checkpoint());
+        JsonValue::object()
+            .with("config", config)
+            .with("trials", JsonValue::Array(trials))
+            .with("summary", summary)
+    }
+
+    /// Serializes the report; identical experiments give identical strings.
+    pub fn to_json_string(&self) -> String {
+        self.to_json().to_json_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::Shape2D;
+
+    fn targets() -> Vec<ChromaticTensor> {
+        let shape = Shape2D::new(3, 3);
+        [
+            [0.8, 0.2, 0.2],
+            [0.2, 0.7, 0.3],
+            [0.3, 0.3, 0.9],
+            [0.6, 0.6, 0.2],
+        ]
+        .iter()
+        .map(|rgb| {
+            let data = rgb.iter().copied().cycle().take(shape.rgb_len()).collect();
+            ChromaticTensor::new(shape, data, None)
+        })
+        .collect()
+    }
+
+    #[test]
+    fn report_is_deterministic_and_paired() {
+        let experiment = SeedingExperiment {
+            bootstrap_samples: 200,
+            ..SeedingExperiment::default()
+        };
+        let a = experiment.run(&targets()).unwrap();
+        let b = experiment.run(&targets()).unwrap();
+        assert_eq!(a.to_json_string(), b.to_json_string());
+        for trial in &a.trials {
+            assert_eq!(trial.random.coherence.len(), experiment.epochs as usize);
+            // The pool is empty on the first epoch, so both arms share it.
+            assert_eq!(trial.random.coherence[0], trial.retrieval.coherence[0]);
+        }
+        assert!(a.ci_low <= a.mean_difference && a.mean_difference <= a.ci_high);
+    }
+
+    #[test]
+    fn retrieval_seeding_improves_coherence() {
+        let report = SeedingExperiment::default().run(&targets()).unwrap();
+        assert!(report.mean_difference > 0.0);
+        assert!(report.relative_gain > 0.0);
+        let json = JsonValue::parse(&report.to_json_string()).unwrap();
+        let summary = json.get("summary").unwrap();
+        assert_eq!(
+            summary.get("meets_checkpoint").and_then(JsonValue::as_bool),
+            Some(report.meets_checkpoint())
+        );
+        assert_eq!(
+            json.get("trials")
+                .and_then(JsonValue::as_array)
+                .map(|t| t.len()),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn rng_seed_round_trips_through_json_as_hex() {
+        let experiment = SeedingExperiment {
+            epochs: 2,
+            bootstrap_samples: 10,
+            rng_seed: u64::MAX,
+            ..SeedingExperiment::default()
+        };
+        let report = experiment.run(&targets()[..2]).unwrap();
+        let json = JsonValue::parse(&report.to_json_string()).unwrap();
+        let seed = json
+            .get("config")
+            .and_then(|config| config.get("rng_seed"))
+            .and_then(JsonValue::as_str)
+            .and_then(|s| u64::from_str_radix(s, 16).ok());
+        assert_eq!(seed, Some(u64::MAX));
+    }
+
+    #[test]
+    fn rejects_invalid_setups() {
+        let experiment = SeedingExperiment::default();
+        assert!(experiment.run(&[]).is_err());
+        let bad = SeedingExperiment {
+            confidence: 1.0,
+            ..SeedingExperiment::default()
+        };
+        assert!(bad.run(&targets()).is_err());
+    }
+
+    #[test]
+    fn every_driver_draws_its_first_epoch_from_split_zero() {
+        use crate::dream::{dream_cycle, evolve_cycle, evolve_generation, EvolutionConfig};
+
+        let root = ChaCha8Rng::seed_from_u64(5);
+        let target = targets().remove(0);
+        let dream = DreamConfig::default();
+        let noise = dream.noise_schedule.initial_level();
+        let seeded_pool = || {
+            let mut pool = SimpleDreamPool::new(4, 0.0);
+            dream_cycle(&target, &mut pool, 1, &mut root.clone());
+            pool
+        };
+
+        let expected = generate_dream_with_config(&target, noise, &dream, &mut root.split(0));
+        assert_eq!(seeded_pool().entries()[0].tensor.rgb, expected.rgb);
+
+        let experiment = SeedingExperiment {
+            dream: dream.clone(),
+            ..SeedingExperiment::default()
+        };
+        let arm = experiment.run_arm(&target, SeedingArm::Random, &root);
+        let mut epoch_rng = root.split(0);
+        let seed = random_tensor(&target, &mut epoch_rng);
+        let expected = generate_dream_with_config(&seed, noise, &dream, &mut epoch_rng);
+        assert_eq!(arm.score[0], dream.evaluator.evaluate(&expected, &target));
+
+        let config = EvolutionConfig::default();
+        let mut evolved = seeded_pool();
+        let mut replayed = seeded_pool();
+        evolve_cycle(&target, &mut evolved, 1, &config, &mut root.clone());
+        evolve_generation(&mut replayed, &target, &config, &mut root.split(1));
+        let scores = |pool: &SimpleDreamPool| -> Vec<Fx> {
+            pool.entries().iter().map(|e| e.score).collect()
+        };
+        assert_eq!(scores(&evolved), scores(&replayed));
+    }
+}
@@ -0,0 +1,257 @@
+//! Mask-conditioned dream generation for ROI-preserving augmentation.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/dream/spec.md`
+//!
+//! A [`DreamMask`] holds per-cell ROI membership in `[0, 1]` and the region
+//! that may be perturbed. The unmasked dream is blended back into its seed
+//! with [`mask_inject`], so the perturbation at each cell is scaled by its
+//! weight: `D_masked = D_seed + w·(D' − D_seed)`. Protected cells (weight 0)
+//! keep the seed's colour exactly, leaving lesion boundaries untouched.
+
+use super::{clamp_unit, coherence_map_from_rgb};
+use crate::{
+    error::{CoreResult, DreamError},
+    tensor::{delta_hsl, mask_inject, rgb_to_hsl, ChromaticTensor, Shape2D},
+    Fx,
+};
+
+/// Region of the mask a dream is allowed to change.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MaskRegion {
+    /// Perturb only inside the ROI.
+    #[default]
+    Inside,
+    /// Perturb only outside the ROI.
+    Outside,
+}
+
+/// Per-cell ROI mask conditioning dream generation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DreamMask {
+    shape: Shape2D,
+    membership: Vec<Fx>,
+    region: MaskRegion,
+}
+
+impl DreamMask {
+    /// Creates a soft mask; `membership` holds one ROI weight per cell, clamped to `[0, 1]`.
+    pub fn new(shape: Shape2D, membership: Vec<Fx>, region: MaskRegion) -> CoreResult<Self> {
+        if membership.len() != shape.cell_count() {
+            return Err(DreamError::Validation(format!(
+                "mask has {} cells but the {}x{} tensor needs {}",
+                membership.len(),
+                shape.h,
+                shape.w,
+                shape.cell_count()
+            )));
+        }
+        if membership.iter().any(|v| !v.is_finite()) {
+            return Err(DreamError::Validation(
+                "mask contains non-finite weights".to_string(),
+            ));
+        }
+        Ok(Self {
+            shape,
+            membership: membership.into_iter().map(clamp_unit).collect(),
+            region,
+        })
+    }
+
+    /// Creates a hard mask from per-cell ROI flags.
+    pub fn from_binary(shape: Shape2D, roi: &[bool], region: MaskRegion) -> CoreResult<Self> {
+        let membership = roi
+            .iter()
+            .map(|&inside| if inside { 1.0 } else { 0.0 })
+            .collect();
+        Self::new(shape, membership, region)
+    }
+
+    pub fn shape(&self) -> Shape2D {
+        self.shape
+    }
+
+    pub fn region(&self) -> MaskRegion {
+        self.region
+    }
+
+    /// ROI membership per cell.
+    pub fn membership(&self) -> &[Fx] {
+        &self.membership
+    }
+
+    /// Returns the same ROI with the perturbed region swapped.
+    pub fn inverted(&self) -> Self {
+        let region = match self.region {
+            MaskRegion::Inside => MaskRegion::Outside,
+            MaskRegion::Outside => MaskRegion::Inside,
+        };
+        Self {
+            region,
+            ..self.clone()
+        }
+    }
+
+    /// Noise amplitude factor per cell: membership inside, its complement outside.
+    pub fn perturbation_weights(&self) -> Vec<Fx> {
+        match self.region {
+            MaskRegion::Inside => self.membership.clone(),
+            MaskRegion::Outside => self.membership.iter().map(|m| 1.0 - m).collect(),
+        }
+    }
+
+    /// Fails unless every tensor in `shapes` has the mask's shape.
+    pub(super) fn check_shapes(&self, shapes: &[(&str, Shape2D)]) -> CoreResult<()> {
+        match shapes.iter().find(|(_, shape)| *shape != self.shape) {
+            Some((name, shape)) => Err(DreamError::Validation(format!(
+                "{}x{} mask does not match the {}x{} {}",
+                self.shape.h, self.shape.w, shape.h, shape.w, name
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Restricts the change from `seed` to `dream` to the perturbed region.
+    pub fn apply(
+        &self,
+        seed: &ChromaticTensor,
+        dream: &ChromaticTensor,
+    ) -> CoreResult<ChromaticTensor> {
+        self.check_shapes(&[("seed", seed.shape), ("dream", dream.shape)])?;
+        Ok(self.blend(seed, dream))
+    }
+
+    /// [`DreamMask::apply`] for tensors already known to match the mask.
+    pub(super) fn blend(&self, seed: &ChromaticTensor, dream: &ChromaticTensor) -> ChromaticTensor {
+        let mut out = seed.clone();
+        mask_inject(&mut out, seed, dream, &self.perturbation_weights());
+        let coherence = coherence_map_from_rgb(out.shape, &out.rgb);
+        out.coh = Some(coherence);
+        out
+    }
+}
+
+/// Score and coherence reported separately for the ROI and its complement.
+///
+/// Region scores are `1 − ΔE_HSL` averaged with ROI membership (inside) or its
+/// complement (outside) as weights; coherence averages the dream's coherence
+/// map the same way. A region with no weight reports zeros.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RegionMetrics {
+    pub inside_score: Fx,
+    pub inside_coherence: Fx,
+    pub outside_score: Fx,
+    pub outside_coherence: Fx,
+}
+
+/// Measures `dream` against `target` inside and outside the mask's ROI.
+pub fn region_metrics(
+    dream: &ChromaticTensor,
+    target: &ChromaticTensor,
+    mask: &DreamMask,
+) -> CoreResult<RegionMetrics> {
+    mask.check_shapes(&[("dream", dream.shape), ("target", target.shape)])?;
+    Ok(measure_regions(dream, target, mask))
+}
+
+/// [`region_metrics`] for tensors already known to match the mask.
+pub(super) fn measure_regions(
+    dream: &ChromaticTensor,
+    target: &ChromaticTensor,
+    mask: &DreamMask,
+) -> RegionMetrics {
+    let coherence = dream
+        .coh
+        .clone()
+        .unwrap_or_else(|| coherence_map_from_rgb(dream.shape, &dream.rgb));
+    let mut sums = [0.0; 6];
+    for (cell, (a, b)) in dream.rgb.chunks(3).zip(target.rgb.chunks(3)).enumerate() {
+        let (dh, ds, dl) = delta_hsl(rgb_to_hsl(a[0], a[1], a[2]), rgb_to_hsl(b[0], b[1], b[2]));
+        let score = clamp_unit(1.0 - (dh * dh + ds * ds + dl * dl).sqrt());
+        let inside = mask.membership[cell];
+        let outside = 1.0 - inside;
+        sums[0] += inside;
+        sums[1] += inside * score;
+        sums[2] += inside * coherence[cell];
+        sums[3] += outside;
+        sums[4] += outside * score;
+        sums[5] += outside * coherence[cell];
+    }
+    let mean = |total: Fx, weight: Fx| {
+        if weight <= Fx::EPSILON {
+            0.0
+        } else {
+            total / weight
+        }
+    };
+    RegionMetrics {
+        inside_score: mean(sums[1], sums[0]),
+        inside_coherence: mean(sums[2], sums[0]),
+        outside_score: mean(sums[4], sums[3]),
+        outside_coherence: mean(sums[5], sums[3]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square_roi(shape: Shape2D) -> Vec<bool> {
+        (0..shape.cell_count())
+            .map(|idx| {
+                let (row, col) = (idx / shape.w, idx % shape.w);
+                (1..3).contains(&row) && (1..3).contains(&col)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn apply_only_changes_the_perturbed_region() {
+        let shape = Shape2D::new(4, 4);
+        let seed = ChromaticTensor::new(shape, vec![0.3; shape.rgb_len()], None);
+        let dream = ChromaticTensor::new(shape, vec![0.7; shape.rgb_len()], None);
+        let roi = square_roi(shape);
+        let inside = DreamMask::from_binary(shape, &roi, MaskRegion::Inside).unwrap();
+        let masked = inside.apply(&seed, &dream).unwrap();
+        let outside = inside.inverted().apply(&seed, &dream).unwrap();
+        for (cell, &in_roi) in roi.iter().enumerate() {
+            let (a, b) = (masked.rgb[cell * 3], outside.rgb[cell * 3]);
+            if in_roi {
+                assert_eq!((a, b), (0.7, 0.3));
+            } else {
+                assert_eq!((a, b), (0.3, 0.7));
+            }
+        }
+        let metrics = region_metrics(&masked, &seed, &inside).unwrap();
+        assert!((metrics.outside_score - 1.0).abs() <= 1e-6);
+        assert!(metrics.inside_score < metrics.outside_score);
+    }
+
+    #[test]
+    fn soft_weights_scale_the_perturbation() {
+        let shape = Shape2D::new(1, 2);
+        let seed = ChromaticTensor::new(shape, vec![0.2; 6], None);
+        let dream = ChromaticTensor::new(shape, vec![0.6; 6], None);
+        let mask = DreamMask::new(shape, vec![0.25, 1.5], MaskRegion::Inside).unwrap();
+        let out = mask.apply(&seed, &dream).unwrap();
+        assert!((out.rgb[0] - 0.3).abs() <= 1e-6);
+        assert!((out.rgb[3] - 0.6).abs() <= 1e-6);
+        assert!(DreamMask::new(shape, vec![0.5], MaskRegion::Inside).is_err());
+    }
+
+    #[test]
+    fn mismatched_shapes_are_errors() {
+        let mask = DreamMask::new(Shape2D::new(1, 2), vec![1.0, 0.0], MaskRegion::Inside).unwrap();
+        let small = ChromaticTensor::new(Shape2D::new(1, 2), vec![0.2; 6], None);
+        let large = ChromaticTensor::new(Shape2D::new(2, 2), vec![0.2; 12], None);
+        assert!(matches!(
+            mask.apply(&large, &large),
+            Err(DreamError::Validation(_))
+        ));
+        assert!(mask.apply(&small, &large).is_err());
+        assert!(region_metrics(&small, &large, &mask).is_err());
+        assert!(region_metrics(&small, &small, &mask).is_ok());
+    }
+}
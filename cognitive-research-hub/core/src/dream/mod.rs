@@ -5,10 +5,54 @@
 //! - `cognitive-research-hub/core/spec.md`
 //! - `cognitive-research-hub/core/src/dream/spec.md`
 
-use std::cmp::Ordering;
-
+use crate::bridge::{decode_to_chromatic, encode_to_spectral};
+use crate::error::{CoreResult, DreamError};
+use crate::utils::{ChaCha8Rng, RngCore};
 use crate::{tensor::*, Fx};
 
+mod archive;
+mod batch;
+mod config;
+mod control;
+mod evaluator;
+mod eviction;
+mod evolution;
+mod experiment;
+mod mask;
+mod noise;
+mod observer;
+mod retrieval;
+
+pub use archive::{
+    ArchiveRecord, DreamArchive, DREAM_ARCHIVE_VERSION, DREAM_INDEX_MAGIC, DREAM_LOG_MAGIC,
+    DREAM_SNAPSHOT_MAGIC,
+};
+pub use batch::{run_dream_batch, BatchConfig, BatchResult, TargetOutcome};
+pub use config::{DreamConfig, NoiseSchedule, SeedingStrategy};
+pub use control::DreamActionExecutor;
+pub use evaluator::{
+    spectral_coherence, DreamEvaluator, EvaluatorConfig, LegacyEvaluator, SpectralCoherence,
+    SpectralEvaluator,
+};
+pub use eviction::{hue_niche, EvictionPolicy, PoolDiversity};
+pub use evolution::{
+    crossover, crossover_mask, evolve_cycle, evolve_generation, fitness_proportional_select,
+    mutate_hue, mutate_saturation, tournament_select, CrossoverMask, EvolutionConfig, Selection,
+};
+pub use experiment::{
+    ArmTrajectory, PairedTrial, SeedingArm, SeedingExperiment, SeedingReport, RETRIEVAL_GAIN_TARGET,
+};
+pub use mask::{region_metrics, DreamMask, MaskRegion, RegionMetrics};
+pub use noise::{
+    NoiseProfile, NoiseSampler, NoiseTable, NOISE_LUT_SEED, NOISE_LUT_SIZE, NOISE_TABLE_HEADER_LEN,
+    NOISE_TABLE_MAGIC, NOISE_TABLE_VERSION,
+};
+pub use observer::{
+    CycleControl, CycleSummary, DreamObserver, DreamTelemetry, EarlyStopping, PoolEvent,
+    StopReason, TelemetryRecorder,
+};
+pub use retrieval::{DistanceComponents, RetrievalHit, RetrievalQuery, RetrievalWeights};
+
 /// Helper clamp that ensures the value resides within the unit interval.
 fn clamp_unit(x: Fx) -> Fx {
     x.max(0.0).min(1.0)
@@ -61,10 +105,33 @@ fn base_offset(shape: Shape2D, row: usize, col: usize) -> usize {
     cell_index(shape, row, col).saturating_mul(3)
 }
 
-fn synthesize_rgb(seed: &ChromaticTensor, noise: Fx) -> (Vec<Fx>, Vec<Fx>) {
+/// Coefficients of the update `D' = αD + (1 − α)N + β·F(t)`.
+#[derive(Clone, Copy, Debug)]
+struct BlendParams {
+    alpha: Fx,
+    amplitude: Fx,
+    beta: Fx,
+}
+
+/// Spectral reconstruction colour of the dream, the mean of `decode(encode(D))`.
+///
+/// The decoder returns one 1×1 cell that stands for the whole tensor, so the
+/// same colour is broadcast to every cell; averaging keeps that true for any
+/// decoded shape.
+fn spectral_feedback_rgb(dream: &ChromaticTensor) -> [Fx; 3] {
+    mean_rgb(&decode_to_chromatic(&encode_to_spectral(dream)))
+}
+
+fn synthesize_rgb(
+    seed: &ChromaticTensor,
+    params: BlendParams,
+    sampler: &NoiseSampler,
+) -> (Vec<Fx>, Vec<Fx>) {
     let shape = seed.shape;
-    let noise_amp = clamp_unit(noise);
+    let alpha = clamp_unit(params.alpha);
     let mean = mean_rgb(seed);
+    // F(t) pulls each cell toward the colour its spectral encoding decodes to.
+    let feedback = (params.beta != 0.0).then(|| spectral_feedback_rgb(seed));
     let mut rgb = Vec::with_capacity(shape.rgb_len());
     for row in 0..shape.h {
         for col in 0..shape.w {
@@ -73,11 +140,12 @@ fn synthesize_rgb(seed: &ChromaticTensor, noise: Fx) -> (Vec<Fx>, Vec<Fx>) {
             for channel in 0..3 {
                 let idx = base_idx + channel;
                 let source = seed.rgb[idx];
-                let modulation =
-                    ((row as Fx + 1.0) * (col as Fx + 1.0) * (channel as Fx + 1.0) * 0.173_205)
-                        .sin();
-                let blended = source * (1.0 - noise_amp)
-                    + (mean[channel] + modulation * 0.1).clamp(0.0, 1.0) * noise_amp;
+                let modulation = sampler.sample(row, col, channel);
+                let noise_term = (mean[channel] + modulation * params.amplitude).clamp(0.0, 1.0);
+                let mut blended = alpha * source + (1.0 - alpha) * noise_term;
+                if let Some(target) = feedback {
+                    blended += params.beta * (target[channel] - source);
+                }
                 rgb.push(clamp_unit(blended));
             }
         }
@@ -182,6 +250,7 @@ pub struct SimpleDreamPool {
     max_size: usize,
     coherence_threshold: Fx,
     epoch_cursor: u32,
+    eviction: EvictionPolicy,
 }
 
 impl SimpleDreamPool {
@@ -193,9 +262,26 @@ impl SimpleDreamPool {
             max_size: capacity,
             coherence_threshold: clamp_unit(coherence_threshold),
             epoch_cursor: 0,
+            eviction: EvictionPolicy::default(),
         }
     }
 
+    /// Returns the pool using `policy` to choose entries to evict.
+    pub fn with_eviction(mut self, policy: EvictionPolicy) -> Self {
+        self.eviction = policy;
+        self
+    }
+
+    /// Returns the active eviction policy.
+    pub fn eviction(&self) -> EvictionPolicy {
+        self.eviction
+    }
+
+    /// Reports pairwise ΔHSL spread and hue-niche coverage of the stored entries.
+    pub fn diversity(&self) -> PoolDiversity {
+        eviction::diversity(&self.entries)
+    }
+
     /// Returns the number of entries stored.
     pub fn len(&self) -> usize {
         self.entries.len()
@@ -223,104 +309,385 @@ impl SimpleDreamPool {
         &self.entries
     }
 
+    /// Removes every entry; the epoch counter keeps advancing.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
     /// Returns the highest epoch recorded in the pool, if any.
     pub fn latest_epoch(&self) -> Option<u32> {
         self.entries.iter().map(|entry| entry.epoch).max()
     }
 
-    fn push_or_replace(&mut self, entry: DreamEntry) {
-        if self.entries.len() < self.max_size {
-            self.entries.push(entry);
-            return;
+    /// Decides what offering `entry` would do without modifying the pool.
+    fn assess(&self, entry: &DreamEntry) -> (PoolEvent, eviction::Placement) {
+        if !entry.score.is_finite() || !entry.coherence.is_finite() {
+            return (PoolEvent::Invalid, eviction::Placement::Reject);
         }
-        if let Some((idx, _)) = self
-            .entries
-            .iter()
-            .enumerate()
-            .min_by(|a, b| a.1.score.partial_cmp(&b.1.score).unwrap_or(Ordering::Equal))
-        {
-            if self.entries[idx].score < entry.score {
-                self.entries[idx] = entry;
-            }
+        if entry.coherence < self.coherence_threshold {
+            return (PoolEvent::BelowThreshold, eviction::Placement::Reject);
+        }
+        let placement = eviction::place(&self.entries, self.max_size, entry, self.eviction);
+        let event = match placement {
+            eviction::Placement::Append => PoolEvent::Inserted,
+            eviction::Placement::Replace(idx) => PoolEvent::Replaced {
+                evicted_epoch: self.entries[idx].epoch,
+                evicted_score: self.entries[idx].score,
+            },
+            eviction::Placement::Reject => PoolEvent::Rejected,
+        };
+        (event, placement)
+    }
+
+    fn commit(&mut self, entry: DreamEntry, placement: eviction::Placement) {
+        match placement {
+            eviction::Placement::Append => self.entries.push(entry),
+            eviction::Placement::Replace(idx) => self.entries[idx] = entry,
+            eviction::Placement::Reject => {}
         }
     }
 
+    /// Highest score wins; ties go to the oldest epoch.
     fn best_entry(&self) -> Option<&DreamEntry> {
         self.entries
             .iter()
-            .max_by(|a, b| a.score.partial_cmp(&b.score).unwrap_or(Ordering::Equal))
+            .max_by(|a, b| a.score.total_cmp(&b.score).then(b.epoch.cmp(&a.epoch)))
     }
 }
 
-/// Generates a deterministic dream tensor from the seed using noise strength.
-pub fn generate_dream(seed: &ChromaticTensor, noise: Fx) -> ChromaticTensor {
-    let shape = seed.shape;
-    let (rgb, coherence_map) = synthesize_rgb(seed, noise);
-    ChromaticTensor::new(shape, rgb, Some(coherence_map))
+/// Generates a dream tensor from the seed using noise strength and the supplied RNG.
+///
+/// Identical RNG state yields bitwise identical dreams. Noise is read from the
+/// default [`NoiseProfile`] table.
+pub fn generate_dream(
+    seed: &ChromaticTensor,
+    noise: Fx,
+    rng: &mut impl RngCore,
+) -> ChromaticTensor {
+    generate_dream_with_profile(seed, noise, NoiseProfile::default(), rng)
+}
+
+/// Generates a dream whose perturbation is confined to `mask`'s region.
+///
+/// With `None` this is [`generate_dream`]; otherwise the dream is blended back
+/// into `seed` with per-cell weights from [`DreamMask::perturbation_weights`].
+/// It is separate from [`generate_dream`] because that signature is fixed by
+/// the spec and cannot report a mask whose shape differs from the seed.
+pub fn generate_dream_masked(
+    seed: &ChromaticTensor,
+    noise: Fx,
+    mask: Option<&DreamMask>,
+    rng: &mut impl RngCore,
+) -> CoreResult<ChromaticTensor> {
+    if let Some(mask) = mask {
+        mask.check_shapes(&[("seed", seed.shape)])?;
+    }
+    let dream = generate_dream(seed, noise, rng);
+    Ok(match mask {
+        Some(mask) => mask.blend(seed, &dream),
+        None => dream,
+    })
+}
+
+/// Generates a dream sampling `profile`'s LUT at offsets drawn from `rng`.
+pub fn generate_dream_with_profile(
+    seed: &ChromaticTensor,
+    noise: Fx,
+    profile: NoiseProfile,
+    rng: &mut impl RngCore,
+) -> ChromaticTensor {
+    let params = BlendParams {
+        alpha: 1.0 - clamp_unit(noise),
+        amplitude: 0.1,
+        beta: 0.0,
+    };
+    let sampler = profile.sampler(rng);
+    let (rgb, coherence_map) = synthesize_rgb(seed, params, &sampler);
+    ChromaticTensor::new(seed.shape, rgb, Some(coherence_map))
+}
+
+/// Generates a dream with the configured blend, noise profile, and spectral feedback.
+///
+/// `noise_level` is the amplitude of the LUT noise tensor, normally taken from
+/// [`NoiseSchedule::level`].
+pub fn generate_dream_with_config(
+    seed: &ChromaticTensor,
+    noise_level: Fx,
+    config: &DreamConfig,
+    rng: &mut impl RngCore,
+) -> ChromaticTensor {
+    let params = BlendParams {
+        alpha: config.alpha,
+        amplitude: clamp_unit(noise_level),
+        beta: config.beta,
+    };
+    let sampler = config.noise_profile.sampler(rng);
+    let (rgb, coherence_map) = synthesize_rgb(seed, params, &sampler);
+    ChromaticTensor::new(seed.shape, rgb, Some(coherence_map))
 }
 
 /// Evaluates the dream tensor against the target returning a scalar score.
+///
+/// Uses the [`LegacyEvaluator`]; configured cycles score with
+/// [`DreamConfig::evaluator`] instead.
 pub fn evaluate_dream(dream: &ChromaticTensor, target: &ChromaticTensor) -> Fx {
-    let hsl_delta = hsl_distance_field(dream, target);
-    let hsl_score = clamp_unit(1.0 - hsl_delta);
-    let profile_dream = frequency_profile(dream);
-    let profile_target = frequency_profile(target);
-    let spectral_score = spectral_similarity(&profile_dream, &profile_target);
-    0.6 * hsl_score + 0.4 * spectral_score
+    LegacyEvaluator::default().evaluate(dream, target)
 }
 
 /// Inserts a dream entry into the pool if it satisfies the coherence threshold.
-pub fn add_dream_to_pool(pool: &mut SimpleDreamPool, dream: DreamEntry) {
-    if dream.coherence < pool.coherence_threshold {
-        return;
+///
+/// Returns whether the entry was stored; entries with non-finite score or
+/// coherence are never stored.
+pub fn add_dream_to_pool(pool: &mut SimpleDreamPool, dream: DreamEntry) -> bool {
+    try_add_dream_to_pool(pool, dream).unwrap_or(false)
+}
+
+/// Like [`add_dream_to_pool`] but reports non-finite scores as
+/// [`DreamError::Validation`].
+pub fn try_add_dream_to_pool(pool: &mut SimpleDreamPool, dream: DreamEntry) -> CoreResult<bool> {
+    let (event, placement) = pool.assess(&dream);
+    if event == PoolEvent::Invalid {
+        return Err(DreamError::Validation(format!(
+            "dream entry at epoch {} has non-finite score {} or coherence {}",
+            dream.epoch, dream.score, dream.coherence
+        )));
     }
-    pool.push_or_replace(dream);
+    pool.commit(dream, placement);
+    Ok(event.accepted())
 }
 
 /// Retrieves the most similar dream tensors to the query according to ΔHSL.
+///
+/// Equivalent to `RetrievalQuery::new(query).limit(limit).run(pool)`; equal
+/// distances rank the older epoch first.
 pub fn retrieve_similar<'a>(
     pool: &'a SimpleDreamPool,
     query: &ChromaticTensor,
     limit: usize,
 ) -> Vec<&'a ChromaticTensor> {
-    if pool.is_empty() {
-        return Vec::new();
-    }
-    let mut scored: Vec<(usize, Fx)> = pool
-        .entries
-        .iter()
-        .enumerate()
-        .map(|(idx, entry)| {
-            let delta = hsl_distance_field(&entry.tensor, query);
-            (idx, delta)
-        })
-        .collect();
-    scored.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
-    let take = limit.min(scored.len());
-    scored
+    RetrievalQuery::new(query)
+        .limit(limit)
+        .run(pool)
         .into_iter()
-        .take(take)
-        .map(|(idx, _)| &pool.entries[idx].tensor)
+        .map(|hit| hit.tensor())
         .collect()
 }
 
 /// Executes a deterministic dream cycle updating the pool with generated entries.
-pub fn dream_cycle(target: &ChromaticTensor, pool: &mut SimpleDreamPool, epochs: u32) {
-    if epochs == 0 {
-        return;
+///
+/// Epoch `e` draws its noise offsets and seed pick from `rng.split(e)`, so
+/// results depend only on the root RNG key and the pool's epoch cursor. The
+/// evolution and experiment drivers follow the same contract.
+pub fn dream_cycle(
+    target: &ChromaticTensor,
+    pool: &mut SimpleDreamPool,
+    epochs: u32,
+    rng: &mut ChaCha8Rng,
+) {
+    dream_cycle_with_config(target, pool, epochs, &DreamConfig::default(), rng);
+}
+
+/// Picks the next epoch's seed according to `strategy`.
+fn select_seed<'a>(
+    pool: &'a SimpleDreamPool,
+    strategy: SeedingStrategy,
+    rng: &mut impl RngCore,
+) -> Option<&'a DreamEntry> {
+    if pool.is_empty() {
+        return None;
     }
+    match strategy {
+        SeedingStrategy::Best => pool.best_entry(),
+        SeedingStrategy::TopKSample { k } => {
+            let mut order: Vec<usize> = (0..pool.entries.len()).collect();
+            order.sort_by(|&a, &b| {
+                let (ea, eb) = (&pool.entries[a], &pool.entries[b]);
+                eb.score.total_cmp(&ea.score).then(eb.epoch.cmp(&ea.epoch))
+            });
+            let k = k.clamp(1, order.len());
+            let pick = rng.next_below(k as u32) as usize;
+            Some(&pool.entries[order[pick]])
+        }
+        SeedingStrategy::Tournament { size } => {
+            let len = pool.entries.len() as u32;
+            let mut winner = &pool.entries[rng.next_below(len) as usize];
+            for _ in 1..size.max(1) {
+                let challenger = &pool.entries[rng.next_below(len) as usize];
+                if challenger.score > winner.score {
+                    winner = challenger;
+                }
+            }
+            Some(winner)
+        }
+    }
+}
+
+/// Executes a dream cycle using the supplied configuration.
+///
+/// Every epoch applies `D' = αD + (1 − α)N + β·F(t)` to the current seed with
+/// the scheduled noise amplitude, scores the result against `target`, and
+/// reseeds from the pool using the configured [`SeedingStrategy`].
+pub fn dream_cycle_with_config(
+    target: &ChromaticTensor,
+    pool: &mut SimpleDreamPool,
+    epochs: u32,
+    config: &DreamConfig,
+    rng: &mut ChaCha8Rng,
+) {
+    dream_cycle_observed(target, pool, epochs, config, rng, &mut ());
+}
+
+/// Executes a configured dream cycle reporting each stage to `observer`.
+///
+/// The cycle ends after `epochs` epochs or as soon as
+/// [`DreamObserver::on_epoch_end`] returns [`CycleControl::Stop`]. Observing a
+/// cycle never changes the dreams it produces.
+pub fn dream_cycle_observed(
+    target: &ChromaticTensor,
+    pool: &mut SimpleDreamPool,
+    epochs: u32,
+    config: &DreamConfig,
+    rng: &mut ChaCha8Rng,
+    observer: &mut impl DreamObserver,
+) -> CycleSummary {
+    dream_cycle_evaluated(
+        target,
+        pool,
+        epochs,
+        config,
+        rng,
+        &config.evaluator,
+        observer,
+    )
+}
+
+/// Like [`dream_cycle_observed`] but scores dreams with a caller-supplied evaluator.
+pub fn dream_cycle_evaluated(
+    target: &ChromaticTensor,
+    pool: &mut SimpleDreamPool,
+    epochs: u32,
+    config: &DreamConfig,
+    rng: &mut ChaCha8Rng,
+    evaluator: &impl DreamEvaluator,
+    observer: &mut impl DreamObserver,
+) -> CycleSummary {
+    let hooks = CycleHooks {
+        evaluator,
+        mask: None,
+        observer,
+    };
+    run_cycle(target, pool, epochs, config, rng, hooks)
+}
+
+/// Executes a configured dream cycle that only perturbs `mask`'s region.
+///
+/// Every epoch's dream is confined with [`DreamMask::apply`] relative to its
+/// seed, so cells with zero perturbation weight keep the target's colour for
+/// the whole cycle. Pool entries of another shape are never used as seeds.
+/// Telemetry carries [`RegionMetrics`] for both regions.
+///
+/// Unlike [`dream_cycle`] this can fail, returning [`DreamError::Validation`]
+/// when the mask and `target` differ in shape, so it is a separate entry point.
+pub fn dream_cycle_masked(
+    target: &ChromaticTensor,
+    pool: &mut SimpleDreamPool,
+    epochs: u32,
+    config: &DreamConfig,
+    mask: &DreamMask,
+    rng: &mut ChaCha8Rng,
+    observer: &mut impl DreamObserver,
+) -> CoreResult<CycleSummary> {
+    mask.check_shapes(&[("target", target.shape)])?;
+    let hooks = CycleHooks {
+        evaluator: &config.evaluator,
+        mask: Some(mask),
+        observer,
+    };
+    Ok(run_cycle(target, pool, epochs, config, rng, hooks))
+}
+
+/// Per-call collaborators of [`run_cycle`].
+struct CycleHooks<'a, E, O> {
+    evaluator: &'a E,
+    /// Already checked against the target's shape.
+    mask: Option<&'a DreamMask>,
+    observer: &'a mut O,
+}
+
+fn run_cycle<E: DreamEvaluator, O: DreamObserver>(
+    target: &ChromaticTensor,
+    pool: &mut SimpleDreamPool,
+    epochs: u32,
+    config: &DreamConfig,
+    rng: &mut ChaCha8Rng,
+    hooks: CycleHooks<'_, E, O>,
+) -> CycleSummary {
+    let CycleHooks {
+        evaluator,
+        mask,
+        observer,
+    } = hooks;
     let mut seed = target.clone();
+    let mut seed_epoch = None;
+    let mut noise = config.noise_schedule.initial_level();
+    let mut improvement = None;
+    let mut epochs_run = 0;
+    let mut stop_reason = StopReason::Completed;
     for step in 0..epochs {
-        let noise = 0.05 + 0.02 * ((step % 7) as Fx);
-        let dream = generate_dream(&seed, noise);
-        let score = evaluate_dream(&dream, target);
+        noise = config.noise_schedule.level(step, noise, improvement);
         let epoch = pool.next_epoch();
+        observer.on_epoch_start(epoch, noise);
+        let mut epoch_rng = rng.split(epoch as u64);
+        let best_before = pool.best_entry().map(|entry| entry.score);
+        let mut dream = generate_dream_with_config(&seed, noise, config, &mut epoch_rng);
+        if let Some(mask) = mask {
+            dream = mask.blend(&seed, &dream);
+        }
+        let regions = mask.map(|mask| mask::measure_regions(&dream, target, mask));
+        observer.on_dream_generated(epoch, &dream);
+        let score = evaluator.evaluate(&dream, target);
         let entry = DreamEntry::new(dream, epoch, score);
-        add_dream_to_pool(pool, entry);
-        if let Some(best) = pool.best_entry() {
-            seed = best.tensor.clone();
+        observer.on_dream_evaluated(epoch, score, entry.coherence);
+        let coherence = entry.coherence;
+        let (event, placement) = pool.assess(&entry);
+        observer.on_pool_update(&entry, event);
+        pool.commit(entry, placement);
+        let best_after = pool.best_entry().map(|entry| entry.score);
+        improvement = Some(match (best_before, best_after) {
+            (Some(before), Some(after)) => after - before,
+            (None, Some(after)) => after,
+            _ => 0.0,
+        });
+        epochs_run += 1;
+        let telemetry = DreamTelemetry {
+            epoch,
+            noise,
+            score,
+            coherence,
+            event,
+            pool_size: pool.len(),
+            best_score: best_after,
+            seed_epoch,
+            regions,
+        };
+        if let Some(next) = select_seed(pool, config.seeding, &mut epoch_rng) {
+            if mask.is_none_or(|mask| mask.shape() == next.tensor.shape) {
+                seed = next.tensor.clone();
+                seed_epoch = Some(next.epoch);
+            }
+        }
+        if let CycleControl::Stop(reason) = observer.on_epoch_end(&telemetry) {
+            stop_reason = reason;
+            break;
         }
     }
+    let summary = CycleSummary {
+        epochs_run,
+        stop_reason,
+        best_score: pool.best_entry().map(|entry| entry.score),
+    };
+    observer.on_cycle_end(&summary);
+    summary
 }
 
 /// Purges entries whose age exceeds the provided threshold.
@@ -361,7 +728,8 @@ mod tests {
     fn generate_assigns_coherence_map() {
         let shape = Shape2D::new(2, 2);
         let seed = uniform_tensor(0.3, shape);
-        let dream = generate_dream(&seed, 0.2);
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let dream = generate_dream(&seed, 0.2, &mut rng);
         assert_eq!(dream.shape, seed.shape);
         assert!(dream.rgb.iter().all(|&v| (0.0..=1.0).contains(&v)));
         assert_eq!(dream.coh.as_ref().unwrap().len(), shape.cell_count());
@@ -415,10 +783,211 @@ mod tests {
         let shape = Shape2D::new(2, 2);
         let target = uniform_tensor(0.5, shape);
         let mut pool = SimpleDreamPool::new(5, 0.3);
-        dream_cycle(&target, &mut pool, 5);
+        dream_cycle(&target, &mut pool, 5, &mut ChaCha8Rng::seed_from_u64(1));
         assert!(pool.len() > 0);
     }
 
+    #[test]
+    fn generate_is_reproducible_per_rng_stream() {
+        let shape = Shape2D::new(2, 3);
+        let seed = uniform_tensor(0.4, shape);
+        let root = ChaCha8Rng::seed_from_u64(99);
+        let a = generate_dream(&seed, 0.5, &mut root.split(0));
+        let b = generate_dream(&seed, 0.5, &mut root.split(0));
+        let c = generate_dream(&seed, 0.5, &mut root.split(1));
+        assert_eq!(a.rgb, b.rgb);
+        assert_ne!(a.rgb, c.rgb);
+    }
+
+    #[test]
+    fn profiles_produce_distinct_dreams_from_same_stream() {
+        let shape = Shape2D::new(3, 3);
+        let seed = uniform_tensor(0.5, shape);
+        let root = ChaCha8Rng::seed_from_u64(21);
+        let perlin =
+            generate_dream_with_profile(&seed, 0.6, NoiseProfile::Perlin, &mut root.split(0));
+        let blue =
+            generate_dream_with_profile(&seed, 0.6, NoiseProfile::BlueNoise, &mut root.split(0));
+        assert_ne!(perlin.rgb, blue.rgb);
+        let default = generate_dream(&seed, 0.6, &mut root.split(0));
+        assert_eq!(default.rgb, perlin.rgb);
+    }
+
+    #[test]
+    fn spectral_feedback_changes_configured_dreams() {
+        let shape = Shape2D::new(2, 2);
+        let rgb = vec![0.9, 0.1, 0.1, 0.1, 0.9, 0.1, 0.1, 0.1, 0.9, 0.5, 0.5, 0.5];
+        let seed = ChromaticTensor::new(shape, rgb, None);
+        let root = ChaCha8Rng::seed_from_u64(3);
+        let plain = DreamConfig {
+            beta: 0.0,
+            ..DreamConfig::default()
+        };
+        let feedback = DreamConfig {
+            beta: 0.5,
+            ..DreamConfig::default()
+        };
+        let a = generate_dream_with_config(&seed, 0.1, &plain, &mut root.split(0));
+        let b = generate_dream_with_config(&seed, 0.1, &feedback, &mut root.split(0));
+        assert_ne!(a.rgb, b.rgb);
+        assert!(b.rgb.iter().all(|&v| (0.0..=1.0).contains(&v)));
+    }
+
+    #[test]
+    fn spectral_feedback_is_the_broadcast_decoded_mean() {
+        let shape = Shape2D::new(2, 2);
+        let rgb = vec![0.9, 0.1, 0.1, 0.1, 0.9, 0.1, 0.1, 0.1, 0.9, 0.5, 0.5, 0.5];
+        let seed = ChromaticTensor::new(shape, rgb, None);
+        let decoded = decode_to_chromatic(&encode_to_spectral(&seed));
+        assert_eq!(decoded.shape, Shape2D::new(1, 1));
+        let feedback = spectral_feedback_rgb(&seed);
+        assert_eq!(feedback, mean_rgb(&decoded));
+        assert_eq!(feedback.to_vec(), decoded.rgb);
+    }
+
+    #[test]
+    fn seeding_strategies_run_deterministically() {
+        let shape = Shape2D::new(2, 2);
+        let target = uniform_tensor(0.45, shape);
+        for seeding in [
+            SeedingStrategy::Best,
+            SeedingStrategy::TopKSample { k: 3 },
+            SeedingStrategy::Tournament { size: 2 },
+        ] {
+            let config = DreamConfig {
+                seeding,
+                noise_schedule: NoiseSchedule::Adaptive {
+                    initial: 0.1,
+                    min: 0.02,
+                    max: 0.3,
+                    gain: 0.25,
+                },
+                ..DreamConfig::default()
+            };
+            let mut first = SimpleDreamPool::new(4, 0.0);
+            let mut second = SimpleDreamPool::new(4, 0.0);
+            let mut rng = ChaCha8Rng::seed_from_u64(17);
+            dream_cycle_with_config(&target, &mut first, 6, &config, &mut rng.clone());
+            dream_cycle_with_config(&target, &mut second, 6, &config, &mut rng);
+            let first_scores: Vec<Fx> = first.entries().iter().map(|e| e.score).collect();
+            let second_scores: Vec<Fx> = second.entries().iter().map(|e| e.score).collect();
+            assert_eq!(first_scores, second_scores);
+            assert_eq!(first.len(), 4);
+        }
+    }
+
+    #[test]
+    fn nan_scores_are_rejected() {
+        let shape = Shape2D::new(1, 1);
+        let mut pool = SimpleDreamPool::new(2, 0.0);
+        let entry = DreamEntry::new(uniform_tensor(0.5, shape), 0, Fx::NAN);
+        assert!(try_add_dream_to_pool(&mut pool, entry.clone()).is_err());
+        assert!(!add_dream_to_pool(&mut pool, entry));
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn score_ties_evict_oldest_entry() {
+        let shape = Shape2D::new(1, 1);
+        let mut pool = SimpleDreamPool::new(2, 0.0);
+        add_dream_to_pool(
+            &mut pool,
+            DreamEntry::new(uniform_tensor(0.2, shape), 4, 0.5),
+        );
+        add_dream_to_pool(
+            &mut pool,
+            DreamEntry::new(uniform_tensor(0.3, shape), 1, 0.5),
+        );
+        assert!(add_dream_to_pool(
+            &mut pool,
+            DreamEntry::new(uniform_tensor(0.4, shape), 7, 0.6)
+        ));
+        let epochs: Vec<u32> = pool.entries().iter().map(|e| e.epoch).collect();
+        assert_eq!(epochs, vec![4, 7]);
+    }
+
+    #[test]
+    fn candidate_tying_the_weakest_evicts_the_older_entry() {
+        let shape = Shape2D::new(1, 1);
+        let mut pool = SimpleDreamPool::new(2, 0.0);
+        for (epoch, score) in [(3, 0.5), (4, 0.7)] {
+            let entry = DreamEntry::new(uniform_tensor(0.2, shape), epoch, score);
+            add_dream_to_pool(&mut pool, entry);
+        }
+        let older = DreamEntry::new(uniform_tensor(0.3, shape), 1, 0.5);
+        assert!(!add_dream_to_pool(&mut pool, older));
+        let newer = DreamEntry::new(uniform_tensor(0.4, shape), 5, 0.5);
+        assert!(add_dream_to_pool(&mut pool, newer));
+        let epochs: Vec<u32> = pool.entries().iter().map(|e| e.epoch).collect();
+        assert_eq!(epochs, vec![5, 4]);
+    }
+
+    fn hued_tensor(r: Fx, g: Fx, b: Fx, shape: Shape2D) -> ChromaticTensor {
+        let rgb = [r, g, b].repeat(shape.cell_count());
+        ChromaticTensor::new(shape, rgb, None)
+    }
+
+    #[test]
+    fn novelty_policy_evicts_near_duplicates() {
+        let shape = Shape2D::new(2, 2);
+        let mut pool = SimpleDreamPool::new(3, 0.0)
+            .with_eviction(EvictionPolicy::NoveltyWeighted { weight: 1.0 });
+        add_dream_to_pool(
+            &mut pool,
+            DreamEntry::new(hued_tensor(0.8, 0.2, 0.2, shape), 0, 0.60),
+        );
+        add_dream_to_pool(
+            &mut pool,
+            DreamEntry::new(hued_tensor(0.8, 0.21, 0.2, shape), 1, 0.61),
+        );
+        add_dream_to_pool(
+            &mut pool,
+            DreamEntry::new(hued_tensor(0.2, 0.2, 0.8, shape), 2, 0.50),
+        );
+        let before = pool.diversity();
+        let novel = DreamEntry::new(hued_tensor(0.2, 0.8, 0.2, shape), 3, 0.55);
+        assert!(add_dream_to_pool(&mut pool, novel));
+        let epochs: Vec<u32> = pool.entries().iter().map(|e| e.epoch).collect();
+        assert_eq!(epochs, vec![3, 1, 2]);
+        assert!(pool.diversity().min_pairwise_delta > before.min_pairwise_delta);
+    }
+
+    #[test]
+    fn hue_niches_keep_one_elite_per_bin() {
+        let shape = Shape2D::new(1, 2);
+        let mut pool = SimpleDreamPool::new(2, 0.0).with_eviction(EvictionPolicy::HueNiches);
+        add_dream_to_pool(
+            &mut pool,
+            DreamEntry::new(hued_tensor(0.9, 0.1, 0.1, shape), 0, 0.9),
+        );
+        add_dream_to_pool(
+            &mut pool,
+            DreamEntry::new(hued_tensor(0.85, 0.1, 0.1, shape), 1, 0.8),
+        );
+        assert_eq!(pool.diversity().niche_count, 1);
+        let blue = DreamEntry::new(hued_tensor(0.1, 0.1, 0.9, shape), 2, 0.3);
+        assert!(add_dream_to_pool(&mut pool, blue));
+        assert_eq!(pool.diversity().niche_count, 2);
+        let weaker_red = DreamEntry::new(hued_tensor(0.9, 0.12, 0.1, shape), 3, 0.5);
+        assert!(!add_dream_to_pool(&mut pool, weaker_red));
+        let epochs: Vec<u32> = pool.entries().iter().map(|e| e.epoch).collect();
+        assert_eq!(epochs, vec![0, 2]);
+    }
+
+    #[test]
+    fn dream_cycle_is_deterministic_for_seed() {
+        let shape = Shape2D::new(2, 2);
+        let target = uniform_tensor(0.5, shape);
+        let mut first = SimpleDreamPool::new(4, 0.0);
+        let mut second = SimpleDreamPool::new(4, 0.0);
+        dream_cycle(&target, &mut first, 6, &mut ChaCha8Rng::seed_from_u64(5));
+        dream_cycle(&target, &mut second, 6, &mut ChaCha8Rng::seed_from_u64(5));
+        let scores = |pool: &SimpleDreamPool| -> Vec<Fx> {
+            pool.entries().iter().map(|e| e.score).collect()
+        };
+        assert_eq!(scores(&first), scores(&second));
+    }
+
     #[test]
     fn purge_removes_stale_entries() {
         let shape = Shape2D::new(1, 1);
@@ -437,4 +1006,62 @@ mod tests {
             .iter()
             .all(|e| latest.saturating_sub(e.epoch) <= 1));
     }
+
+    #[test]
+    fn observed_cycle_records_telemetry_without_changing_results() {
+        let shape = Shape2D::new(2, 2);
+        let target = uniform_tensor(0.45, shape);
+        let config = DreamConfig::default();
+        let mut plain = SimpleDreamPool::new(4, 0.0);
+        dream_cycle_with_config(
+            &target,
+            &mut plain,
+            6,
+            &config,
+            &mut ChaCha8Rng::seed_from_u64(8),
+        );
+        let mut observed = SimpleDreamPool::new(4, 0.0);
+        let mut recorder = TelemetryRecorder::new();
+        let summary = dream_cycle_observed(
+            &target,
+            &mut observed,
+            6,
+            &config,
+            &mut ChaCha8Rng::seed_from_u64(8),
+            &mut recorder,
+        );
+        let scores = |pool: &SimpleDreamPool| -> Vec<Fx> {
+            pool.entries().iter().map(|e| e.score).collect()
+        };
+        assert_eq!(scores(&plain), scores(&observed));
+        assert_eq!(summary.epochs_run, 6);
+        assert_eq!(summary.stop_reason, StopReason::Completed);
+        assert_eq!(recorder.summary, Some(summary));
+        assert_eq!(recorder.epochs.len(), 6);
+        assert_eq!(recorder.pool_sizes(), vec![1, 2, 3, 4, 4, 4]);
+        assert_eq!(recorder.epochs[0].seed_epoch, None);
+        assert!(recorder.epochs[1].seed_epoch.is_some());
+        let best = recorder.best_scores();
+        assert!(best.windows(2).all(|w| w[1] >= w[0]));
+    }
+
+    #[test]
+    fn early_stopping_ends_cycle_from_callbacks() {
+        let shape = Shape2D::new(2, 2);
+        let target = uniform_tensor(0.45, shape);
+        let mut pool = SimpleDreamPool::new(4, 0.0);
+        let mut observers = (TelemetryRecorder::new(), EarlyStopping::target(0.0));
+        let summary = dream_cycle_observed(
+            &target,
+            &mut pool,
+            10,
+            &DreamConfig::default(),
+            &mut ChaCha8Rng::seed_from_u64(8),
+            &mut observers,
+        );
+        assert_eq!(summary.epochs_run, 1);
+        assert_eq!(summary.stop_reason, StopReason::TargetReached);
+        assert_eq!(observers.0.epochs.len(), 1);
+        assert_eq!(pool.next_epoch(), 1);
+    }
 }
@@ -0,0 +1,576 @@
+//! Precomputed noise look-up tables used for dream perturbation.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/dream/spec.md`
+//!
+//! Noise is never generated while dreaming. Each [`NoiseProfile`] owns a
+//! tileable table embedded from `lut/*.tbl`; the seeded RNG only selects the
+//! per-channel offsets a [`NoiseSampler`] reads from. Tables are laid out
+//! little-endian as:
+//!
+//! | Field | Size | Notes |
+//! | --- | --- | --- |
+//! | magic | 4 | `CNLT` |
+//! | layout version | 2 | [`NOISE_TABLE_VERSION`] |
+//! | profile | 1 | [`NoiseProfile`] tag |
+//! | reserved | 1 | always zero |
+//! | width | 4 | columns |
+//! | height | 4 | rows |
+//! | payload | width × height × 4 | `f32` samples in `[-1, 1]` |
+//! | checksum | 4 | CRC-32 over header and payload |
+//!
+//! The tables are produced by [`NoiseTable::generate`]; run
+//! `cargo run --example generate_noise_luts` to rebuild them.
+
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use crate::{
+    error::{CoreResult, DreamError},
+    utils::{crc32, ChaCha8Rng, RngCore},
+    Fx,
+};
+
+/// Magic bytes opening every noise table.
+pub const NOISE_TABLE_MAGIC: [u8; 4] = *b"CNLT";
+/// Layout version written by [`NoiseTable::to_bytes`].
+pub const NOISE_TABLE_VERSION: u16 = 1;
+/// Size in bytes of the fixed table header.
+pub const NOISE_TABLE_HEADER_LEN: usize = 16;
+/// Edge length of the embedded square tables.
+pub const NOISE_LUT_SIZE: usize = 64;
+/// Base seed the embedded tables were generated with.
+pub const NOISE_LUT_SEED: u64 = 0x6472_6561_6d5f_6c75;
+
+const GAUSSIAN_TABLE: &[u8] = include_bytes!("lut/gaussian_noise.tbl");
+const PERLIN_TABLE: &[u8] = include_bytes!("lut/perlin_noise.tbl");
+const BLUE_TABLE: &[u8] = include_bytes!("lut/blue_noise.tbl");
+const HARMONIC_TABLE: &[u8] = include_bytes!("lut/harmonic_noise.tbl");
+
+/// Noise family used to perturb dreams.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NoiseProfile {
+    /// Clipped white Gaussian noise.
+    Gaussian,
+    /// Two-octave tileable gradient noise (Perlin/simplex family).
+    #[default]
+    Perlin,
+    /// High-pass filtered noise with suppressed low frequencies.
+    BlueNoise,
+    /// Sum of low-order tileable sinusoids.
+    Harmonic,
+}
+
+impl NoiseProfile {
+    /// Every supported profile in tag order.
+    pub const ALL: [NoiseProfile; 4] = [
+        NoiseProfile::Gaussian,
+        NoiseProfile::Perlin,
+        NoiseProfile::BlueNoise,
+        NoiseProfile::Harmonic,
+    ];
+
+    fn tag(self) -> u8 {
+        match self {
+            NoiseProfile::Gaussian => 0,
+            NoiseProfile::Perlin => 1,
+            NoiseProfile::BlueNoise => 2,
+            NoiseProfile::Harmonic => 3,
+        }
+    }
+
+    fn from_tag(tag: u8) -> CoreResult<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|profile| profile.tag() == tag)
+            .ok_or_else(|| DreamError::Dream(format!("unknown noise profile tag {}", tag)))
+    }
+
+    /// Returns the configuration name of the profile.
+    pub fn name(self) -> &'static str {
+        match self {
+            NoiseProfile::Gaussian => "gaussian",
+            NoiseProfile::Perlin => "perlin",
+            NoiseProfile::BlueNoise => "blue",
+            NoiseProfile::Harmonic => "harmonic",
+        }
+    }
+
+    /// Parses a configuration name; `simplex` is accepted as an alias for Perlin.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "gaussian" => Some(NoiseProfile::Gaussian),
+            "perlin" | "simplex" => Some(NoiseProfile::Perlin),
+            "blue" | "blue_noise" => Some(NoiseProfile::BlueNoise),
+            "harmonic" => Some(NoiseProfile::Harmonic),
+            _ => None,
+        }
+    }
+
+    /// Returns the file name of the table inside `lut/`.
+    pub fn file_name(self) -> &'static str {
+        match self {
+            NoiseProfile::Gaussian => "gaussian_noise.tbl",
+            NoiseProfile::Perlin => "perlin_noise.tbl",
+            NoiseProfile::BlueNoise => "blue_noise.tbl",
+            NoiseProfile::Harmonic => "harmonic_noise.tbl",
+        }
+    }
+
+    /// Returns the seed the embedded table for this profile is generated from.
+    pub fn lut_seed(self) -> u64 {
+        NOISE_LUT_SEED.wrapping_add(self.tag() as u64)
+    }
+
+    fn embedded_bytes(self) -> &'static [u8] {
+        match self {
+            NoiseProfile::Gaussian => GAUSSIAN_TABLE,
+            NoiseProfile::Perlin => PERLIN_TABLE,
+            NoiseProfile::BlueNoise => BLUE_TABLE,
+            NoiseProfile::Harmonic => HARMONIC_TABLE,
+        }
+    }
+
+    /// Returns the embedded table, decoding and verifying it on first use.
+    pub fn table(self) -> &'static NoiseTable {
+        static TABLES: [OnceLock<NoiseTable>; 4] = [
+            OnceLock::new(),
+            OnceLock::new(),
+            OnceLock::new(),
+            OnceLock::new(),
+        ];
+        TABLES[self.tag() as usize].get_or_init(|| {
+            NoiseTable::from_bytes(self.embedded_bytes())
+                .expect("embedded noise table failed verification")
+        })
+    }
+
+    /// Draws per-channel offsets from `rng` into this profile's embedded table.
+    pub fn sampler(self, rng: &mut impl RngCore) -> NoiseSampler<'static> {
+        NoiseSampler::new(self.table(), rng)
+    }
+}
+
+/// Tileable two-dimensional table of noise samples in `[-1, 1]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NoiseTable {
+    profile: NoiseProfile,
+    width: usize,
+    height: usize,
+    samples: Vec<Fx>,
+}
+
+impl NoiseTable {
+    /// Builds a table from raw samples; the length must equal `width × height`.
+    pub fn new(
+        profile: NoiseProfile,
+        width: usize,
+        height: usize,
+        samples: Vec<Fx>,
+    ) -> CoreResult<Self> {
+        if width == 0 || height == 0 || samples.len() != width.saturating_mul(height) {
+            return Err(DreamError::Dream(format!(
+                "noise table {}x{} cannot hold {} samples",
+                width,
+                height,
+                samples.len()
+            )));
+        }
+        if samples.iter().any(|v| !v.is_finite()) {
+            return Err(DreamError::Dream(
+                "noise table contains non-finite samples".to_string(),
+            ));
+        }
+        Ok(Self {
+            profile,
+            width,
+            height,
+            samples,
+        })
+    }
+
+    /// Deterministically generates a table for `profile` from `seed`.
+    pub fn generate(profile: NoiseProfile, width: usize, height: usize, seed: u64) -> Self {
+        let width = width.max(1);
+        let height = height.max(1);
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let mut samples = match profile {
+            NoiseProfile::Gaussian => gaussian_field(width, height, &mut rng),
+            NoiseProfile::Perlin => perlin_field(width, height, &mut rng),
+            NoiseProfile::BlueNoise => blue_field(width, height, &mut rng),
+            NoiseProfile::Harmonic => harmonic_field(width, height, &mut rng),
+        };
+        normalize_field(&mut samples);
+        Self {
+            profile,
+            width,
+            height,
+            samples,
+        }
+    }
+
+    /// Returns the profile the table was generated for.
+    pub fn profile(&self) -> NoiseProfile {
+        self.profile
+    }
+
+    /// Returns the number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the samples in row-major order.
+    pub fn samples(&self) -> &[Fx] {
+        &self.samples
+    }
+
+    /// Reads the sample at `(row, col)`, wrapping toroidally.
+    pub fn sample(&self, row: usize, col: usize) -> Fx {
+        let r = row % self.height;
+        let c = col % self.width;
+        self.samples[r * self.width + c]
+    }
+
+    /// Serializes the table with header and CRC-32 trailer.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let payload = self.samples.len().saturating_mul(4);
+        let mut bytes = Vec::with_capacity(NOISE_TABLE_HEADER_LEN + payload + 4);
+        bytes.extend_from_slice(&NOISE_TABLE_MAGIC);
+        bytes.extend_from_slice(&NOISE_TABLE_VERSION.to_le_bytes());
+        bytes.push(self.profile.tag());
+        bytes.push(0);
+        bytes.extend_from_slice(&(self.width as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.height as u32).to_le_bytes());
+        for value in &self.samples {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        let checksum = crc32(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    /// Parses and verifies a serialized table.
+    pub fn from_bytes(bytes: &[u8]) -> CoreResult<Self> {
+        if bytes.len() < NOISE_TABLE_HEADER_LEN + 4 {
+            return Err(DreamError::Dream("truncated noise table".to_string()));
+        }
+        if bytes[0..4] != NOISE_TABLE_MAGIC {
+            return Err(DreamError::Dream(
+                "missing noise table magic bytes".to_string(),
+            ));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != NOISE_TABLE_VERSION {
+            return Err(DreamError::Dream(format!(
+                "unsupported noise table version {} (expected {})",
+                version, NOISE_TABLE_VERSION
+            )));
+        }
+        let profile = NoiseProfile::from_tag(bytes[6])?;
+        let width = u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize;
+        let height = u32::from_le_bytes([bytes[12], bytes[13], bytes[14], bytes[15]]) as usize;
+        let payload = width.saturating_mul(height).saturating_mul(4);
+        let expected = NOISE_TABLE_HEADER_LEN
+            .saturating_add(payload)
+            .saturating_add(4);
+        if bytes.len() != expected {
+            return Err(DreamError::Dream(format!(
+                "noise table length {} does not match {}x{} layout ({} bytes)",
+                bytes.len(),
+                width,
+                height,
+                expected
+            )));
+        }
+        let body_end = expected - 4;
+        let stored = u32::from_le_bytes([
+            bytes[body_end],
+            bytes[body_end + 1],
+            bytes[body_end + 2],
+            bytes[body_end + 3],
+        ]);
+        let actual = crc32(&bytes[..body_end]);
+        if stored != actual {
+            return Err(DreamError::Dream(format!(
+                "noise table checksum mismatch: stored {:08x}, computed {:08x}",
+                stored, actual
+            )));
+        }
+        let samples = bytes[NOISE_TABLE_HEADER_LEN..body_end]
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect();
+        Self::new(profile, width, height, samples)
+    }
+
+    /// Loads and verifies a table from disk.
+    pub fn load(path: impl AsRef<Path>) -> CoreResult<Self> {
+        let bytes = fs::read(path)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Writes the table to disk.
+    pub fn save(&self, path: impl AsRef<Path>) -> CoreResult<()> {
+        fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+}
+
+/// View into a [`NoiseTable`] with RNG-selected per-channel offsets.
+#[derive(Clone, Copy, Debug)]
+pub struct NoiseSampler<'a> {
+    table: &'a NoiseTable,
+    offsets: [(usize, usize); 3],
+}
+
+impl<'a> NoiseSampler<'a> {
+    /// Draws one `(row, col)` offset per RGB channel from `rng`.
+    pub fn new(table: &'a NoiseTable, rng: &mut impl RngCore) -> Self {
+        let mut offsets = [(0, 0); 3];
+        for offset in offsets.iter_mut() {
+            let row = rng.next_below(table.height as u32) as usize;
+            let col = rng.next_below(table.width as u32) as usize;
+            *offset = (row, col);
+        }
+        Self { table, offsets }
+    }
+
+    /// Returns the selected `(row, col)` offsets.
+    pub fn offsets(&self) -> [(usize, usize); 3] {
+        self.offsets
+    }
+
+    /// Reads the noise value for a tensor cell and channel.
+    pub fn sample(&self, row: usize, col: usize, channel: usize) -> Fx {
+        let (row_offset, col_offset) = self.offsets[channel % 3];
+        self.table
+            .sample(row.wrapping_add(row_offset), col.wrapping_add(col_offset))
+    }
+}
+
+/// Removes the mean and scales the field so its peak magnitude is one.
+fn normalize_field(samples: &mut [Fx]) {
+    if samples.is_empty() {
+        return;
+    }
+    let mean = samples.iter().fold(0.0f64, |acc, &v| acc + v as f64) / samples.len() as f64;
+    let mut peak = 0.0f64;
+    for value in samples.iter_mut() {
+        let centered = *value as f64 - mean;
+        *value = centered as Fx;
+        peak = peak.max(centered.abs());
+    }
+    if peak > 0.0 {
+        let scale = (1.0 / peak) as Fx;
+        for value in samples.iter_mut() {
+            *value = (*value * scale).clamp(-1.0, 1.0);
+        }
+    }
+}
+
+fn gaussian_field(width: usize, height: usize, rng: &mut ChaCha8Rng) -> Vec<Fx> {
+    let count = width * height;
+    let mut samples = Vec::with_capacity(count);
+    while samples.len() < count {
+        // Box-Muller; `1 - u` keeps the logarithm argument in (0, 1].
+        let u1 = 1.0 - rng.next_f32() as f64;
+        let u2 = rng.next_f32() as f64;
+        let radius = (-2.0 * u1.ln()).sqrt();
+        let angle = std::f64::consts::TAU * u2;
+        for z in [radius * angle.cos(), radius * angle.sin()] {
+            if samples.len() < count {
+                samples.push((z.clamp(-3.0, 3.0) / 3.0) as Fx);
+            }
+        }
+    }
+    samples
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// Adds one tileable gradient-noise octave with `cells` lattice cells per axis.
+fn perlin_octave(
+    samples: &mut [Fx],
+    width: usize,
+    height: usize,
+    cells: usize,
+    amplitude: f64,
+    rng: &mut ChaCha8Rng,
+) {
+    let cells = cells.max(1);
+    let gradients: Vec<(f64, f64)> = (0..cells * cells)
+        .map(|_| {
+            let angle = std::f64::consts::TAU * rng.next_f32() as f64;
+            (angle.cos(), angle.sin())
+        })
+        .collect();
+    let gradient = |gx: usize, gy: usize| gradients[(gy % cells) * cells + (gx % cells)];
+    for row in 0..height {
+        let y = row as f64 * cells as f64 / height as f64;
+        let y0 = y.floor() as usize;
+        let fy = y - y0 as f64;
+        for col in 0..width {
+            let x = col as f64 * cells as f64 / width as f64;
+            let x0 = x.floor() as usize;
+            let fx = x - x0 as f64;
+            let dot = |gx: usize, gy: usize, dx: f64, dy: f64| {
+                let (ux, uy) = gradient(gx, gy);
+                ux * dx + uy * dy
+            };
+            let n00 = dot(x0, y0, fx, fy);
+            let n10 = dot(x0 + 1, y0, fx - 1.0, fy);
+            let n01 = dot(x0, y0 + 1, fx, fy - 1.0);
+            let n11 = dot(x0 + 1, y0 + 1, fx - 1.0, fy - 1.0);
+            let sx = fade(fx);
+            let sy = fade(fy);
+            let top = n00 + sx * (n10 - n00);
+            let bottom = n01 + sx * (n11 - n01);
+            let value = top + sy * (bottom - top);
+            samples[row * width + col] += (value * amplitude) as Fx;
+        }
+    }
+}
+
+fn perlin_field(width: usize, height: usize, rng: &mut ChaCha8Rng) -> Vec<Fx> {
+    let mut samples = vec![0.0; width * height];
+    let base = (width.min(height) / 16).max(1);
+    perlin_octave(&mut samples, width, height, base, 1.0, rng);
+    perlin_octave(&mut samples, width, height, base * 2, 0.5, rng);
+    samples
+}
+
+fn blue_field(width: usize, height: usize, rng: &mut ChaCha8Rng) -> Vec<Fx> {
+    let mut samples: Vec<Fx> = (0..width * height).map(|_| rng.next_signed()).collect();
+    // Two passes of a toroidal 3×3 high-pass filter push energy to high frequencies.
+    for _ in 0..2 {
+        let source = samples.clone();
+        for row in 0..height {
+            for col in 0..width {
+                let mut sum = 0.0;
+                for dr in [height - 1, 0, 1] {
+                    for dc in [width - 1, 0, 1] {
+                        sum += source[((row + dr) % height) * width + (col + dc) % width];
+                    }
+                }
+                samples[row * width + col] = source[row * width + col] - sum / 9.0;
+            }
+        }
+    }
+    samples
+}
+
+fn harmonic_field(width: usize, height: usize, rng: &mut ChaCha8Rng) -> Vec<Fx> {
+    const COMPONENTS: usize = 6;
+    let components: Vec<(f64, f64, f64, f64)> = (0..COMPONENTS)
+        .map(|k| {
+            let fx = (1 + rng.next_below(4)) as f64;
+            let fy = rng.next_below(4) as f64;
+            let phase = std::f64::consts::TAU * rng.next_f32() as f64;
+            (fx, fy, phase, 1.0 / (k as f64 + 1.0))
+        })
+        .collect();
+    let mut samples = Vec::with_capacity(width * height);
+    for row in 0..height {
+        let v = row as f64 / height as f64;
+        for col in 0..width {
+            let u = col as f64 / width as f64;
+            let value = components.iter().fold(0.0, |acc, &(fx, fy, phase, amp)| {
+                acc + amp * (std::f64::consts::TAU * (fx * u + fy * v) + phase).sin()
+            });
+            samples.push(value as Fx);
+        }
+    }
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mean_abs_neighbor_delta(table: &NoiseTable) -> Fx {
+        let mut total = 0.0;
+        for row in 0..table.height() {
+            for col in 0..table.width() {
+                total += (table.sample(row, col) - table.sample(row, col + 1)).abs();
+            }
+        }
+        total / table.samples().len() as Fx
+    }
+
+    #[test]
+    fn embedded_tables_verify_and_are_normalized() {
+        for profile in NoiseProfile::ALL {
+            let table = profile.table();
+            assert_eq!(table.profile(), profile);
+            assert_eq!(table.width(), NOISE_LUT_SIZE);
+            assert_eq!(table.height(), NOISE_LUT_SIZE);
+            let peak = table
+                .samples()
+                .iter()
+                .fold(0.0f32, |acc, v| acc.max(v.abs()));
+            assert!(
+                (peak - 1.0).abs() <= 1e-4,
+                "{} peak {}",
+                profile.name(),
+                peak
+            );
+            let mean = table.samples().iter().sum::<Fx>() / table.samples().len() as Fx;
+            assert!(mean.abs() <= 1e-3, "{} mean {}", profile.name(), mean);
+        }
+    }
+
+    #[test]
+    fn table_round_trips_and_detects_corruption() {
+        let table = NoiseTable::generate(NoiseProfile::Harmonic, 8, 4, 3);
+        let mut bytes = table.to_bytes();
+        assert_eq!(NoiseTable::from_bytes(&bytes).unwrap(), table);
+        bytes[NOISE_TABLE_HEADER_LEN + 5] ^= 0x40;
+        assert!(NoiseTable::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn smooth_profiles_are_smoother_than_blue_noise() {
+        let perlin = mean_abs_neighbor_delta(NoiseProfile::Perlin.table());
+        let harmonic = mean_abs_neighbor_delta(NoiseProfile::Harmonic.table());
+        let blue = mean_abs_neighbor_delta(NoiseProfile::BlueNoise.table());
+        assert!(perlin < blue);
+        assert!(harmonic < blue);
+    }
+
+    #[test]
+    fn sampler_offsets_follow_rng_stream() {
+        let a = NoiseProfile::Gaussian.sampler(&mut ChaCha8Rng::seed_from_u64(4));
+        let b = NoiseProfile::Gaussian.sampler(&mut ChaCha8Rng::seed_from_u64(4));
+        let c = NoiseProfile::Gaussian.sampler(&mut ChaCha8Rng::seed_from_u64(5));
+        assert_eq!(a.offsets(), b.offsets());
+        assert_ne!(a.offsets(), c.offsets());
+        let (row, col) = a.offsets()[1];
+        assert_eq!(
+            a.sample(2, 3, 1),
+            NoiseProfile::Gaussian.table().sample(row + 2, col + 3)
+        );
+    }
+
+    #[test]
+    fn profile_names_round_trip() {
+        for profile in NoiseProfile::ALL {
+            assert_eq!(NoiseProfile::from_name(profile.name()), Some(profile));
+        }
+        assert_eq!(
+            NoiseProfile::from_name("Simplex"),
+            Some(NoiseProfile::Perlin)
+        );
+        assert_eq!(NoiseProfile::from_name("pink"), None);
+    }
+}
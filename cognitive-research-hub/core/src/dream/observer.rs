@@ -0,0 +1,355 @@
+//! Observer hooks, per-epoch telemetry, and early stopping for dream cycles.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/dream/spec.md`
+//!
+//! [`super::dream_cycle_observed`] reports every stage of an epoch to a
+//! [`DreamObserver`]. After each epoch the observer receives a
+//! [`DreamTelemetry`] row and may end the cycle early by returning
+//! [`CycleControl::Stop`]. Observers compose as tuples; a tuple stops when
+//! any member asks to.
+
+use super::{DreamEntry, RegionMetrics};
+use crate::{tensor::ChromaticTensor, Fx};
+
+/// What happened when a dream was offered to the pool.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PoolEvent {
+    /// Stored in a free slot.
+    Inserted,
+    /// Stored by evicting the entry from `evicted_epoch`.
+    Replaced {
+        evicted_epoch: u32,
+        evicted_score: Fx,
+    },
+    /// Lost to the eviction policy.
+    Rejected,
+    /// Coherence fell below the pool threshold.
+    BelowThreshold,
+    /// Score or coherence was not finite.
+    Invalid,
+}
+
+impl PoolEvent {
+    /// Returns true when the dream now lives in the pool.
+    pub fn accepted(self) -> bool {
+        matches!(self, PoolEvent::Inserted | PoolEvent::Replaced { .. })
+    }
+}
+
+/// Why a dream cycle ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    /// All requested epochs ran.
+    Completed,
+    /// The best score stopped improving.
+    Plateau,
+    /// The best score reached the requested target.
+    TargetReached,
+}
+
+/// Decision returned after every epoch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CycleControl {
+    Continue,
+    Stop(StopReason),
+}
+
+/// Snapshot of one dream epoch.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DreamTelemetry {
+    pub epoch: u32,
+    /// Noise amplitude scheduled for the epoch.
+    pub noise: Fx,
+    pub score: Fx,
+    pub coherence: Fx,
+    pub event: PoolEvent,
+    /// Pool size after the insertion attempt.
+    pub pool_size: usize,
+    /// Best pool score after the insertion attempt, if any entry exists.
+    pub best_score: Option<Fx>,
+    /// Epoch of the pool entry used as seed; `None` when seeded from the target.
+    pub seed_epoch: Option<u32>,
+    /// Inside/outside ROI metrics for mask-conditioned cycles.
+    pub regions: Option<RegionMetrics>,
+}
+
+/// Outcome of an observed dream cycle.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CycleSummary {
+    pub epochs_run: u32,
+    pub stop_reason: StopReason,
+    pub best_score: Option<Fx>,
+}
+
+/// Callbacks invoked by [`super::dream_cycle_observed`]; every method defaults to a no-op.
+pub trait DreamObserver {
+    /// Called before a dream is generated with the scheduled noise level.
+    fn on_epoch_start(&mut self, _epoch: u32, _noise: Fx) {}
+
+    /// Called with the freshly generated dream.
+    fn on_dream_generated(&mut self, _epoch: u32, _dream: &ChromaticTensor) {}
+
+    /// Called once the dream has been scored against the target.
+    fn on_dream_evaluated(&mut self, _epoch: u32, _score: Fx, _coherence: Fx) {}
+
+    /// Called with the result of offering the dream to the pool.
+    fn on_pool_update(&mut self, _entry: &DreamEntry, _event: PoolEvent) {}
+
+    /// Called after each epoch; returning [`CycleControl::Stop`] ends the cycle.
+    fn on_epoch_end(&mut self, _telemetry: &DreamTelemetry) -> CycleControl {
+        CycleControl::Continue
+    }
+
+    /// Called once when the cycle finishes.
+    fn on_cycle_end(&mut self, _summary: &CycleSummary) {}
+}
+
+impl DreamObserver for () {}
+
+impl<T: DreamObserver + ?Sized> DreamObserver for &mut T {
+    fn on_epoch_start(&mut self, epoch: u32, noise: Fx) {
+        (**self).on_epoch_start(epoch, noise);
+    }
+
+    fn on_dream_generated(&mut self, epoch: u32, dream: &ChromaticTensor) {
+        (**self).on_dream_generated(epoch, dream);
+    }
+
+    fn on_dream_evaluated(&mut self, epoch: u32, score: Fx, coherence: Fx) {
+        (**self).on_dream_evaluated(epoch, score, coherence);
+    }
+
+    fn on_pool_update(&mut self, entry: &DreamEntry, event: PoolEvent) {
+        (**self).on_pool_update(entry, event);
+    }
+
+    fn on_epoch_end(&mut self, telemetry: &DreamTelemetry) -> CycleControl {
+        (**self).on_epoch_end(telemetry)
+    }
+
+    fn on_cycle_end(&mut self, summary: &CycleSummary) {
+        (**self).on_cycle_end(summary);
+    }
+}
+
+impl<A: DreamObserver, B: DreamObserver> DreamObserver for (A, B) {
+    fn on_epoch_start(&mut self, epoch: u32, noise: Fx) {
+        self.0.on_epoch_start(epoch, noise);
+        self.1.on_epoch_start(epoch, noise);
+    }
+
+    fn on_dream_generated(&mut self, epoch: u32, dream: &ChromaticTensor) {
+        self.0.on_dream_generated(epoch, dream);
+        self.1.on_dream_generated(epoch, dream);
+    }
+
+    fn on_dream_evaluated(&mut self, epoch: u32, score: Fx, coherence: Fx) {
+        self.0.on_dream_evaluated(epoch, score, coherence);
+        self.1.on_dream_evaluated(epoch, score, coherence);
+    }
+
+    fn on_pool_update(&mut self, entry: &DreamEntry, event: PoolEvent) {
+        self.0.on_pool_update(entry, event);
+        self.1.on_pool_update(entry, event);
+    }
+
+    /// Both members always see the telemetry; the first stop request wins.
+    fn on_epoch_end(&mut self, telemetry: &DreamTelemetry) -> CycleControl {
+        let first = self.0.on_epoch_end(telemetry);
+        let second = self.1.on_epoch_end(telemetry);
+        match first {
+            CycleControl::Continue => second,
+            stop => stop,
+        }
+    }
+
+    fn on_cycle_end(&mut self, summary: &CycleSummary) {
+        self.0.on_cycle_end(summary);
+        self.1.on_cycle_end(summary);
+    }
+}
+
+/// Observer collecting the per-epoch telemetry series.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TelemetryRecorder {
+    pub epochs: Vec<DreamTelemetry>,
+    pub summary: Option<CycleSummary>,
+}
+
+impl TelemetryRecorder {
+    /// Creates an empty recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Score per epoch.
+    pub fn scores(&self) -> Vec<Fx> {
+        self.epochs.iter().map(|t| t.score).collect()
+    }
+
+    /// Coherence per epoch.
+    pub fn coherences(&self) -> Vec<Fx> {
+        self.epochs.iter().map(|t| t.coherence).collect()
+    }
+
+    /// Scheduled noise level per epoch.
+    pub fn noise_levels(&self) -> Vec<Fx> {
+        self.epochs.iter().map(|t| t.noise).collect()
+    }
+
+    /// Pool size per epoch.
+    pub fn pool_sizes(&self) -> Vec<usize> {
+        self.epochs.iter().map(|t| t.pool_size).collect()
+    }
+
+    /// Best pool score per epoch; zero before the first accepted dream.
+    pub fn best_scores(&self) -> Vec<Fx> {
+        self.epochs
+            .iter()
+            .map(|t| t.best_score.unwrap_or(0.0))
+            .collect()
+    }
+}
+
+impl DreamObserver for TelemetryRecorder {
+    fn on_epoch_end(&mut self, telemetry: &DreamTelemetry) -> CycleControl {
+        self.epochs.push(*telemetry);
+        CycleControl::Continue
+    }
+
+    fn on_cycle_end(&mut self, summary: &CycleSummary) {
+        self.summary = Some(*summary);
+    }
+}
+
+/// Observer stopping a cycle on a score plateau or once a target score is reached.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EarlyStopping {
+    /// Epochs without an improvement larger than `min_delta` before stopping.
+    pub patience: u32,
+    pub min_delta: Fx,
+    /// Stop as soon as the pool's best score reaches this value.
+    pub target_score: Option<Fx>,
+    best: Option<Fx>,
+    stale: u32,
+}
+
+impl EarlyStopping {
+    /// Stops after `patience` epochs without a best-score gain above `min_delta`.
+    pub fn plateau(patience: u32, min_delta: Fx) -> Self {
+        Self {
+            patience: patience.max(1),
+            min_delta: min_delta.max(0.0),
+            target_score: None,
+            best: None,
+            stale: 0,
+        }
+    }
+
+    /// Stops once the pool's best score reaches `target`.
+    pub fn target(target: Fx) -> Self {
+        Self {
+            target_score: Some(target),
+            ..Self::plateau(u32::MAX, 0.0)
+        }
+    }
+
+    /// Adds a target score to a plateau rule.
+    pub fn with_target(mut self, target: Fx) -> Self {
+        self.target_score = Some(target);
+        self
+    }
+}
+
+impl DreamObserver for EarlyStopping {
+    fn on_epoch_end(&mut self, telemetry: &DreamTelemetry) -> CycleControl {
+        let Some(best) = telemetry.best_score else {
+            self.stale = self.stale.saturating_add(1);
+            return if self.stale >= self.patience {
+                CycleControl::Stop(StopReason::Plateau)
+            } else {
+                CycleControl::Continue
+            };
+        };
+        if let Some(target) = self.target_score {
+            if best >= target {
+                return CycleControl::Stop(StopReason::TargetReached);
+            }
+        }
+        match self.best {
+            Some(previous) if best - previous <= self.min_delta => {
+                self.stale = self.stale.saturating_add(1);
+            }
+            _ => {
+                self.best = Some(best);
+                self.stale = 0;
+            }
+        }
+        if self.stale >= self.patience {
+            CycleControl::Stop(StopReason::Plateau)
+        } else {
+            CycleControl::Continue
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn telemetry(epoch: u32, best: Fx) -> DreamTelemetry {
+        DreamTelemetry {
+            epoch,
+            noise: 0.05,
+            score: best,
+            coherence: 0.9,
+            event: PoolEvent::Inserted,
+            pool_size: 1,
+            best_score: Some(best),
+            seed_epoch: None,
+            regions: None,
+        }
+    }
+
+    #[test]
+    fn plateau_counts_epochs_without_gain() {
+        let mut stop = EarlyStopping::plateau(2, 0.01);
+        assert_eq!(
+            stop.on_epoch_end(&telemetry(0, 0.5)),
+            CycleControl::Continue
+        );
+        assert_eq!(
+            stop.on_epoch_end(&telemetry(1, 0.505)),
+            CycleControl::Continue
+        );
+        assert_eq!(
+            stop.on_epoch_end(&telemetry(2, 0.6)),
+            CycleControl::Continue
+        );
+        assert_eq!(
+            stop.on_epoch_end(&telemetry(3, 0.6)),
+            CycleControl::Continue
+        );
+        assert_eq!(
+            stop.on_epoch_end(&telemetry(4, 0.605)),
+            CycleControl::Stop(StopReason::Plateau)
+        );
+    }
+
+    #[test]
+    fn target_and_tuple_composition() {
+        let mut pair = (TelemetryRecorder::new(), EarlyStopping::target(0.8));
+        assert_eq!(
+            pair.on_epoch_end(&telemetry(0, 0.7)),
+            CycleControl::Continue
+        );
+        assert_eq!(
+            pair.on_epoch_end(&telemetry(1, 0.85)),
+            CycleControl::Stop(StopReason::TargetReached)
+        );
+        assert_eq!(pair.0.best_scores(), vec![0.7, 0.85]);
+    }
+}
@@ -0,0 +1,345 @@
+//! Multi-criteria dream retrieval with explainable distances.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/dream/spec.md`
+//!
+//! Every candidate gets four component distances, each oriented so smaller is
+//! closer:
+//!
+//! | Component | Definition |
+//! | --- | --- |
+//! | `delta_hsl` | mean per-cell ΔHSL magnitude |
+//! | `spectral` | `1 −` 2D FFT magnitude similarity |
+//! | `coherence_gap` | `|coherence(entry) − coherence(query)|` |
+//! | `ums` | RMS distance between UMS projections |
+//!
+//! The ranking distance is their weighted mean. Components whose weight is
+//! zero (or negative) are not computed and report `None`, so a ΔHSL-only
+//! query never pays for the FFT or the UMS projection. Entries whose shape
+//! differs from the query's are not comparable cell by cell and are skipped
+//! like filtered entries. Hits are ordered by ranking
+//! distance (`f32::total_cmp`), then by ascending epoch, then by pool index,
+//! so ties never depend on sort stability or insertion history.
+
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+use super::{
+    coherence_metric, hsl_distance_field, spectral_coherence, DreamEntry, SimpleDreamPool,
+};
+use crate::{
+    bridge::{encode_to_spectral, project_to_ums, UnifiedModalitySpace},
+    tensor::ChromaticTensor,
+    Fx,
+};
+
+/// Component distances between a pool entry and the query; `None` marks a
+/// component that was skipped because its weight is zero.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DistanceComponents {
+    pub delta_hsl: Option<Fx>,
+    pub spectral: Option<Fx>,
+    pub coherence_gap: Option<Fx>,
+    pub ums: Option<Fx>,
+}
+
+/// Relative importance of each component in the ranking distance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetrievalWeights {
+    pub delta_hsl: Fx,
+    pub spectral: Fx,
+    pub coherence_gap: Fx,
+    pub ums: Fx,
+}
+
+impl Default for RetrievalWeights {
+    /// ΔHSL only, matching [`super::retrieve_similar`].
+    fn default() -> Self {
+        Self {
+            delta_hsl: 1.0,
+            spectral: 0.0,
+            coherence_gap: 0.0,
+            ums: 0.0,
+        }
+    }
+}
+
+impl RetrievalWeights {
+    /// Evaluates `distance` only when `weight` contributes to the ranking.
+    fn measure(weight: Fx, distance: impl FnOnce() -> Fx) -> Option<Fx> {
+        (weight > 0.0).then(distance)
+    }
+
+    fn combine(&self, d: &DistanceComponents) -> Fx {
+        let parts = [
+            (self.delta_hsl, d.delta_hsl),
+            (self.spectral, d.spectral),
+            (self.coherence_gap, d.coherence_gap),
+            (self.ums, d.ums),
+        ];
+        let (weighted, total) = parts
+            .iter()
+            .filter_map(|&(w, v)| v.map(|v| (w, v)))
+            .fold((0.0, 0.0), |(sum, total): (Fx, Fx), (w, v)| {
+                (sum + w * v, total + w)
+            });
+        if total <= Fx::EPSILON {
+            return 0.0;
+        }
+        weighted / total
+    }
+}
+
+/// One ranked retrieval result.
+#[derive(Clone, Copy, Debug)]
+pub struct RetrievalHit<'a> {
+    /// 1-based position in the ranking.
+    pub rank: usize,
+    /// Index of the entry in [`SimpleDreamPool::entries`].
+    pub pool_index: usize,
+    pub entry: &'a DreamEntry,
+    /// Weighted ranking distance.
+    pub distance: Fx,
+    pub components: DistanceComponents,
+}
+
+impl<'a> RetrievalHit<'a> {
+    pub fn tensor(&self) -> &'a ChromaticTensor {
+        &self.entry.tensor
+    }
+}
+
+fn ums_of(tensor: &ChromaticTensor) -> UnifiedModalitySpace {
+    project_to_ums(tensor, &encode_to_spectral(tensor))
+}
+
+fn ums_distance(a: &UnifiedModalitySpace, b: &UnifiedModalitySpace) -> Fx {
+    let sum: Fx = a
+        .as_slice()
+        .iter()
+        .zip(b.as_slice())
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum();
+    (sum / a.len().max(1) as Fx).sqrt()
+}
+
+/// Builder for weighted, filtered retrieval against a query tensor.
+#[derive(Clone, Debug)]
+pub struct RetrievalQuery<'q> {
+    query: &'q ChromaticTensor,
+    weights: RetrievalWeights,
+    min_coherence: Option<Fx>,
+    epochs: Option<RangeInclusive<u32>>,
+    scores: Option<RangeInclusive<Fx>>,
+    limit: usize,
+}
+
+impl<'q> RetrievalQuery<'q> {
+    /// Starts a ΔHSL-only query without filters or limit.
+    pub fn new(query: &'q ChromaticTensor) -> Self {
+        Self {
+            query,
+            weights: RetrievalWeights::default(),
+            min_coherence: None,
+            epochs: None,
+            scores: None,
+            limit: usize::MAX,
+        }
+    }
+
+    pub fn weights(mut self, weights: RetrievalWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    pub fn hsl_weight(mut self, weight: Fx) -> Self {
+        self.weights.delta_hsl = weight;
+        self
+    }
+
+    pub fn spectral_weight(mut self, weight: Fx) -> Self {
+        self.weights.spectral = weight;
+        self
+    }
+
+    pub fn coherence_weight(mut self, weight: Fx) -> Self {
+        self.weights.coherence_gap = weight;
+        self
+    }
+
+    pub fn ums_weight(mut self, weight: Fx) -> Self {
+        self.weights.ums = weight;
+        self
+    }
+
+    /// Keeps entries whose coherence is at least `min`.
+    pub fn min_coherence(mut self, min: Fx) -> Self {
+        self.min_coherence = Some(min);
+        self
+    }
+
+    /// Keeps entries whose epoch lies in `window` (inclusive).
+    pub fn epoch_window(mut self, window: RangeInclusive<u32>) -> Self {
+        self.epochs = Some(window);
+        self
+    }
+
+    /// Keeps entries whose score lies in `range` (inclusive).
+    pub fn score_range(mut self, range: RangeInclusive<Fx>) -> Self {
+        self.scores = Some(range);
+        self
+    }
+
+    /// Returns at most `limit` hits.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    fn admits(&self, entry: &DreamEntry) -> bool {
+        self.min_coherence.is_none_or(|min| entry.coherence >= min)
+            && self
+                .epochs
+                .as_ref()
+                .is_none_or(|window| window.contains(&entry.epoch))
+            && self
+                .scores
+                .as_ref()
+                .is_none_or(|range| range.contains(&entry.score))
+    }
+
+    /// Scores, filters, and ranks the pool's entries.
+    pub fn run<'a>(&self, pool: &'a SimpleDreamPool) -> Vec<RetrievalHit<'a>> {
+        let w = self.weights;
+        let query_coherence = coherence_metric(self.query);
+        let query_ums = (w.ums > 0.0).then(|| ums_of(self.query));
+        let mut hits: Vec<RetrievalHit<'a>> = pool
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.tensor.shape == self.query.shape && self.admits(entry))
+            .map(|(pool_index, entry)| {
+                let components = DistanceComponents {
+                    delta_hsl: RetrievalWeights::measure(w.delta_hsl, || {
+                        hsl_distance_field(&entry.tensor, self.query)
+                    }),
+                    spectral: RetrievalWeights::measure(w.spectral, || {
+                        1.0 - spectral_coherence(&entry.tensor, self.query).magnitude
+                    }),
+                    coherence_gap: RetrievalWeights::measure(w.coherence_gap, || {
+                        (entry.coherence - query_coherence).abs()
+                    }),
+                    ums: query_ums
+                        .as_ref()
+                        .map(|query_ums| ums_distance(&ums_of(&entry.tensor), query_ums)),
+                };
+                RetrievalHit {
+                    rank: 0,
+                    pool_index,
+                    entry,
+                    distance: self.weights.combine(&components),
+                    components,
+                }
+            })
+            .collect();
+        hits.sort_by(ranking_order);
+        hits.truncate(self.limit);
+        for (idx, hit) in hits.iter_mut().enumerate() {
+            hit.rank = idx + 1;
+        }
+        hits
+    }
+}
+
+/// Distance, then epoch, then pool index.
+fn ranking_order(a: &RetrievalHit, b: &RetrievalHit) -> Ordering {
+    a.distance
+        .total_cmp(&b.distance)
+        .then(a.entry.epoch.cmp(&b.entry.epoch))
+        .then(a.pool_index.cmp(&b.pool_index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dream::add_dream_to_pool;
+    use crate::tensor::Shape2D;
+
+    fn tensor(value: Fx) -> ChromaticTensor {
+        let shape = Shape2D::new(2, 2);
+        ChromaticTensor::new(shape, vec![value; shape.rgb_len()], None)
+    }
+
+    fn pool() -> SimpleDreamPool {
+        let mut pool = SimpleDreamPool::new(6, 0.0);
+        for (value, score) in [(0.2, 0.3), (0.6, 0.9), (0.4, 0.5), (0.6, 0.7)] {
+            let epoch = pool.next_epoch();
+            add_dream_to_pool(&mut pool, DreamEntry::new(tensor(value), epoch, score));
+        }
+        pool
+    }
+
+    #[test]
+    fn ties_break_by_epoch_and_hits_explain_distance() {
+        let pool = pool();
+        let query = tensor(0.6);
+        let hits = RetrievalQuery::new(&query).run(&pool);
+        let epochs: Vec<u32> = hits.iter().map(|h| h.entry.epoch).collect();
+        assert_eq!(epochs, vec![1, 3, 2, 0]);
+        assert_eq!(hits[0].rank, 1);
+        assert_eq!(hits[0].distance, hits[1].distance);
+        assert_eq!(Some(hits[0].distance), hits[0].components.delta_hsl);
+        // Zero-weight components are skipped rather than computed.
+        assert_eq!(hits[0].components.ums, None);
+        assert_eq!(hits[3].components.spectral, None);
+        assert_eq!(hits[3].components.coherence_gap, None);
+        assert!(hits[3].components.delta_hsl.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn filters_and_weights_shape_the_ranking() {
+        let pool = pool();
+        let query = tensor(0.6);
+        let hits = RetrievalQuery::new(&query)
+            .score_range(0.4..=0.8)
+            .epoch_window(2..=3)
+            .limit(1)
+            .run(&pool);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entry.epoch, 3);
+
+        let ums_only = RetrievalQuery::new(&query)
+            .hsl_weight(0.0)
+            .ums_weight(1.0)
+            .run(&pool);
+        assert_eq!(ums_only[0].components.ums, Some(0.0));
+        assert!(ums_only[3].components.ums.unwrap() > 0.0);
+        assert_eq!(Some(ums_only[3].distance), ums_only[3].components.ums);
+        assert_eq!(ums_only[3].components.delta_hsl, None);
+        assert!(RetrievalQuery::new(&query)
+            .min_coherence(1.1)
+            .run(&pool)
+            .is_empty());
+    }
+
+    #[test]
+    fn entries_of_another_shape_are_skipped() {
+        let mut pool = pool();
+        let wide = Shape2D::new(3, 2);
+        let odd = ChromaticTensor::new(wide, vec![0.6; wide.rgb_len()], None);
+        let epoch = pool.next_epoch();
+        add_dream_to_pool(&mut pool, DreamEntry::new(odd, epoch, 0.8));
+        let query = tensor(0.6);
+        let hits = RetrievalQuery::new(&query)
+            .hsl_weight(1.0)
+            .spectral_weight(1.0)
+            .coherence_weight(1.0)
+            .ums_weight(1.0)
+            .run(&pool);
+        assert_eq!(hits.len(), 4);
+        assert!(hits.iter().all(|hit| hit.tensor().shape == query.shape));
+        assert!(hits.iter().all(|hit| hit.components.spectral.is_some()));
+    }
+}
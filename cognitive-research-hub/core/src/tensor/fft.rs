@@ -0,0 +1,185 @@
+//! Deterministic discrete Fourier transforms for chromatic tensors.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/tensor/spec.md`
+//!
+//! Power-of-two lengths use an iterative radix-2 Cooley–Tukey transform; other
+//! lengths fall back to the direct O(n²) DFT. Twiddle factors are evaluated in
+//! `f64` and every butterfly runs in a fixed order, so identical input always
+//! yields bitwise identical spectra.
+
+use std::f64::consts::PI;
+
+use super::{ChromaticTensor, Shape2D};
+use crate::Fx;
+
+/// Complex sample used by the transforms.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Complex {
+    pub re: Fx,
+    pub im: Fx,
+}
+
+impl Complex {
+    /// Creates a complex number from its parts.
+    pub const fn new(re: Fx, im: Fx) -> Self {
+        Self { re, im }
+    }
+
+    /// Magnitude `|z|`.
+    pub fn norm(self) -> Fx {
+        self.re.hypot(self.im)
+    }
+
+    /// Phase angle in radians within `(-π, π]`.
+    pub fn arg(self) -> Fx {
+        self.im.atan2(self.re)
+    }
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+fn twiddle(k: usize, n: usize) -> Complex {
+    let angle = -2.0 * PI * k as f64 / n as f64;
+    Complex::new(angle.cos() as Fx, angle.sin() as Fx)
+}
+
+/// Forward DFT of `data` in place.
+pub fn fft(data: &mut [Complex]) {
+    let n = data.len();
+    if n <= 1 {
+        return;
+    }
+    if n.is_power_of_two() {
+        radix2(data);
+    } else {
+        let input = data.to_vec();
+        for (k, out) in data.iter_mut().enumerate() {
+            let mut acc = Complex::default();
+            for (j, &x) in input.iter().enumerate() {
+                acc = acc.add(x.mul(twiddle((j * k) % n, n)));
+            }
+            *out = acc;
+        }
+    }
+}
+
+fn radix2(data: &mut [Complex]) {
+    let n = data.len();
+    let bits = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if j > i {
+            data.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        for start in (0..n).step_by(len) {
+            for k in 0..half {
+                let w = twiddle(k, len);
+                let a = data[start + k];
+                let b = data[start + k + half].mul(w);
+                data[start + k] = a.add(b);
+                data[start + k + half] = a.sub(b);
+            }
+        }
+        len *= 2;
+    }
+}
+
+/// Row-major 2D forward DFT: rows first, then columns.
+pub fn fft2d(data: &mut [Complex], shape: Shape2D) {
+    assert_eq!(
+        data.len(),
+        shape.cell_count(),
+        "buffer does not match shape"
+    );
+    for row in data.chunks_mut(shape.w) {
+        fft(row);
+    }
+    let mut column = vec![Complex::default(); shape.h];
+    for col in 0..shape.w {
+        for (row, slot) in column.iter_mut().enumerate() {
+            *slot = data[row * shape.w + col];
+        }
+        fft(&mut column);
+        for (row, value) in column.iter().enumerate() {
+            data[row * shape.w + col] = *value;
+        }
+    }
+}
+
+/// 2D spectrum of one RGB channel (`0..3`) of `tensor`.
+pub fn channel_spectrum(tensor: &ChromaticTensor, channel: usize) -> Vec<Complex> {
+    assert!(channel < 3, "channel index must be 0, 1 or 2");
+    let mut data: Vec<Complex> = tensor
+        .rgb
+        .chunks(3)
+        .map(|rgb| Complex::new(rgb[channel], 0.0))
+        .collect();
+    fft2d(&mut data, tensor.shape);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn direct(data: &[Complex]) -> Vec<Complex> {
+        let mut out = data.to_vec();
+        let n = data.len();
+        for (k, slot) in out.iter_mut().enumerate() {
+            let mut acc = Complex::default();
+            for (j, &x) in data.iter().enumerate() {
+                acc = acc.add(x.mul(twiddle((j * k) % n, n)));
+            }
+            *slot = acc;
+        }
+        out
+    }
+
+    #[test]
+    fn radix2_matches_direct_dft() {
+        let data: Vec<Complex> = (0..8)
+            .map(|i| Complex::new((i as Fx * 0.7).sin(), (i as Fx * 0.3).cos()))
+            .collect();
+        let mut fast = data.clone();
+        fft(&mut fast);
+        for (a, b) in fast.iter().zip(direct(&data)) {
+            assert!((a.re - b.re).abs() <= 1e-5 && (a.im - b.im).abs() <= 1e-5);
+        }
+    }
+
+    #[test]
+    fn impulse_has_flat_spectrum_and_dc_sums_signal() {
+        let shape = Shape2D::new(3, 4);
+        let mut impulse = vec![Complex::default(); shape.cell_count()];
+        impulse[0] = Complex::new(1.0, 0.0);
+        fft2d(&mut impulse, shape);
+        assert!(impulse.iter().all(|z| (z.norm() - 1.0).abs() <= 1e-5));
+
+        let rgb: Vec<Fx> = (0..shape.rgb_len()).map(|i| (i % 5) as Fx * 0.1).collect();
+        let tensor = ChromaticTensor::new(shape, rgb.clone(), None);
+        let spectrum = channel_spectrum(&tensor, 1);
+        let sum: Fx = rgb.iter().skip(1).step_by(3).sum();
+        assert!((spectrum[0].re - sum).abs() <= 1e-5);
+        assert!(spectrum[0].im.abs() <= 1e-5);
+    }
+}
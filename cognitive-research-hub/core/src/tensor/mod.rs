@@ -6,6 +6,7 @@
 //! - `cognitive-research-hub/core/src/tensor/spec.md`
 
 mod chromatic;
+mod fft;
 mod layout;
 mod ops;
 <<<<<<< ours
@@ -35,6 +36,7 @@ mod quant;
 mod spectral;
 
 pub use chromatic::{delta_hsl, hsl_to_rgb, normalize_hue, rgb_to_hsl, ChromaticTensor};
+pub use fft::{channel_spectrum, fft, fft2d, Complex};
 pub use layout::{Shape2D, Stride2D};
 <<<<<<< ours
 <<<<<<< ours
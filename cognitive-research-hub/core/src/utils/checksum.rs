@@ -0,0 +1,91 @@
+//! Deterministic CRC-32 checksums for on-disk artefacts.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/utils/spec.md`
+
+/// Reflected IEEE 802.3 polynomial.
+const CRC32_POLY: u32 = 0xedb8_8320;
+
+const CRC32_TABLE: [u32; 256] = build_table();
+
+const fn build_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut idx = 0;
+    while idx < 256 {
+        let mut crc = idx as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ CRC32_POLY
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[idx] = crc;
+        idx += 1;
+    }
+    table
+}
+
+/// Incremental CRC-32 (IEEE) hasher used for streaming writers and readers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Crc32 {
+    state: u32,
+}
+
+impl Crc32 {
+    /// Creates a hasher in its initial state.
+    pub fn new() -> Self {
+        Self { state: 0xffff_ffff }
+    }
+
+    /// Feeds `bytes` into the running checksum in order.
+    pub fn update(&mut self, bytes: &[u8]) {
+        let mut crc = self.state;
+        for &byte in bytes {
+            let idx = ((crc ^ byte as u32) & 0xff) as usize;
+            crc = (crc >> 8) ^ CRC32_TABLE[idx];
+        }
+        self.state = crc;
+    }
+
+    /// Returns the checksum of all bytes fed so far.
+    pub fn finish(&self) -> u32 {
+        self.state ^ 0xffff_ffff
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Computes the CRC-32 (IEEE) checksum of `bytes` in a single pass.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut hasher = Crc32::new();
+    hasher.update(bytes);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_matches_reference_vector() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn incremental_updates_match_single_pass() {
+        let mut hasher = Crc32::new();
+        hasher.update(b"1234");
+        hasher.update(b"56789");
+        assert_eq!(hasher.finish(), crc32(b"123456789"));
+    }
+}
@@ -0,0 +1,16 @@
+//! Crate-wide helper utilities shared across the Chromatic Core.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/utils/spec.md`
+
+mod checksum;
+mod json;
+mod rng;
+mod toml;
+
+pub use checksum::{crc32, Crc32};
+pub use json::JsonValue;
+pub use rng::{ChaCha8Rng, RngCore, RngState, RNG_STATE_LEN};
+pub use toml::parse_toml;
@@ -0,0 +1,420 @@
+//! Portable counter-based ChaCha8 random number generator.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/utils/spec.md`
+//! - `cognitive-research-hub/core/src/dream/spec.md`
+//!
+//! The generator keeps a 256-bit key, a 64-bit stream identifier, and a 64-bit
+//! block counter. Because output is a pure function of those three values the
+//! RNG can jump ahead in O(1), derive independent child streams per epoch or
+//! worker, and be serialized and restored mid-stream without drift.
+
+use crate::error::{CoreResult, DreamError};
+use crate::utils::JsonValue;
+
+/// Number of 32-bit words produced per ChaCha block.
+const BLOCK_WORDS: usize = 16;
+/// Number of ChaCha rounds (four double rounds).
+const CHACHA_ROUNDS: usize = 8;
+/// "expand 32-byte k" constants.
+const SIGMA: [u32; 4] = [0x6170_7865, 0x3320_646e, 0x7962_2d32, 0x6b20_6574];
+/// Stream reserved for deriving child keys in [`ChaCha8Rng::split`].
+const SPLIT_STREAM: u64 = u64::MAX;
+/// Serialized length of [`RngState`] in bytes.
+pub const RNG_STATE_LEN: usize = 48;
+
+/// Minimal random source trait used across the dream subsystem.
+pub trait RngCore {
+    /// Returns the next 32 random bits.
+    fn next_u32(&mut self) -> u32;
+
+    /// Returns the next 64 random bits (low word first).
+    fn next_u64(&mut self) -> u64 {
+        let lo = self.next_u32() as u64;
+        let hi = self.next_u32() as u64;
+        (hi << 32) | lo
+    }
+
+    /// Returns a uniformly distributed `f32` in `[0, 1)` with 24 bits of precision.
+    fn next_f32(&mut self) -> f32 {
+        (self.next_u32() >> 8) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+
+    /// Returns a uniformly distributed `f32` in `[-1, 1)`.
+    fn next_signed(&mut self) -> f32 {
+        self.next_f32() * 2.0 - 1.0
+    }
+
+    /// Returns an unbiased integer in `[0, bound)`; `bound` must be non-zero.
+    fn next_below(&mut self, bound: u32) -> u32 {
+        assert!(bound > 0, "bound must be positive");
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let product = self.next_u32() as u64 * bound as u64;
+            if (product as u32) >= threshold {
+                return (product >> 32) as u32;
+            }
+        }
+    }
+}
+
+#[inline]
+fn quarter_round(state: &mut [u32; BLOCK_WORDS], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+/// Computes one ChaCha block for `key`, 64-bit `counter`, and 64-bit `stream`.
+fn chacha_block(key: &[u32; 8], counter: u64, stream: u64, rounds: usize) -> [u32; BLOCK_WORDS] {
+    let mut input = [0u32; BLOCK_WORDS];
+    input[..4].copy_from_slice(&SIGMA);
+    input[4..12].copy_from_slice(key);
+    input[12] = counter as u32;
+    input[13] = (counter >> 32) as u32;
+    input[14] = stream as u32;
+    input[15] = (stream >> 32) as u32;
+    let mut state = input;
+    for _ in 0..rounds / 2 {
+        quarter_round(&mut state, 0, 4, 8, 12);
+        quarter_round(&mut state, 1, 5, 9, 13);
+        quarter_round(&mut state, 2, 6, 10, 14);
+        quarter_round(&mut state, 3, 7, 11, 15);
+        quarter_round(&mut state, 0, 5, 10, 15);
+        quarter_round(&mut state, 1, 6, 11, 12);
+        quarter_round(&mut state, 2, 7, 8, 13);
+        quarter_round(&mut state, 3, 4, 9, 14);
+    }
+    for (word, original) in state.iter_mut().zip(input.iter()) {
+        *word = word.wrapping_add(*original);
+    }
+    state
+}
+
+/// SplitMix64 step used to expand a 64-bit seed into a ChaCha key.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Complete, serializable position of a [`ChaCha8Rng`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RngState {
+    pub key: [u32; 8],
+    pub stream: u64,
+    /// Absolute position in 32-bit words from the start of the stream.
+    pub word_pos: u64,
+}
+
+impl RngState {
+    /// Encodes the state as 48 little-endian bytes.
+    pub fn to_bytes(&self) -> [u8; RNG_STATE_LEN] {
+        let mut bytes = [0u8; RNG_STATE_LEN];
+        for (idx, word) in self.key.iter().enumerate() {
+            let offset = idx * 4;
+            bytes[offset..offset + 4].copy_from_slice(&word.to_le_bytes());
+        }
+        bytes[32..40].copy_from_slice(&self.stream.to_le_bytes());
+        bytes[40..48].copy_from_slice(&self.word_pos.to_le_bytes());
+        bytes
+    }
+
+    /// Decodes a state previously produced by [`RngState::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> CoreResult<Self> {
+        if bytes.len() != RNG_STATE_LEN {
+            return Err(DreamError::Validation(format!(
+                "RNG state must be {} bytes, got {}",
+                RNG_STATE_LEN,
+                bytes.len()
+            )));
+        }
+        let mut key = [0u32; 8];
+        for (idx, word) in key.iter_mut().enumerate() {
+            let offset = idx * 4;
+            *word = u32::from_le_bytes([
+                bytes[offset],
+                bytes[offset + 1],
+                bytes[offset + 2],
+                bytes[offset + 3],
+            ]);
+        }
+        let mut stream = [0u8; 8];
+        stream.copy_from_slice(&bytes[32..40]);
+        let mut word_pos = [0u8; 8];
+        word_pos.copy_from_slice(&bytes[40..48]);
+        Ok(Self {
+            key,
+            stream: u64::from_le_bytes(stream),
+            word_pos: u64::from_le_bytes(word_pos),
+        })
+    }
+
+    /// Converts the state into a JSON object; 64-bit fields are written as hex strings.
+    pub fn to_json(&self) -> JsonValue {
+        let key: Vec<u32> = self.key.to_vec();
+        JsonValue::object()
+            .with("key", key)
+            .with("stream", format!("{:016x}", self.stream))
+            .with("word_pos", format!("{:016x}", self.word_pos))
+    }
+
+    /// Rebuilds a state from the JSON produced by [`RngState::to_json`].
+    pub fn from_json(value: &JsonValue) -> CoreResult<Self> {
+        let invalid = |field: &str| DreamError::Config(format!("invalid RNG state `{}`", field));
+        let words = value
+            .get("key")
+            .and_then(JsonValue::as_array)
+            .filter(|items| items.len() == 8)
+            .ok_or_else(|| invalid("key"))?;
+        let mut key = [0u32; 8];
+        for (slot, item) in key.iter_mut().zip(words) {
+            *slot = item
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| invalid("key"))?;
+        }
+        let hex = |field: &str| {
+            value
+                .get(field)
+                .and_then(JsonValue::as_str)
+                .and_then(|s| u64::from_str_radix(s, 16).ok())
+                .ok_or_else(|| invalid(field))
+        };
+        Ok(Self {
+            key,
+            stream: hex("stream")?,
+            word_pos: hex("word_pos")?,
+        })
+    }
+}
+
+/// ChaCha8 generator with 64-bit block counter and 64-bit stream selector.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChaCha8Rng {
+    key: [u32; 8],
+    stream: u64,
+    /// Counter of the next block to generate.
+    counter: u64,
+    buffer: [u32; BLOCK_WORDS],
+    index: usize,
+}
+
+impl ChaCha8Rng {
+    /// Creates a generator from an explicit 256-bit key on stream 0.
+    pub fn from_key(key: [u32; 8]) -> Self {
+        Self {
+            key,
+            stream: 0,
+            counter: 0,
+            buffer: [0; BLOCK_WORDS],
+            index: BLOCK_WORDS,
+        }
+    }
+
+    /// Creates a generator whose key is expanded from `seed` with SplitMix64.
+    pub fn seed_from_u64(seed: u64) -> Self {
+        let mut sm = seed;
+        let mut key = [0u32; 8];
+        for pair in key.chunks_mut(2) {
+            let value = splitmix64(&mut sm);
+            pair[0] = value as u32;
+            pair[1] = (value >> 32) as u32;
+        }
+        Self::from_key(key)
+    }
+
+    /// Returns a generator sharing this key but positioned at the start of `stream`.
+    ///
+    /// Stream `u64::MAX` is reserved for [`ChaCha8Rng::split`] key derivation.
+    pub fn with_stream(&self, stream: u64) -> Self {
+        let mut rng = Self::from_key(self.key);
+        rng.stream = stream;
+        rng
+    }
+
+    /// Derives an independent child generator identified by `tag`.
+    ///
+    /// The child depends only on this generator's key and `tag`, never on how
+    /// many values have been drawn, so `rng.split(epoch)` or `rng.split(worker)`
+    /// yields the same stream regardless of call order. Children can be split
+    /// again to build per-epoch, per-worker hierarchies.
+    pub fn split(&self, tag: u64) -> Self {
+        let block = chacha_block(&self.key, tag, SPLIT_STREAM ^ self.stream, CHACHA_ROUNDS);
+        let mut key = [0u32; 8];
+        key.copy_from_slice(&block[..8]);
+        Self::from_key(key)
+    }
+
+    /// Returns the stream identifier.
+    pub fn stream(&self) -> u64 {
+        self.stream
+    }
+
+    /// Returns the absolute position in 32-bit words within the current stream.
+    pub fn word_pos(&self) -> u64 {
+        if self.index >= BLOCK_WORDS {
+            self.counter.wrapping_mul(BLOCK_WORDS as u64)
+        } else {
+            self.counter
+                .wrapping_sub(1)
+                .wrapping_mul(BLOCK_WORDS as u64)
+                .wrapping_add(self.index as u64)
+        }
+    }
+
+    /// Repositions the generator at an absolute word offset in O(1).
+    pub fn set_word_pos(&mut self, word_pos: u64) {
+        let block = word_pos / BLOCK_WORDS as u64;
+        let offset = (word_pos % BLOCK_WORDS as u64) as usize;
+        self.counter = block;
+        self.index = BLOCK_WORDS;
+        if offset != 0 {
+            self.refill();
+            self.index = offset;
+        }
+    }
+
+    /// Skips `blocks` ChaCha blocks (16 words each) without generating them.
+    pub fn jump(&mut self, blocks: u64) {
+        let target = self
+            .word_pos()
+            .wrapping_add(blocks.wrapping_mul(BLOCK_WORDS as u64));
+        self.set_word_pos(target);
+    }
+
+    /// Captures the full generator position for serialization.
+    pub fn state(&self) -> RngState {
+        RngState {
+            key: self.key,
+            stream: self.stream,
+            word_pos: self.word_pos(),
+        }
+    }
+
+    /// Restores a generator from a captured [`RngState`].
+    pub fn from_state(state: &RngState) -> Self {
+        let mut rng = Self::from_key(state.key);
+        rng.stream = state.stream;
+        rng.set_word_pos(state.word_pos);
+        rng
+    }
+
+    fn refill(&mut self) {
+        self.buffer = chacha_block(&self.key, self.counter, self.stream, CHACHA_ROUNDS);
+        self.counter = self.counter.wrapping_add(1);
+        self.index = 0;
+    }
+}
+
+impl RngCore for ChaCha8Rng {
+    fn next_u32(&mut self) -> u32 {
+        if self.index >= BLOCK_WORDS {
+            self.refill();
+        }
+        let value = self.buffer[self.index];
+        self.index += 1;
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chacha20_block_matches_rfc7539_vector() {
+        let mut key = [0u32; 8];
+        for (idx, word) in key.iter_mut().enumerate() {
+            let base = (idx * 4) as u32;
+            *word = u32::from_le_bytes([
+                base as u8,
+                (base + 1) as u8,
+                (base + 2) as u8,
+                (base + 3) as u8,
+            ]);
+        }
+        let counter = 1u64 | (0x0900_0000u64 << 32);
+        let stream = 0x4a00_0000u64;
+        let block = chacha_block(&key, counter, stream, 20);
+        assert_eq!(
+            &block[..4],
+            &[0xe4e7_f110, 0x1559_3bd1, 0x1fdd_0f50, 0xc471_20a3]
+        );
+    }
+
+    #[test]
+    fn seeded_generators_are_reproducible() {
+        let mut a = ChaCha8Rng::seed_from_u64(42);
+        let mut b = ChaCha8Rng::seed_from_u64(42);
+        let mut c = ChaCha8Rng::seed_from_u64(43);
+        let seq_a: Vec<u32> = (0..40).map(|_| a.next_u32()).collect();
+        let seq_b: Vec<u32> = (0..40).map(|_| b.next_u32()).collect();
+        let seq_c: Vec<u32> = (0..40).map(|_| c.next_u32()).collect();
+        assert_eq!(seq_a, seq_b);
+        assert_ne!(seq_a, seq_c);
+    }
+
+    #[test]
+    fn jump_matches_sequential_draws() {
+        let mut sequential = ChaCha8Rng::seed_from_u64(7);
+        for _ in 0..(3 * BLOCK_WORDS + 5) {
+            sequential.next_u32();
+        }
+        let mut jumped = ChaCha8Rng::seed_from_u64(7);
+        for _ in 0..5 {
+            jumped.next_u32();
+        }
+        jumped.jump(3);
+        assert_eq!(jumped.word_pos(), sequential.word_pos());
+        assert_eq!(jumped.next_u64(), sequential.next_u64());
+    }
+
+    #[test]
+    fn split_is_independent_of_parent_position() {
+        let root = ChaCha8Rng::seed_from_u64(9);
+        let mut advanced = root.clone();
+        for _ in 0..100 {
+            advanced.next_u32();
+        }
+        let mut child_a = root.split(3);
+        let mut child_b = advanced.split(3);
+        let mut sibling = root.split(4);
+        let first = child_a.next_u64();
+        assert_eq!(first, child_b.next_u64());
+        assert_ne!(first, sibling.next_u64());
+        assert_ne!(root.split(3), root.with_stream(3));
+    }
+
+    #[test]
+    fn state_round_trips_through_bytes_and_json() {
+        let mut rng = ChaCha8Rng::seed_from_u64(11).with_stream(5);
+        for _ in 0..21 {
+            rng.next_u32();
+        }
+        let state = rng.state();
+        assert_eq!(RngState::from_bytes(&state.to_bytes()).unwrap(), state);
+        assert_eq!(RngState::from_json(&state.to_json()).unwrap(), state);
+        let mut restored = ChaCha8Rng::from_state(&state);
+        assert_eq!(restored.next_u64(), rng.next_u64());
+    }
+
+    #[test]
+    fn unit_and_bounded_draws_stay_in_range() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        for _ in 0..1000 {
+            let f = rng.next_f32();
+            assert!((0.0..1.0).contains(&f));
+            assert!(rng.next_below(7) < 7);
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! Minimal TOML reader for configuration files.
+//!
+//! Specification references:
+//! - `cognitive-research-hub/spec.md`
+//! - `cognitive-research-hub/core/spec.md`
+//! - `cognitive-research-hub/core/src/utils/spec.md`
+//!
+//! Supports the subset used by the shipped configs: comments, `[table]` and
+//! dotted `[a.b]` headers, bare or quoted keys, and scalar values (strings,
+//! numbers, booleans) plus single-line arrays of scalars. Documents are
+//! returned as a [`JsonValue`] tree so callers share one accessor API.
+
+use crate::error::{CoreResult, DreamError};
+use crate::utils::JsonValue;
+
+/// Parses a TOML document into an insertion-ordered [`JsonValue`] object.
+pub fn parse_toml(text: &str) -> CoreResult<JsonValue> {
+    let mut root = JsonValue::object();
+    let mut current: Vec<String> = Vec::new();
+    for (idx, raw) in text.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = strip_comment(raw).trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[') {
+            let header = header
+                .strip_suffix(']')
+                .ok_or_else(|| error(line_no, "unterminated table header"))?;
+            current = header
+                .split('.')
+                .map(|part| parse_key(part.trim(), line_no))
+                .collect::<CoreResult<_>>()?;
+            table_mut(&mut root, &current, line_no)?;
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error(line_no, "expected `key = value`"))?;
+        let key = parse_key(key.trim(), line_no)?;
+        let value = parse_value(value.trim(), line_no)?;
+        let table = table_mut(&mut root, &current, line_no)?;
+        if table.iter().any(|(existing, _)| *existing == key) {
+            return Err(error(line_no, &format!("duplicate key `{}`", key)));
+        }
+        table.push((key, value));
+    }
+    Ok(root)
+}
+
+fn error(line: usize, msg: &str) -> DreamError {
+    DreamError::Config(format!("TOML parse error on line {}: {}", line, msg))
+}
+
+/// Removes a trailing `#` comment that is not inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (idx, ch) in line.char_indices() {
+        match quote {
+            Some(q) => {
+                if escaped {
+                    escaped = false;
+                } else if ch == '\\' && q == '"' {
+                    escaped = true;
+                } else if ch == q {
+                    quote = None;
+                }
+            }
+            None => match ch {
+                '"' | '\'' => quote = Some(ch),
+                '#' => return &line[..idx],
+                _ => {}
+            },
+        }
+    }
+    line
+}
+
+fn parse_key(key: &str, line: usize) -> CoreResult<String> {
+    if key.starts_with('"') || key.starts_with('\'') {
+        return parse_string(key, line);
+    }
+    let valid = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(key.to_string())
+    } else {
+        Err(error(line, &format!("invalid key `{}`", key)))
+    }
+}
+
+fn parse_string(text: &str, line: usize) -> CoreResult<String> {
+    if let Some(inner) = text
+        .strip_prefix('\'')
+        .and_then(|rest| rest.strip_suffix('\''))
+    {
+        return Ok(inner.to_string());
+    }
+    let inner = text
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .ok_or_else(|| error(line, "unterminated string"))?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            _ => return Err(error(line, "unsupported escape sequence")),
+        }
+    }
+    Ok(out)
+}
+
+fn parse_value(text: &str, line: usize) -> CoreResult<JsonValue> {
+    match text {
+        "" => Err(error(line, "missing value")),
+        "true" => Ok(JsonValue::Bool(true)),
+        "false" => Ok(JsonValue::Bool(false)),
+        _ if text.starts_with('"') || text.starts_with('\'') => {
+            parse_string(text, line).map(JsonValue::String)
+        }
+        _ if text.starts_with('[') => {
+            let inner = text
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+                .ok_or_else(|| error(line, "arrays must be closed on the same line"))?;
+            split_array(inner)
+                .into_iter()
+                .map(|item| item.trim())
+                .filter(|item| !item.is_empty())
+                .map(|item| parse_value(item, line))
+                .collect::<CoreResult<Vec<_>>>()
+                .map(JsonValue::Array)
+        }
+        _ => {
+            let cleaned: String = text.chars().filter(|&c| c != '_').collect();
+            cleaned
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .map(JsonValue::Number)
+                .ok_or_else(|| error(line, &format!("invalid value `{}`", text)))
+        }
+    }
+}
+
+/// Splits array items on commas that are outside quoted strings.
+fn split_array(inner: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    for (idx, ch) in inner.char_indices() {
+        match quote {
+            Some(q) if ch == q => quote = None,
+            Some(_) => {}
+            None => match ch {
+                '"' | '\'' => quote = Some(ch),
+                ',' => {
+                    items.push(&inner[start..idx]);
+                    start = idx + 1;
+                }
+                _ => {}
+            },
+        }
+    }
+    items.push(&inner[start..]);
+    items
+}
+
+/// Walks (creating as needed) the nested table at `path`.
+fn table_mut<'a>(
+    root: &'a mut JsonValue,
+    path: &[String],
+    line: usize,
+) -> CoreResult<&'a mut Vec<(String, JsonValue)>> {
+    let mut node = root;
+    for part in path {
+        let JsonValue::Object(fields) = node else {
+            return Err(error(line, &format!("`{}` is not a table", part)));
+        };
+        let pos = match fields.iter().position(|(key, _)| key == part) {
+            Some(pos) => pos,
+            None => {
+                fields.push((part.clone(), JsonValue::object()));
+                fields.len() - 1
+            }
+        };
+        node = &mut fields[pos].1;
+    }
+    match node {
+        JsonValue::Object(fields) => Ok(fields),
+        _ => Err(error(line, "key path is not a table")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tables_scalars_and_arrays() {
+        let doc = parse_toml(
+            "# header\nalpha = 0.5\nname = \"dream # one\" # trailing\n\n[schedule]\nkind = 'step'\nperiod = 1_000\nflags = [true, false]\n\n[a.b]\nx = -2\n",
+        )
+        .unwrap();
+        assert_eq!(doc.get("alpha").and_then(JsonValue::as_f64), Some(0.5));
+        assert_eq!(
+            doc.get("name").and_then(JsonValue::as_str),
+            Some("dream # one")
+        );
+        let schedule = doc.get("schedule").unwrap();
+        assert_eq!(
+            schedule.get("kind").and_then(JsonValue::as_str),
+            Some("step")
+        );
+        assert_eq!(
+            schedule.get("period").and_then(JsonValue::as_u64),
+            Some(1000)
+        );
+        assert_eq!(
+            schedule
+                .get("flags")
+                .and_then(JsonValue::as_array)
+                .map(|a| a.len()),
+            Some(2)
+        );
+        let nested = doc.get("a").and_then(|a| a.get("b")).unwrap();
+        assert_eq!(nested.get("x").and_then(JsonValue::as_f64), Some(-2.0));
+    }
+
+    #[test]
+    fn rejects_malformed_documents() {
+        assert!(parse_toml("alpha").is_err());
+        assert!(parse_toml("alpha = ").is_err());
+        assert!(parse_toml("alpha = 1\nalpha = 2").is_err());
+        assert!(parse_toml("[open").is_err());
+        assert!(parse_toml("value = nope").is_err());
+    }
+}
@@ -275,3 +275,29 @@ fn ums_compression_round_trip_within_tolerance() {
 >>>>>>> theirs
 =======
 >>>>>>> theirs
+
+#[test]
+fn ums_file_round_trip_supports_streaming_and_indexed_reads() {
+    use chromatic_core::bridge::{read_ums_file, write_ums_file, UmsFile, UnifiedModalitySpace};
+
+    let batch: Vec<UnifiedModalitySpace> = [Shape2D::new(2, 3), Shape2D::new(3, 4)]
+        .iter()
+        .map(|&shape| {
+            let chromatic = make_gradient_tensor(shape);
+            let spectral = encode_to_spectral(&chromatic);
+            project_to_ums(&chromatic, &spectral)
+        })
+        .collect();
+
+    let path = std::env::temp_dir().join(format!("bridge_tests_{}.ums", std::process::id()));
+    write_ums_file(&path, &batch).expect("write .ums file");
+    let streamed: Vec<UnifiedModalitySpace> = read_ums_file(&path).expect("read .ums file");
+    let mut indexed = UmsFile::<UnifiedModalitySpace>::open(&path).expect("open .ums file");
+    assert_eq!(indexed.len(), batch.len());
+    let second = indexed.get(1).expect("read record 1");
+    drop(indexed);
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(streamed, batch);
+    assert_eq!(second.as_ref(), Some(&batch[1]));
+}
@@ -6,7 +6,7 @@
 //! - `cognitive-research-hub/core/tests/spec.md`
 //! - `cognitive-research-hub/core/tests/diagnostics_tests/spec.md`
 
-use chromatic_core::diagnostics::{self, metrics};
+use chromatic_core::diagnostics::{self, metrics, visual};
 use chromatic_core::tensor::{rgb_to_hsl, ChromaticTensor, Shape2D, SpectralTensor};
 
 fn build_chromatic(fill: [f32; 3], delta: [f32; 3]) -> (ChromaticTensor, ChromaticTensor) {
@@ -160,3 +160,85 @@ fn metrics_snapshot_determinism_checks_equality() {
     };
     assert!(metrics::validate_determinism(&snap_a, &snap_b));
 }
+
+#[test]
+fn energy_audit_holds_across_bridge_round_trip() {
+    let (a, b) = build_chromatic([0.2, 0.3, 0.4], [0.05, -0.02, 0.03]);
+    let report = metrics::EnergyAudit::new(metrics::EnergyNorm::L1)
+        .strict(true)
+        .run(&[a, b])
+        .expect("L1 energy is conserved by the bridge");
+    assert_eq!(report.samples.len(), 2);
+    assert!(report.batch_delta_db.abs() < metrics::ENERGY_TOLERANCE_DB);
+    assert!(report.passed());
+}
+
+fn draw_scene() -> (visual::ImageBuffer, visual::SvgDocument) {
+    let mut image = visual::ImageBuffer::new(64, 48, visual::WHITE);
+    let mut svg = visual::SvgDocument::new(64, 48);
+    let points: Vec<(f32, f32)> = (0..16)
+        .map(|i| (4.0 + 3.7 * i as f32, 24.0 + 12.0 * (i as f32 * 0.6).sin()))
+        .collect();
+    let pixels: Vec<(i32, i32)> = points
+        .iter()
+        .map(|&(x, y)| (visual::quantize_coord(x), visual::quantize_coord(y)))
+        .collect();
+    let accent = visual::quantize_rgb([0.9, 0.35, 0.1]);
+    image.draw_polyline(&pixels, accent);
+    image.fill_circle(32, 24, 3, visual::BLACK);
+    image.draw_text(2, 2, "dE 0.5", visual::BLACK, 1);
+    svg.polyline(&points, visual::Style::stroke(accent, 1.0))
+        .circle(32.0, 24.0, 3.0, visual::Style::fill(visual::BLACK))
+        .text(
+            2.0,
+            9.0,
+            7.0,
+            visual::TextAnchor::Start,
+            visual::BLACK,
+            "dE 0.5",
+        );
+    (image, svg)
+}
+
+#[test]
+fn visual_backend_is_byte_identical_across_runs() {
+    let (image_a, svg_a) = draw_scene();
+    let (image_b, svg_b) = draw_scene();
+    let png = image_a.to_png().unwrap();
+    assert_eq!(png, image_b.to_png().unwrap());
+    assert_eq!(svg_a.to_svg_string(), svg_b.to_svg_string());
+
+    let dir = std::env::temp_dir().join(format!("visual_backend_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    image_a.save_png(dir.join("scene.png")).unwrap();
+    svg_a.save(dir.join("scene.svg")).unwrap();
+    assert_eq!(std::fs::read(dir.join("scene.png")).unwrap(), png);
+    assert_eq!(
+        std::fs::read_to_string(dir.join("scene.svg")).unwrap(),
+        svg_b.to_svg_string()
+    );
+    assert_eq!(&png[..8], &visual::PNG_SIGNATURE);
+    std::fs::remove_dir_all(&dir).ok();
+}
+
+#[test]
+fn dashboard_exports_one_self_contained_html_file() {
+    let (a, b) = build_chromatic([0.6, 0.3, 0.2], [0.05, 0.0, -0.05]);
+    let snapshot = visual::DiagnosticsSnapshot {
+        title: "archived run".to_string(),
+        spectra: vec![build_spectral(), build_spectral()],
+        tensors: vec![a, b],
+        ..Default::default()
+    };
+    let panel = visual::compose_diagnostics_dashboard(&snapshot).unwrap();
+    let dir = std::env::temp_dir().join(format!("visual_dashboard_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("report.html");
+    visual::export_visual_report(&path, &panel).unwrap();
+    let html = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(html, panel.to_html().unwrap());
+    assert!(html.contains("<h2>Metrics snapshot</h2>"));
+    assert!(html.contains("data:image/png;base64,"));
+    assert!(!html.contains("<script"));
+    std::fs::remove_dir_all(&dir).ok();
+}
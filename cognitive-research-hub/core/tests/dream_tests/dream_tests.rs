@@ -7,10 +7,12 @@
 
 use chromatic_core::{
     dream::{
-        add_dream_to_pool, dream_cycle, evaluate_dream, generate_dream, purge_stale_entries,
-        retrieve_similar, DreamEntry, SimpleDreamPool,
+        add_dream_to_pool, dream_cycle, dream_cycle_masked, evaluate_dream, generate_dream,
+        purge_stale_entries, retrieve_similar, DreamArchive, DreamConfig, DreamEntry, DreamMask,
+        MaskRegion, SimpleDreamPool, TelemetryRecorder,
     },
     tensor::{map_rgb_inplace, ChromaticTensor, Shape2D},
+    utils::ChaCha8Rng,
     Fx,
 };
 
@@ -26,8 +28,9 @@ fn uniform_tensor(value: Fx, shape: Shape2D) -> ChromaticTensor {
 fn dream_cycle_populates_high_coherence_entries() {
     let shape = Shape2D::new(3, 3);
     let target = uniform_tensor(0.45, shape);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
     let mut pool = SimpleDreamPool::new(6, 0.4);
-    dream_cycle(&target, &mut pool, 8);
+    dream_cycle(&target, &mut pool, 8, &mut rng);
     assert!(pool.len() > 0, "expected entries in pool");
     for entry in pool.entries() {
         assert!(
@@ -41,10 +44,11 @@ fn dream_cycle_populates_high_coherence_entries() {
 fn retrieve_similar_respects_limit_and_ordering() {
     let shape = Shape2D::new(2, 3);
     let target = uniform_tensor(0.35, shape);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
     let mut pool = SimpleDreamPool::new(8, 0.2);
-    dream_cycle(&target, &mut pool, 6);
+    dream_cycle(&target, &mut pool, 6, &mut rng);
 
-    let mut extra = generate_dream(&target, 0.3);
+    let mut extra = generate_dream(&target, 0.3, &mut rng);
     map_rgb_inplace(&mut extra, |v| (v + 0.1).min(1.0));
     let epoch = pool.next_epoch();
     let score = evaluate_dream(&extra, &target);
@@ -64,8 +68,9 @@ fn retrieve_similar_respects_limit_and_ordering() {
 fn purge_stale_entries_keeps_recent_epochs() {
     let shape = Shape2D::new(2, 2);
     let target = uniform_tensor(0.5, shape);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
     let mut pool = SimpleDreamPool::new(5, 0.0);
-    dream_cycle(&target, &mut pool, 4);
+    dream_cycle(&target, &mut pool, 4, &mut rng);
 
     let latest = pool.latest_epoch().unwrap_or(0);
     purge_stale_entries(&mut pool, 0);
@@ -73,3 +78,80 @@ fn purge_stale_entries_keeps_recent_epochs() {
         assert_eq!(entry.epoch, latest);
     }
 }
+
+#[test]
+fn archived_pool_restores_identical_retrieval() {
+    let shape = Shape2D::new(3, 3);
+    let target = uniform_tensor(0.4, shape);
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    let mut pool = SimpleDreamPool::new(6, 0.2);
+    dream_cycle(&target, &mut pool, 6, &mut rng);
+
+    let dir = std::env::temp_dir().join(format!("dream_tests_archive_{}", std::process::id()));
+    let archive = DreamArchive::open(&dir).expect("archive opens");
+    archive.snapshot(&pool).expect("snapshot written");
+    let mut restored = archive.restore().expect("snapshot restored");
+    std::fs::remove_dir_all(&dir).ok();
+
+    assert_eq!(restored.coherence_threshold(), pool.coherence_threshold());
+    let before: Vec<Vec<Fx>> = retrieve_similar(&pool, &target, 4)
+        .iter()
+        .map(|t| t.rgb.clone())
+        .collect();
+    let after: Vec<Vec<Fx>> = retrieve_similar(&restored, &target, 4)
+        .iter()
+        .map(|t| t.rgb.clone())
+        .collect();
+    assert_eq!(before, after);
+    assert_eq!(restored.next_epoch(), pool.next_epoch());
+}
+
+#[test]
+fn masked_cycle_preserves_protected_cells() {
+    let shape = Shape2D::new(4, 4);
+    let mut target = uniform_tensor(0.3, shape);
+    for cell in 0..shape.cell_count() {
+        target.rgb[cell * 3] = 0.2 + 0.05 * cell as Fx;
+    }
+    let roi: Vec<bool> = (0..shape.cell_count()).map(|cell| cell % 4 < 2).collect();
+    let mask = DreamMask::from_binary(shape, &roi, MaskRegion::Inside).expect("valid mask");
+    let mut pool = SimpleDreamPool::new(6, 0.0);
+    let mut recorder = TelemetryRecorder::new();
+    let mut rng = ChaCha8Rng::seed_from_u64(42);
+    dream_cycle_masked(
+        &target,
+        &mut pool,
+        6,
+        &DreamConfig::default(),
+        &mask,
+        &mut rng,
+        &mut recorder,
+    )
+    .expect("mask matches the target");
+
+    for entry in pool.entries() {
+        for (cell, &inside) in roi.iter().enumerate() {
+            let range = cell * 3..cell * 3 + 3;
+            if !inside {
+                assert_eq!(entry.tensor.rgb[range.clone()], target.rgb[range]);
+            }
+        }
+    }
+    for telemetry in &recorder.epochs {
+        let regions = telemetry.regions.expect("masked cycles report regions");
+        assert_eq!(regions.outside_score, 1.0);
+        assert!(regions.inside_score <= regions.outside_score);
+    }
+
+    let other = uniform_tensor(0.3, Shape2D::new(2, 2));
+    let mismatched = dream_cycle_masked(
+        &other,
+        &mut pool,
+        1,
+        &DreamConfig::default(),
+        &mask,
+        &mut rng,
+        &mut recorder,
+    );
+    assert!(mismatched.is_err());
+}